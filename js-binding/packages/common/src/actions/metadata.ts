@@ -671,7 +671,7 @@ export async function createMasterEdition(
     {
       pubkey: toPublicKey(metadataAccount),
       isSigner: false,
-      isWritable: false,
+      isWritable: true,
     },
 
     {
@@ -134,6 +134,38 @@ pub enum AuctionError {
     /// Gap tick size percentage must be between 0 and 100
     #[error("Gap tick size percentage must be between 0 and 100")]
     InvalidGapTickSizePercentage,
+
+    /// This raffle has no room for more entries
+    #[error("This raffle has no room for more entries")]
+    RaffleFull,
+
+    /// This auction is not a raffle
+    #[error("This auction is not a raffle")]
+    NotARaffle,
+
+    /// A raffle can only be ended by drawing its winners
+    #[error("A raffle can only be ended by drawing its winners")]
+    MustDrawRaffle,
+
+    /// This raffle is still taking entries
+    #[error("This raffle is still taking entries")]
+    RaffleStillOpen,
+
+    /// A raffle needs an end time, at least one winner and room for at least as many entries as winners
+    #[error("A raffle needs an end time, at least one winner and room for at least as many entries as winners")]
+    InvalidRaffleSettings,
+
+    /// Raffle entries can't be withdrawn while the winners are waiting to be drawn
+    #[error("Raffle entries can't be withdrawn while the winners are waiting to be drawn")]
+    RaffleDrawPending,
+
+    /// This raffle wasn't drawn in time and can no longer be drawn
+    #[error("This raffle wasn't drawn in time and can no longer be drawn")]
+    RaffleDrawExpired,
+
+    /// Slot hashes sysvar account is invalid
+    #[error("Slot hashes sysvar account is invalid")]
+    InvalidSlotHashesSysvar,
 }
 
 impl PrintProgramError for AuctionError {
@@ -7,8 +7,10 @@ use solana_program::{
 };
 
 pub use crate::processor::{
-    cancel_bid::CancelBidArgs, claim_bid::ClaimBidArgs, create_auction::CreateAuctionArgs,
-    end_auction::EndAuctionArgs, place_bid::PlaceBidArgs, start_auction::StartAuctionArgs,
+    cancel_auction::CancelAuctionArgs, cancel_bid::CancelBidArgs, claim_bid::ClaimBidArgs,
+    create_auction::CreateAuctionArgs, create_raffle::CreateRaffleArgs,
+    draw_raffle::DrawRaffleArgs, end_auction::EndAuctionArgs, place_bid::PlaceBidArgs,
+    start_auction::StartAuctionArgs,
 };
 
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
@@ -73,6 +75,31 @@ pub enum AuctionInstruction {
     ///   11. `[]` System program
     ///   12. `[]` SPL Token Program
     PlaceBid(PlaceBidArgs),
+
+    /// Create a new raffle, an auction where every bid at or above the entry price is an entry and
+    /// the winners are drawn from the entries. Losing entries are refunded with CancelBid.
+    ///   0. `[signer]` The account creating the raffle, which is authorised to make changes.
+    ///   1. `[writable]` Uninitialized auction account.
+    ///   2. `[writable]` Uninitialized auction extended account.
+    ///   3. `[]` Rent sysvar
+    ///   4. `[]` System account
+    CreateRaffle(CreateRaffleArgs),
+
+    /// Draw the winners of a raffle that stopped taking entries by revealing the committed secret,
+    /// which also ends it. Winner #1 ends up at the position of the top bid of an English auction.
+    /// Must happen within RAFFLE_DRAW_WINDOW of the raffle closing.
+    ///   0. `[writable]` Auction account.
+    ///   1. `[]` Auction extended account.
+    ///   2. `[]` Clock sysvar
+    ///   3. `[]` SlotHashes sysvar
+    DrawRaffle(DrawRaffleArgs),
+
+    /// Cancel an auction that hasn't ended yet, or a raffle that wasn't drawn in time. All bids are
+    /// dropped so there are no winners and every bidder can reclaim their pot with CancelBid.
+    ///   0. `[signer]` The authority on the auction
+    ///   1. `[writable]` Auction account.
+    ///   2. `[]` Clock sysvar
+    CancelAuction(CancelAuctionArgs),
 }
 
 /// Creates an CreateAuction instruction.
@@ -354,3 +381,92 @@ pub fn claim_bid_instruction(
         data: AuctionInstruction::ClaimBid(args).try_to_vec().unwrap(),
     }
 }
+
+/// Creates an CreateRaffle instruction.
+pub fn create_raffle_instruction(
+    program_id: Pubkey,
+    creator_pubkey: Pubkey,
+    args: CreateRaffleArgs,
+) -> Instruction {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(creator_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: AuctionInstruction::CreateRaffle(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an DrawRaffle instruction.
+pub fn draw_raffle_instruction(program_id: Pubkey, args: DrawRaffleArgs) -> Instruction {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    let seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        args.resource.as_ref(),
+        EXTENDED.as_bytes(),
+    ];
+    let (auction_extended_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new_readonly(auction_extended_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        ],
+        data: AuctionInstruction::DrawRaffle(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an CancelAuction instruction.
+pub fn cancel_auction_instruction(
+    program_id: Pubkey,
+    authority_pubkey: Pubkey,
+    args: CancelAuctionArgs,
+) -> Instruction {
+    let seeds = &[
+        PREFIX.as_bytes(),
+        &program_id.as_ref(),
+        args.resource.as_ref(),
+    ];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(authority_pubkey, true),
+            AccountMeta::new(auction_pubkey, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: AuctionInstruction::CancelAuction(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
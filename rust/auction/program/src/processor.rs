@@ -2,24 +2,36 @@ use crate::errors::AuctionError;
 use arrayref::array_ref;
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, borsh::try_from_slice_unchecked, clock::UnixTimestamp,
-    entrypoint::ProgramResult, hash::Hash, msg, program_error::ProgramError, pubkey::Pubkey,
+    account_info::AccountInfo,
+    borsh::try_from_slice_unchecked,
+    clock::UnixTimestamp,
+    entrypoint::ProgramResult,
+    hash::{hashv, Hash},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
 };
 use std::{cell::Ref, cmp, mem};
 
 // Declare submodules, each contains a single handler for each instruction variant in the program.
+pub mod cancel_auction;
 pub mod cancel_bid;
 pub mod claim_bid;
 pub mod create_auction;
+pub mod create_raffle;
+pub mod draw_raffle;
 pub mod end_auction;
 pub mod place_bid;
 pub mod set_authority;
 pub mod start_auction;
 
 // Re-export submodules handlers + associated types for other programs to consume.
+pub use cancel_auction::*;
 pub use cancel_bid::*;
 pub use claim_bid::*;
 pub use create_auction::*;
+pub use create_raffle::*;
+pub use draw_raffle::*;
 pub use end_auction::*;
 pub use place_bid::*;
 pub use set_authority::*;
@@ -39,6 +51,9 @@ pub fn process_instruction(
         AuctionInstruction::PlaceBid(args) => place_bid(program_id, accounts, args),
         AuctionInstruction::SetAuthority => set_authority(program_id, accounts),
         AuctionInstruction::StartAuction(args) => start_auction(program_id, accounts, args),
+        AuctionInstruction::CreateRaffle(args) => create_raffle(program_id, accounts, args),
+        AuctionInstruction::DrawRaffle(args) => draw_raffle(program_id, accounts, args),
+        AuctionInstruction::CancelAuction(args) => cancel_auction(program_id, accounts, args),
     }
 }
 
@@ -61,6 +76,10 @@ pub enum PriceFloor {
 // NOTE: New research suggests u32s are used for vecs in borsh, not u64s, so the first extra 8 should be a 4
 // but for legacy reasons we leave it behind.
 pub const BASE_AUCTION_DATA_SIZE: usize = 32 + 32 + 9 + 9 + 9 + 9 + 1 + 32 + 1 + 8 + 8 + 8;
+
+/// How long after a raffle stops taking entries its winners may be drawn. Entries are locked in
+/// until then, after that the raffle can no longer be drawn and every entry can be refunded.
+pub const RAFFLE_DRAW_WINDOW: UnixTimestamp = 60 * 60 * 24 * 7;
 pub const BID_LENGTH: usize = 32 + 8;
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Debug)]
@@ -104,6 +123,8 @@ pub struct AuctionDataExtended {
     pub tick_size: Option<u64>,
     /// gap_tick_size_percentage - two decimal points
     pub gap_tick_size_percentage: Option<u8>,
+    /// Hash of the secret that draws the winners of a raffle, None for every other auction.
+    pub raffle_commitment: Option<Hash>,
 }
 
 impl AuctionDataExtended {
@@ -276,7 +297,30 @@ impl AuctionData {
         };
     }
 
+    /// The time by which a raffle has to be drawn, None for every other auction and for raffles
+    /// that haven't started yet.
+    pub fn raffle_draw_deadline(&self) -> Result<Option<UnixTimestamp>, ProgramError> {
+        match (&self.bid_state, self.ended_at) {
+            (BidState::Raffle { .. }, Some(end)) => Ok(Some(
+                end.checked_add(RAFFLE_DRAW_WINDOW)
+                    .ok_or(AuctionError::NumericalOverflowError)?,
+            )),
+            _ => Ok(None),
+        }
+    }
+
+    /// Raffle winners are only known once drawn, which is also what ends a raffle.
+    fn winners_known(&self) -> bool {
+        match self.bid_state {
+            BidState::Raffle { .. } => self.state == AuctionState::Ended,
+            _ => true,
+        }
+    }
+
     pub fn is_winner(&self, key: &Pubkey) -> Option<usize> {
+        if !self.winners_known() {
+            return None;
+        }
         let minimum = match self.price_floor {
             PriceFloor::MinimumPrice(min) => min[0],
             _ => 0,
@@ -293,6 +337,9 @@ impl AuctionData {
     }
 
     pub fn winner_at(&self, idx: usize) -> Option<Pubkey> {
+        if !self.winners_known() {
+            return None;
+        }
         self.bid_state.winner_at(idx)
     }
 
@@ -367,6 +414,7 @@ pub struct Bid(pub Pubkey, pub u64);
 pub enum BidState {
     EnglishAuction { bids: Vec<Bid>, max: usize },
     OpenEdition { bids: Vec<Bid>, max: usize },
+    Raffle { bids: Vec<Bid>, max: usize },
 }
 
 /// Bidding Implementations.
@@ -376,6 +424,9 @@ pub enum BidState {
 ///
 /// Open Edition: All bids are accepted, cancellations return money to the bidder and always
 /// succeed.
+///
+/// Raffle: every bid is an entry kept in arrival order until the draw, which moves the drawn
+/// entries to the end of the list where an English auction keeps its winners.
 impl BidState {
     pub fn new_english(n: usize) -> Self {
         BidState::EnglishAuction {
@@ -391,6 +442,13 @@ impl BidState {
         }
     }
 
+    pub fn new_raffle(n: usize) -> Self {
+        BidState::Raffle {
+            bids: vec![],
+            max: n,
+        }
+    }
+
     pub fn max_array_size_for(n: usize) -> usize {
         let mut real_max = n;
         if real_max < 8 {
@@ -522,6 +580,16 @@ impl BidState {
 
             // In an open auction, bidding simply succeeds.
             BidState::OpenEdition { bids, max } => Ok(()),
+
+            // Every raffle entry is kept, room for them is checked when placing the bid. Each bidder
+            // only gets one entry.
+            BidState::Raffle { ref mut bids, max } => {
+                if bids.iter().any(|b| b.0 == bid.0) {
+                    return Err(AuctionError::BidAlreadyActive.into());
+                }
+                bids.push(bid);
+                Ok(())
+            }
         }
     }
 
@@ -529,7 +597,8 @@ impl BidState {
     /// function simple no-ops.
     pub fn cancel_bid(&mut self, key: Pubkey) -> Result<(), ProgramError> {
         match self {
-            BidState::EnglishAuction { ref mut bids, max } => {
+            BidState::EnglishAuction { ref mut bids, max }
+            | BidState::Raffle { ref mut bids, max } => {
                 bids.retain(|b| b.0 != key);
                 Ok(())
             }
@@ -540,9 +609,18 @@ impl BidState {
         }
     }
 
+    /// Drops every bid, leaving no winners behind, used when an auction is cancelled.
+    pub fn cancel_all_bids(&mut self) {
+        match self {
+            BidState::EnglishAuction { ref mut bids, .. }
+            | BidState::OpenEdition { ref mut bids, .. }
+            | BidState::Raffle { ref mut bids, .. } => bids.clear(),
+        }
+    }
+
     pub fn amount(&self, index: usize) -> u64 {
         match self {
-            BidState::EnglishAuction { bids, max } => {
+            BidState::EnglishAuction { bids, max } | BidState::Raffle { bids, max } => {
                 if index >= 0 as usize && index < bids.len() {
                     return bids[bids.len() - index - 1].1;
                 } else {
@@ -559,7 +637,7 @@ impl BidState {
 
         match self {
             // Presense in the winner list is enough to check win state.
-            BidState::EnglishAuction { bids, max } => {
+            BidState::EnglishAuction { bids, max } | BidState::Raffle { bids, max } => {
                 match bids.iter().position(|bid| &bid.0 == key && bid.1 >= min) {
                     Some(val) => {
                         let zero_based_index = bids.len() - val - 1;
@@ -580,14 +658,16 @@ impl BidState {
 
     pub fn num_winners(&self) -> u64 {
         match self {
-            BidState::EnglishAuction { bids, max } => cmp::min(bids.len(), *max) as u64,
+            BidState::EnglishAuction { bids, max } | BidState::Raffle { bids, max } => {
+                cmp::min(bids.len(), *max) as u64
+            }
             BidState::OpenEdition { bids, max } => 0,
         }
     }
 
     pub fn num_possible_winners(&self) -> u64 {
         match self {
-            BidState::EnglishAuction { bids, max } => *max as u64,
+            BidState::EnglishAuction { bids, max } | BidState::Raffle { bids, max } => *max as u64,
             BidState::OpenEdition { bids, max } => 0,
         }
     }
@@ -595,7 +675,7 @@ impl BidState {
     /// Idea is to present #1 winner as index 0 to outside world with this method
     pub fn winner_at(&self, index: usize) -> Option<Pubkey> {
         match self {
-            BidState::EnglishAuction { bids, max } => {
+            BidState::EnglishAuction { bids, max } | BidState::Raffle { bids, max } => {
                 if index < *max && index < bids.len() {
                     let bid = &bids[bids.len() - index - 1];
                     Some(bids[bids.len() - index - 1].0)
@@ -606,6 +686,26 @@ impl BidState {
             BidState::OpenEdition { bids, max } => None,
         }
     }
+
+    /// Draws raffle winners with a partial Fisher-Yates shuffle, swapping winner #1 into the last
+    /// slot, winner #2 into the one before it and so on. Each roll hashes the previous one.
+    pub fn draw_raffle(&mut self, seed: Hash) -> ProgramResult {
+        match self {
+            BidState::Raffle { ref mut bids, max } => {
+                let mut roll = seed;
+                let entries = bids.len();
+                for i in 0..cmp::min(entries, *max) {
+                    roll = hashv(&[roll.as_ref(), &(i as u64).to_le_bytes()]);
+                    let remaining = entries - i;
+                    let pick =
+                        u64::from_le_bytes(*array_ref![roll.as_ref(), 0, 8]) % remaining as u64;
+                    bids.swap(pick as usize, remaining - 1);
+                }
+                Ok(())
+            }
+            _ => Err(AuctionError::NotARaffle.into()),
+        }
+    }
 }
 
 #[repr(C)]
@@ -0,0 +1,100 @@
+//! Cancels an auction that has not ended yet, or a raffle that wasn't drawn in time. All bids are
+//! dropped, so nobody wins and every bidder can take their money back out of their BidderPot with
+//! CancelBid.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionState},
+    utils::{assert_derivation, assert_owned_by, assert_signer},
+    PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CancelAuctionArgs {
+    /// The resource being auctioned. See AuctionData.
+    pub resource: Pubkey,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    authority: &'a AccountInfo<'b>,
+    auction: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        authority: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_signer(accounts.authority)?;
+    Ok(accounts)
+}
+
+pub fn cancel_auction<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: CancelAuctionArgs,
+) -> ProgramResult {
+    msg!("+ Processing CancelAuction");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+
+    if auction.authority != *accounts.authority.key {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+
+    // Once an auction is over its winners are owed their items, so it can't be cancelled anymore.
+    // A raffle that was never drawn in time has no winners though, so that one still can be.
+    let draw_expired = match auction.raffle_draw_deadline()? {
+        Some(deadline) => clock.unix_timestamp > deadline,
+        None => false,
+    };
+    if auction.state == AuctionState::Ended
+        || (auction.ended(clock.unix_timestamp)? && !draw_expired)
+    {
+        return Err(AuctionError::AuctionTransitionInvalid.into());
+    }
+
+    auction.bid_state.cancel_all_bids();
+
+    AuctionData {
+        ended_at: Some(clock.unix_timestamp),
+        state: auction.state.end()?,
+        ..auction
+    }
+    .serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    Ok(())
+}
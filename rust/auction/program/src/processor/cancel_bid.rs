@@ -3,6 +3,9 @@
 //! 1) The auction is still going on, in which case it is possible to cancel a bid at any time.
 //! 2) The auction has finished, but the bid did not win. This allows users to claim back their
 //!    funds from bid accounts.
+//!
+//! Raffles only allow 2) once drawn, entries can't be cancelled between the raffle closing and
+//! its draw unless the draw window passes without one.
 
 use crate::{
     errors::AuctionError,
@@ -179,6 +182,17 @@ pub fn cancel_bid(
         return Err(AuctionError::InvalidState.into());
     }
 
+    // Raffle entries are locked in from the moment the raffle stops taking entries until it is
+    // drawn, otherwise entries could be pulled once the field is known. If the draw window
+    // passes without a draw, entries can be refunded again.
+    if auction.state != AuctionState::Ended && auction.ended(clock.unix_timestamp)? {
+        if let Some(deadline) = auction.raffle_draw_deadline()? {
+            if clock.unix_timestamp <= deadline {
+                return Err(AuctionError::RaffleDrawPending.into());
+            }
+        }
+    }
+
     // Confirm we're looking at the real SPL account for this bidder.
     let bidder_pot = BidderPot::from_account_info(accounts.bidder_pot)?;
     if bidder_pot.bidder_pot != *accounts.bidder_pot_token.key {
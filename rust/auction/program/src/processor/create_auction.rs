@@ -156,6 +156,7 @@ pub fn create_auction(
         total_uncancelled_bids: 0,
         tick_size: args.tick_size,
         gap_tick_size_percentage: args.gap_tick_size_percentage,
+        raffle_commitment: None,
     }
     .serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
 
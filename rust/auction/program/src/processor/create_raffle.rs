@@ -0,0 +1,165 @@
+//! Creates a raffle, an auction where every bid at or above the entry price is an entry and the
+//! winners are drawn from the entries once the raffle closes. The authority commits to a secret
+//! here, before anyone can enter, and reveals it with DrawRaffle to pick the winners.
+
+use crate::{
+    errors::AuctionError,
+    processor::{
+        AuctionData, AuctionDataExtended, AuctionState, Bid, BidState, PriceFloor,
+        BASE_AUCTION_DATA_SIZE, MAX_AUCTION_DATA_EXTENDED_SIZE,
+    },
+    utils::{assert_derivation, create_or_allocate_account_raw},
+    EXTENDED, PREFIX,
+};
+
+use {
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::UnixTimestamp,
+        entrypoint::ProgramResult,
+        hash::Hash,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    std::mem,
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct CreateRaffleArgs {
+    /// How many entries are drawn as winners.
+    pub winners: usize,
+    /// How many entries the raffle has room for, this sizes the auction account.
+    pub max_entries: usize,
+    /// How long after starting the raffle stops taking entries. See AuctionData.
+    pub end_auction_at: UnixTimestamp,
+    /// Token mint for the SPL token used for entering.
+    pub token_mint: Pubkey,
+    /// Authority
+    pub authority: Pubkey,
+    /// The resource being raffled. See AuctionData.
+    pub resource: Pubkey,
+    /// Price of an entry, bids below it are rejected.
+    pub entry_price: u64,
+    /// Hash of the 32 byte secret that DrawRaffle reveals.
+    pub commitment: Hash,
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    payer: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    system: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        payer: next_account_info(account_iter)?,
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        rent: next_account_info(account_iter)?,
+        system: next_account_info(account_iter)?,
+    };
+    Ok(accounts)
+}
+
+pub fn create_raffle(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreateRaffleArgs,
+) -> ProgramResult {
+    msg!("+ Processing CreateRaffle");
+    let accounts = parse_accounts(program_id, accounts)?;
+
+    if args.winners == 0 || args.max_entries < args.winners || args.end_auction_at <= 0 {
+        return Err(AuctionError::InvalidRaffleSettings.into());
+    }
+
+    let auction_bump = assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            &args.resource.to_bytes(),
+        ],
+    )?;
+
+    // Unlike an English auction no entry is ever pruned, so make room for all of them.
+    let auction_size = mem::size_of::<Bid>()
+        .checked_mul(args.max_entries)
+        .and_then(|size| size.checked_add(BASE_AUCTION_DATA_SIZE))
+        .ok_or(AuctionError::NumericalOverflowError)?;
+
+    create_or_allocate_account_raw(
+        *program_id,
+        accounts.auction,
+        accounts.rent,
+        accounts.system,
+        accounts.payer,
+        auction_size,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            &args.resource.to_bytes(),
+            &[auction_bump],
+        ],
+    )?;
+
+    let auction_ext_bump = assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            &args.resource.to_bytes(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    create_or_allocate_account_raw(
+        *program_id,
+        accounts.auction_extended,
+        accounts.rent,
+        accounts.system,
+        accounts.payer,
+        MAX_AUCTION_DATA_EXTENDED_SIZE,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            &args.resource.to_bytes(),
+            EXTENDED.as_bytes(),
+            &[auction_ext_bump],
+        ],
+    )?;
+
+    AuctionDataExtended {
+        total_uncancelled_bids: 0,
+        tick_size: None,
+        gap_tick_size_percentage: None,
+        raffle_commitment: Some(args.commitment),
+    }
+    .serialize(&mut *accounts.auction_extended.data.borrow_mut())?;
+
+    AuctionData {
+        authority: args.authority,
+        bid_state: BidState::new_raffle(args.winners),
+        end_auction_at: Some(args.end_auction_at),
+        end_auction_gap: None,
+        ended_at: None,
+        last_bid: None,
+        price_floor: PriceFloor::MinimumPrice([args.entry_price, 0, 0, 0]),
+        state: AuctionState::create(),
+        token_mint: args.token_mint,
+    }
+    .serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    Ok(())
+}
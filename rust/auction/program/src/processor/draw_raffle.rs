@@ -0,0 +1,132 @@
+//! Draws the winners of a raffle that stopped taking entries and ends it. Anybody holding the
+//! secret committed to in CreateRaffle can draw, in practice that is the raffle's creator. The
+//! draw is seeded by the secret, the auction key and the most recent slot hash, so entrants can't
+//! influence it and the creator can't work out the winners before the entries are locked in. A
+//! raffle has to be drawn within RAFFLE_DRAW_WINDOW of closing, after that entries are refunded.
+
+use crate::{
+    errors::AuctionError,
+    processor::{AuctionData, AuctionDataExtended, AuctionState},
+    utils::{assert_derivation, assert_owned_by},
+    EXTENDED, PREFIX,
+};
+
+use {
+    arrayref::array_ref,
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        hash::{hash, hashv},
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::{self, Sysvar},
+    },
+};
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct DrawRaffleArgs {
+    /// The resource being raffled. See AuctionData.
+    pub resource: Pubkey,
+    /// The secret whose hash was committed to when creating the raffle.
+    pub secret: [u8; 32],
+}
+
+struct Accounts<'a, 'b: 'a> {
+    auction: &'a AccountInfo<'b>,
+    auction_extended: &'a AccountInfo<'b>,
+    clock_sysvar: &'a AccountInfo<'b>,
+    slot_hashes_sysvar: &'a AccountInfo<'b>,
+}
+
+fn parse_accounts<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<Accounts<'a, 'b>, ProgramError> {
+    let account_iter = &mut accounts.iter();
+    let accounts = Accounts {
+        auction: next_account_info(account_iter)?,
+        auction_extended: next_account_info(account_iter)?,
+        clock_sysvar: next_account_info(account_iter)?,
+        slot_hashes_sysvar: next_account_info(account_iter)?,
+    };
+    assert_owned_by(accounts.auction, program_id)?;
+    assert_owned_by(accounts.auction_extended, program_id)?;
+    if *accounts.slot_hashes_sysvar.key != sysvar::slot_hashes::id() {
+        return Err(AuctionError::InvalidSlotHashesSysvar.into());
+    }
+    Ok(accounts)
+}
+
+pub fn draw_raffle<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+    args: DrawRaffleArgs,
+) -> ProgramResult {
+    msg!("+ Processing DrawRaffle");
+    let accounts = parse_accounts(program_id, accounts)?;
+    let clock = Clock::from_account_info(accounts.clock_sysvar)?;
+
+    assert_derivation(
+        program_id,
+        accounts.auction,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        accounts.auction_extended,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            args.resource.as_ref(),
+            EXTENDED.as_bytes(),
+        ],
+    )?;
+
+    let mut auction = AuctionData::from_account_info(accounts.auction)?;
+    let auction_extended = AuctionDataExtended::from_account_info(accounts.auction_extended)?;
+
+    let commitment = auction_extended
+        .raffle_commitment
+        .ok_or(AuctionError::NotARaffle)?;
+    if hash(&args.secret) != commitment {
+        return Err(AuctionError::InvalidReveal.into());
+    }
+
+    if auction.state == AuctionState::Started && !auction.ended(clock.unix_timestamp)? {
+        return Err(AuctionError::RaffleStillOpen.into());
+    }
+
+    // Entrants have been able to take their entries back since the deadline passed.
+    if let Some(deadline) = auction.raffle_draw_deadline()? {
+        if clock.unix_timestamp > deadline {
+            return Err(AuctionError::RaffleDrawExpired.into());
+        }
+    }
+
+    // SlotHashes is a vector of (slot, hash) pairs, most recent first, after its u64 length.
+    let slot_hashes = accounts.slot_hashes_sysvar.data.borrow();
+    let recent_slot_hash = array_ref![slot_hashes, 16, 32];
+
+    auction.bid_state.draw_raffle(hashv(&[
+        &args.secret,
+        accounts.auction.key.as_ref(),
+        recent_slot_hash,
+    ]))?;
+
+    AuctionData {
+        ended_at: auction.ended_at.or(Some(clock.unix_timestamp)),
+        state: auction.state.end()?,
+        ..auction
+    }
+    .serialize(&mut *accounts.auction.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -100,6 +100,11 @@ pub fn end_auction<'a, 'b: 'a>(
         return Err(AuctionError::InvalidAuthority.into());
     }
 
+    // Raffles end when their winners are drawn.
+    if let BidState::Raffle { .. } = auction.bid_state {
+        return Err(AuctionError::MustDrawRaffle.into());
+    }
+
     // As long as it hasn't already ended.
     if auction.ended_at.is_some() {
         return Err(AuctionError::AuctionTransitionInvalid.into());
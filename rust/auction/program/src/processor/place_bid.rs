@@ -15,7 +15,8 @@ use borsh::try_to_vec_with_schema;
 use crate::{
     errors::AuctionError,
     processor::{
-        AuctionData, AuctionDataExtended, AuctionState, Bid, BidderMetadata, BidderPot, PriceFloor,
+        AuctionData, AuctionDataExtended, AuctionState, Bid, BidState, BidderMetadata, BidderPot,
+        PriceFloor, BASE_AUCTION_DATA_SIZE,
     },
     utils::{
         assert_derivation, assert_initialized, assert_owned_by, assert_signer,
@@ -138,6 +139,10 @@ pub fn place_bid<'r, 'b: 'r>(
 
     // Verify auction has not ended.
     if auction.ended(clock.unix_timestamp)? {
+        // Raffles stop taking entries here but only end once their winners are drawn.
+        if let BidState::Raffle { .. } = auction.bid_state {
+            return Err(AuctionError::InvalidState.into());
+        }
         auction.state = auction.state.end()?;
         auction.serialize(&mut *accounts.auction.data.borrow_mut())?;
         msg!("Auction ended!");
@@ -228,6 +233,15 @@ pub fn place_bid<'r, 'b: 'r>(
         return Err(AuctionError::InvalidState.into());
     }
 
+    // Raffles keep every entry, so the auction account must have room for one more.
+    if let BidState::Raffle { ref bids, .. } = auction.bid_state {
+        let capacity =
+            (accounts.auction.data_len() - BASE_AUCTION_DATA_SIZE) / mem::size_of::<Bid>();
+        if bids.len() >= capacity {
+            return Err(AuctionError::RaffleFull.into());
+        }
+    }
+
     let bump_authority_seeds = &[
         PREFIX.as_bytes(),
         program_id.as_ref(),
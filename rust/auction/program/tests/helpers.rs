@@ -9,8 +9,8 @@ use solana_sdk::{
 use spl_auction::{
     instruction,
     processor::{
-        CancelBidArgs, ClaimBidArgs, CreateAuctionArgs, EndAuctionArgs, PlaceBidArgs, PriceFloor,
-        StartAuctionArgs, WinnerLimit,
+        CancelAuctionArgs, CancelBidArgs, ClaimBidArgs, CreateAuctionArgs, CreateRaffleArgs,
+        DrawRaffleArgs, EndAuctionArgs, PlaceBidArgs, PriceFloor, StartAuctionArgs, WinnerLimit,
     },
 };
 
@@ -154,7 +154,91 @@ pub async fn create_auction(
                 winners: WinnerLimit::Capped(max_winners),
                 price_floor: PriceFloor::None([0u8; 32]),
                 gap_tick_size_percentage: Some(0),
-                tick_size: Some(0),
+                tick_size: None,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_raffle(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    resource: &Pubkey,
+    mint_keypair: &Pubkey,
+    winners: usize,
+    max_entries: usize,
+    end_auction_at: i64,
+    entry_price: u64,
+    commitment: Hash,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::create_raffle_instruction(
+            *program_id,
+            payer.pubkey(),
+            CreateRaffleArgs {
+                winners,
+                max_entries,
+                end_auction_at,
+                token_mint: *mint_keypair,
+                authority: payer.pubkey(),
+                resource: *resource,
+                entry_price,
+                commitment,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn draw_raffle(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    recent_blockhash: &Hash,
+    payer: &Keypair,
+    resource: &Pubkey,
+    secret: [u8; 32],
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::draw_raffle_instruction(
+            *program_id,
+            DrawRaffleArgs {
+                resource: *resource,
+                secret,
+            },
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        *recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+    Ok(())
+}
+
+pub async fn cancel_auction(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    recent_blockhash: &Hash,
+    payer: &Keypair,
+    resource: &Pubkey,
+) -> Result<(), TransportError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction::cancel_auction_instruction(
+            *program_id,
+            payer.pubkey(),
+            CancelAuctionArgs {
+                resource: *resource,
             },
         )],
         Some(&payer.pubkey()),
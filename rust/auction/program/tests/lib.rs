@@ -6,7 +6,8 @@ use solana_program_test::*;
 use solana_sdk::program_pack::Pack;
 use solana_sdk::{
     account::Account,
-    hash::Hash,
+    clock::Clock,
+    hash::{hash, Hash},
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::{Keypair, Signer},
@@ -19,6 +20,7 @@ use spl_auction::{
     processor::{
         process_instruction, AuctionData, AuctionState, Bid, BidState, BidderPot, CancelBidArgs,
         CreateAuctionArgs, PlaceBidArgs, PriceFloor, StartAuctionArgs, WinnerLimit,
+        RAFFLE_DRAW_WINDOW,
     },
     PREFIX,
 };
@@ -26,6 +28,78 @@ use std::mem;
 
 mod helpers;
 
+/// Generate bidders with tokens that can be used for testing, along with the SPL pots they
+/// deposit their bids into.
+async fn setup_bidders(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    auction_pubkey: &Pubkey,
+    mint: &Pubkey,
+    mint_manager: &Keypair,
+) -> Vec<(Keypair, Keypair, Pubkey)> {
+    let mut bidders = vec![];
+    for n in 0..5 {
+        // Bidder SPL Account, with Minted Tokens
+        let bidder = Keypair::new();
+        // PDA in the auction for the Bidder to deposit their funds to.
+        let auction_spl_pot = Keypair::new();
+
+        // Generate User SPL Wallet Account
+        helpers::create_token_account(
+            banks_client,
+            payer,
+            recent_blockhash,
+            &bidder,
+            mint,
+            &payer.pubkey(),
+        )
+        .await
+        .unwrap();
+
+        // Owner via pot PDA.
+        let (bid_pot_pubkey, pot_bump) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                auction_pubkey.as_ref(),
+                bidder.pubkey().as_ref(),
+            ],
+            program_id,
+        );
+
+        // Generate Auction SPL Pot to Transfer to.
+        helpers::create_token_account(
+            banks_client,
+            payer,
+            recent_blockhash,
+            &auction_spl_pot,
+            mint,
+            auction_pubkey,
+        )
+        .await
+        .unwrap();
+
+        // Mint Tokens
+        helpers::mint_tokens(
+            banks_client,
+            payer,
+            recent_blockhash,
+            mint,
+            &bidder.pubkey(),
+            mint_manager,
+            10_000_000,
+        )
+        .await
+        .unwrap();
+
+        bidders.push((bidder, auction_spl_pot, bid_pot_pubkey));
+    }
+
+    bidders
+}
+
 /// Initialize an auction with a random resource, and generate bidders with tokens that can be used
 /// for testing.
 async fn setup_auction(
@@ -75,63 +149,16 @@ async fn setup_auction(
     .unwrap();
 
     // Attach useful Accounts for testing.
-    let mut bidders = vec![];
-    for n in 0..5 {
-        // Bidder SPL Account, with Minted Tokens
-        let bidder = Keypair::new();
-        // PDA in the auction for the Bidder to deposit their funds to.
-        let auction_spl_pot = Keypair::new();
-
-        // Generate User SPL Wallet Account
-        helpers::create_token_account(
-            &mut banks_client,
-            &payer,
-            &recent_blockhash,
-            &bidder,
-            &mint_keypair.pubkey(),
-            &payer.pubkey(),
-        )
-        .await
-        .unwrap();
-
-        // Owner via pot PDA.
-        let (bid_pot_pubkey, pot_bump) = Pubkey::find_program_address(
-            &[
-                PREFIX.as_bytes(),
-                program_id.as_ref(),
-                auction_pubkey.as_ref(),
-                bidder.pubkey().as_ref(),
-            ],
-            &program_id,
-        );
-
-        // Generate Auction SPL Pot to Transfer to.
-        helpers::create_token_account(
-            &mut banks_client,
-            &payer,
-            &recent_blockhash,
-            &auction_spl_pot,
-            &mint_keypair.pubkey(),
-            &auction_pubkey,
-        )
-        .await
-        .unwrap();
-
-        // Mint Tokens
-        helpers::mint_tokens(
-            &mut banks_client,
-            &payer,
-            &recent_blockhash,
-            &mint_keypair.pubkey(),
-            &bidder.pubkey(),
-            &mint_manager,
-            10_000_000,
-        )
-        .await
-        .unwrap();
-
-        bidders.push((bidder, auction_spl_pot, bid_pot_pubkey));
-    }
+    let bidders = setup_bidders(
+        &mut banks_client,
+        &payer,
+        &recent_blockhash,
+        &program_id,
+        &auction_pubkey,
+        &mint_keypair.pubkey(),
+        &mint_manager,
+    )
+    .await;
 
     // Verify Auction was created as expected.
     let auction: AuctionData = try_from_slice_unchecked(
@@ -694,3 +721,469 @@ async fn test_incorrect_runs() {
     }
 }
 */
+
+/// Price of a raffle entry in the raffle tests.
+const ENTRY_PRICE: u64 = 1000;
+/// Raffles in the raffle tests stop taking entries an hour after starting.
+const RAFFLE_LENGTH: i64 = 60 * 60;
+
+/// Initialize and start a raffle with a random resource whose winners are drawn with `secret`,
+/// and generate bidders with tokens that can be used for testing.
+async fn setup_raffle(
+    winners: usize,
+    secret: [u8; 32],
+) -> (
+    Pubkey,
+    ProgramTestContext,
+    Vec<(Keypair, Keypair, Pubkey)>,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+) {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new("spl_auction", program_id, processor!(process_instruction));
+    let mut context = program_test.start_with_context().await;
+
+    let (mint_keypair, mint_manager) = helpers::create_mint(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let resource = Pubkey::new_unique();
+    let seeds = &[PREFIX.as_bytes(), &program_id.as_ref(), resource.as_ref()];
+    let (auction_pubkey, _) = Pubkey::find_program_address(seeds, &program_id);
+
+    helpers::create_raffle(
+        &mut context.banks_client,
+        &program_id,
+        &context.payer,
+        &context.last_blockhash,
+        &resource,
+        &mint_keypair.pubkey(),
+        winners,
+        10,
+        RAFFLE_LENGTH,
+        ENTRY_PRICE,
+        hash(&secret),
+    )
+    .await
+    .unwrap();
+
+    let bidders = setup_bidders(
+        &mut context.banks_client,
+        &context.payer,
+        &context.last_blockhash,
+        &program_id,
+        &auction_pubkey,
+        &mint_keypair.pubkey(),
+        &mint_manager,
+    )
+    .await;
+
+    helpers::start_auction(
+        &mut context.banks_client,
+        &program_id,
+        &context.last_blockhash,
+        &context.payer,
+        &resource,
+    )
+    .await
+    .unwrap();
+
+    (
+        program_id,
+        context,
+        bidders,
+        resource,
+        mint_keypair.pubkey(),
+        auction_pubkey,
+    )
+}
+
+/// Approve a fresh transfer authority for the bidder and place a bid with it.
+#[allow(clippy::too_many_arguments)]
+async fn bid(
+    banks_client: &mut BanksClient,
+    recent_blockhash: &Hash,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    bidder: &(Keypair, Keypair, Pubkey),
+    resource: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Result<(), TransportError> {
+    let transfer_authority = Keypair::new();
+    helpers::approve(
+        banks_client,
+        recent_blockhash,
+        payer,
+        &transfer_authority.pubkey(),
+        &bidder.0,
+        amount,
+    )
+    .await?;
+
+    helpers::place_bid(
+        banks_client,
+        recent_blockhash,
+        program_id,
+        payer,
+        &bidder.0,
+        &bidder.1,
+        &transfer_authority,
+        resource,
+        mint,
+        amount,
+    )
+    .await
+}
+
+/// Move the clock forward, as if `seconds` had passed, and move on to a fresh blockhash so
+/// transactions that failed before can be sent again.
+async fn advance_clock(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+    context.get_new_latest_blockhash().await.unwrap();
+}
+
+async fn get_auction(banks_client: &mut BanksClient, auction_pubkey: &Pubkey) -> AuctionData {
+    try_from_slice_unchecked(
+        &helpers::get_account(banks_client, auction_pubkey)
+            .await
+            .data,
+    )
+    .unwrap()
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_raffle_draw() {
+    let secret = [7u8; 32];
+    let (program_id, mut context, bidders, resource, mint, auction_pubkey) =
+        setup_raffle(2, secret).await;
+
+    for bidder in bidders.iter() {
+        bid(
+            &mut context.banks_client,
+            &context.last_blockhash,
+            &program_id,
+            &context.payer,
+            bidder,
+            &resource,
+            &mint,
+            ENTRY_PRICE,
+        )
+        .await
+        .expect("place_bid");
+    }
+
+    // Every bidder gets a single entry.
+    assert!(bid(
+        &mut context.banks_client,
+        &context.last_blockhash,
+        &program_id,
+        &context.payer,
+        &bidders[0],
+        &resource,
+        &mint,
+        ENTRY_PRICE,
+    )
+    .await
+    .is_err());
+
+    // Nothing can be drawn while the raffle takes entries.
+    assert!(helpers::draw_raffle(
+        &mut context.banks_client,
+        &program_id,
+        &context.last_blockhash,
+        &context.payer,
+        &resource,
+        secret,
+    )
+    .await
+    .is_err());
+
+    advance_clock(&mut context, RAFFLE_LENGTH + 1).await;
+
+    // Entries are locked in once the raffle closes.
+    assert!(helpers::cancel_bid(
+        &mut context.banks_client,
+        &context.last_blockhash,
+        &program_id,
+        &context.payer,
+        &bidders[0].0,
+        &bidders[0].1,
+        &resource,
+        &mint,
+    )
+    .await
+    .is_err());
+
+    // Only the committed secret draws the raffle.
+    assert!(helpers::draw_raffle(
+        &mut context.banks_client,
+        &program_id,
+        &context.last_blockhash,
+        &context.payer,
+        &resource,
+        [8u8; 32],
+    )
+    .await
+    .is_err());
+
+    helpers::draw_raffle(
+        &mut context.banks_client,
+        &program_id,
+        &context.last_blockhash,
+        &context.payer,
+        &resource,
+        secret,
+    )
+    .await
+    .expect("draw_raffle");
+
+    let auction = get_auction(&mut context.banks_client, &auction_pubkey).await;
+    assert_eq!(auction.state, AuctionState::Ended);
+    assert_eq!(auction.num_winners(), 2);
+    let winners = vec![auction.winner_at(0).unwrap(), auction.winner_at(1).unwrap()];
+    assert_ne!(winners[0], winners[1]);
+
+    // Move on to a fresh blockhash, the cancel above would otherwise be a duplicate.
+    context.get_new_latest_blockhash().await.unwrap();
+
+    for (bidder, pot, _) in bidders.iter() {
+        let result = helpers::cancel_bid(
+            &mut context.banks_client,
+            &context.last_blockhash,
+            &program_id,
+            &context.payer,
+            bidder,
+            pot,
+            &resource,
+            &mint,
+        )
+        .await;
+
+        if winners.contains(&bidder.pubkey()) {
+            // Winners keep their entry in the pot.
+            assert!(result.is_err());
+            assert_eq!(
+                helpers::get_token_balance(&mut context.banks_client, &pot.pubkey()).await,
+                ENTRY_PRICE
+            );
+        } else {
+            // Everybody else gets their entry back.
+            result.expect("cancel_bid");
+            assert_eq!(
+                helpers::get_token_balance(&mut context.banks_client, &bidder.pubkey()).await,
+                10_000_000
+            );
+            assert_eq!(
+                helpers::get_token_balance(&mut context.banks_client, &pot.pubkey()).await,
+                0
+            );
+        }
+    }
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_raffle_draw_expired() {
+    let secret = [7u8; 32];
+    let (program_id, mut context, bidders, resource, mint, auction_pubkey) =
+        setup_raffle(2, secret).await;
+
+    for bidder in bidders.iter() {
+        bid(
+            &mut context.banks_client,
+            &context.last_blockhash,
+            &program_id,
+            &context.payer,
+            bidder,
+            &resource,
+            &mint,
+            ENTRY_PRICE,
+        )
+        .await
+        .expect("place_bid");
+    }
+
+    advance_clock(&mut context, RAFFLE_LENGTH + RAFFLE_DRAW_WINDOW + 1).await;
+
+    // The draw window has passed, so the raffle can't be drawn anymore.
+    assert!(helpers::draw_raffle(
+        &mut context.banks_client,
+        &program_id,
+        &context.last_blockhash,
+        &context.payer,
+        &resource,
+        secret,
+    )
+    .await
+    .is_err());
+
+    // Instead every entry can be refunded.
+    for (bidder, pot, _) in bidders.iter() {
+        helpers::cancel_bid(
+            &mut context.banks_client,
+            &context.last_blockhash,
+            &program_id,
+            &context.payer,
+            bidder,
+            pot,
+            &resource,
+            &mint,
+        )
+        .await
+        .expect("cancel_bid");
+        assert_eq!(
+            helpers::get_token_balance(&mut context.banks_client, &bidder.pubkey()).await,
+            10_000_000
+        );
+    }
+
+    // Which leaves nothing to draw from.
+    let auction = get_auction(&mut context.banks_client, &auction_pubkey).await;
+    assert_eq!(auction.num_winners(), 0);
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_cancel_auction() {
+    let (
+        program_id,
+        mut banks_client,
+        bidders,
+        payer,
+        resource,
+        mint,
+        mint_authority,
+        auction_pubkey,
+        recent_blockhash,
+    ) = setup_auction(true, 3).await;
+
+    for (index, amount) in [(0, 1000), (1, 2000)].iter() {
+        bid(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            &bidders[*index],
+            &resource,
+            &mint,
+            *amount,
+        )
+        .await
+        .expect("place_bid");
+    }
+
+    helpers::cancel_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .expect("cancel_auction");
+
+    // A cancelled auction is over and nobody won it.
+    let auction = get_auction(&mut banks_client, &auction_pubkey).await;
+    assert_eq!(auction.state, AuctionState::Ended);
+    assert_eq!(auction.num_winners(), 0);
+
+    // So every bidder gets their bid back.
+    for (bidder, pot, _) in bidders[..2].iter() {
+        helpers::cancel_bid(
+            &mut banks_client,
+            &recent_blockhash,
+            &program_id,
+            &payer,
+            bidder,
+            pot,
+            &resource,
+            &mint,
+        )
+        .await
+        .expect("cancel_bid");
+        assert_eq!(
+            helpers::get_token_balance(&mut banks_client, &bidder.pubkey()).await,
+            10_000_000
+        );
+        assert_eq!(
+            helpers::get_token_balance(&mut banks_client, &pot.pubkey()).await,
+            0
+        );
+    }
+
+    // And no more bids are taken.
+    assert!(bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[2],
+        &resource,
+        &mint,
+        3000,
+    )
+    .await
+    .is_err());
+}
+
+#[cfg(feature = "test-bpf")]
+#[tokio::test]
+async fn test_cancel_ended_auction() {
+    let (
+        program_id,
+        mut banks_client,
+        bidders,
+        payer,
+        resource,
+        mint,
+        mint_authority,
+        auction_pubkey,
+        recent_blockhash,
+    ) = setup_auction(true, 3).await;
+
+    bid(
+        &mut banks_client,
+        &recent_blockhash,
+        &program_id,
+        &payer,
+        &bidders[0],
+        &resource,
+        &mint,
+        1000,
+    )
+    .await
+    .expect("place_bid");
+
+    helpers::end_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .expect("end_auction");
+
+    // The winner is owed the item, so an ended auction can't be cancelled.
+    assert!(helpers::cancel_auction(
+        &mut banks_client,
+        &program_id,
+        &recent_blockhash,
+        &payer,
+        &resource,
+    )
+    .await
+    .is_err());
+
+    let auction = get_auction(&mut banks_client, &auction_pubkey).await;
+    assert_eq!(auction.winner_at(0), Some(bidders[0].0.pubkey()));
+}
@@ -4,16 +4,16 @@ use {
         state::{
             AuctionManager, AuctionManagerStatus, CommonWinningIndexChecks,
             CommonWinningIndexReturn, Key, NonWinningConstraint, ParticipationConfigV2,
-            PrintingV2CalculationCheckReturn, PrintingV2CalculationChecks, WinningConfigType,
-            WinningConstraint,
+            PlatformFee, PrintingV2CalculationCheckReturn, PrintingV2CalculationChecks,
+            WinningConfigType, WinningConstraint,
         },
         utils::try_from_slice_checked,
     },
     arrayref::array_ref,
     borsh::{BorshDeserialize, BorshSerialize},
     solana_program::{
-        account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
-        pubkey::Pubkey,
+        account_info::AccountInfo, clock::UnixTimestamp, entrypoint::ProgramResult, msg,
+        program_error::ProgramError, pubkey::Pubkey,
     },
     spl_auction::processor::AuctionData,
     spl_token_metadata::state::Metadata,
@@ -103,6 +103,18 @@ impl AuctionManager for AuctionManagerV1 {
         self.accept_payment
     }
 
+    /// V1 auction managers predate the platform fee and never pay one
+    fn platform_fee(&self) -> Option<PlatformFee> {
+        None
+    }
+
+    /// V1 auction managers have no room to record when they started
+    fn started_at(&self) -> Option<UnixTimestamp> {
+        None
+    }
+
+    fn set_started_at(&mut self, _started_at: UnixTimestamp) {}
+
     fn status(&self) -> AuctionManagerStatus {
         self.state.status
     }
@@ -187,7 +199,7 @@ impl AuctionManager for AuctionManagerV1 {
 
         let mut winning_config_item_index = None;
         for i in 0..winning_config.items.len() {
-            if winning_config.items[i].safety_deposit_box_index
+            if winning_config.items[i].safety_deposit_box_index as u32
                 == SafetyDepositBox::get_order(safety_deposit_info)
             {
                 winning_config_item_index = Some(i);
@@ -347,12 +359,11 @@ impl AuctionManager for AuctionManagerV1 {
         _safety_deposit_config_info: Option<&AccountInfo>,
     ) -> Result<usize, ProgramError> {
         let mut max_bids_allowed_before_removal_is_stopped = 0;
-        let u8_order = safety_deposit_box_order as u8;
         for n in 0..self.settings.winning_configs.len() {
             if self.settings.winning_configs[n]
                 .items
                 .iter()
-                .find(|i| i.safety_deposit_box_index == u8_order)
+                .find(|i| i.safety_deposit_box_index as u64 == safety_deposit_box_order)
                 .is_some()
             {
                 // This means at least n bids must exist for there to be at least one bidder that will be eligible for this prize.
@@ -369,7 +380,6 @@ impl AuctionManager for AuctionManagerV1 {
         safety_deposit_box_order: u64,
         _safety_deposit_config_info: Option<&AccountInfo>,
     ) -> ProgramResult {
-        let u8_order = safety_deposit_box_order as u8;
         let atleast_one_matching = self
             .settings
             .winning_configs
@@ -378,7 +388,7 @@ impl AuctionManager for AuctionManagerV1 {
                 c.items
                     .iter()
                     .find(|i| {
-                        i.safety_deposit_box_index == u8_order
+                        i.safety_deposit_box_index as u64 == safety_deposit_box_order
                             && i.winning_config_type == WinningConfigType::PrintingV2
                     })
                     .is_some()
@@ -387,7 +397,7 @@ impl AuctionManager for AuctionManagerV1 {
 
         if !atleast_one_matching {
             if let Some(config) = &self.settings.participation_config {
-                if config.safety_deposit_box_index != u8_order {
+                if config.safety_deposit_box_index as u64 != safety_deposit_box_order {
                     return Err(MetaplexError::InvalidOperation.into());
                 }
             } else {
@@ -472,7 +482,7 @@ impl AuctionManager for AuctionManagerV1 {
             if (winning_index as usize) < winning_configs.len() {
                 let winning_config = &winning_configs[winning_index as usize];
                 if let Some(item_index) = winning_config_item_index {
-                    if winning_config.items[item_index as usize].safety_deposit_box_index
+                    if winning_config.items[item_index as usize].safety_deposit_box_index as u32
                         != safety_deposit.order
                     {
                         return Err(MetaplexError::WinningConfigSafetyDepositMismatch.into());
@@ -484,7 +494,7 @@ impl AuctionManager for AuctionManagerV1 {
                 return Err(MetaplexError::InvalidWinningConfigIndex.into());
             }
         } else if let Some(participation) = &self.settings.participation_config {
-            if participation.safety_deposit_box_index != safety_deposit.order {
+            if participation.safety_deposit_box_index as u32 != safety_deposit.order {
                 return Err(MetaplexError::ParticipationSafetyDepositMismatch.into());
             }
         } else {
@@ -515,10 +525,10 @@ impl AuctionManagerV1 {
 
 fn count_item_amount_by_safety_deposit_order(
     items: &Vec<WinningConfigItem>,
-    safety_deposit_index: u8,
+    safety_deposit_index: u32,
 ) -> u64 {
     let item = items.iter().find_map(|i| {
-        if i.safety_deposit_box_index == safety_deposit_index {
+        if i.safety_deposit_box_index as u32 == safety_deposit_index {
             Some(i)
         } else {
             None
@@ -430,6 +430,98 @@ pub enum MetaplexError {
     /// Not allowed to use Master Edition V1 printing with Auction Manager v2
     #[error("Not allowed to use Master Edition V1 printing with Auction Manager v2")]
     PrintingV1NotAllowedWithAuctionManagerV2,
+
+    /// The metadata's token standard can't be used with this winning config type
+    #[error("The metadata's token standard can't be used with this winning config type")]
+    InvalidTokenStandard,
+
+    /// Basis points cannot be more than 10000
+    #[error("Basis points cannot be more than 10000")]
+    InvalidBasisPoints,
+
+    /// This store does not take a platform fee
+    #[error("This store does not take a platform fee")]
+    NoPlatformFee,
+
+    /// This fixed price listing is not active
+    #[error("This fixed price listing is not active")]
+    FixedPriceListingNotActive,
+
+    /// Account does not match the one recorded on the fixed price listing
+    #[error("Account does not match the one recorded on the fixed price listing")]
+    FixedPriceListingMismatch,
+
+    /// Prints can only be listed off a Master Edition V2
+    #[error("Prints can only be listed off a Master Edition V2")]
+    MasterEditionV2Required,
+
+    /// This store indexer page is full, use the next page
+    #[error("This store indexer page is full, use the next page")]
+    StoreIndexerPageFull,
+
+    /// Auction caches in a store indexer page must stay sorted by timestamp
+    #[error("Auction caches in a store indexer page must stay sorted by timestamp")]
+    AuctionCacheOutOfOrder,
+
+    /// This auction cache is already in the store indexer page
+    #[error("This auction cache is already in the store indexer page")]
+    AuctionCacheAlreadyIndexed,
+
+    /// This auction cache is not in the store indexer page
+    #[error("This auction cache is not in the store indexer page")]
+    AuctionCacheNotIndexed,
+
+    /// Only caches of auctions that ended without any bids can be pruned
+    #[error("Only caches of auctions that ended without any bids can be pruned")]
+    AuctionCacheStillLive,
+
+    /// Signer is neither the store owner nor holds a role allowing this
+    #[error("Signer is neither the store owner nor holds a role allowing this")]
+    MissingStoreRole,
+
+    /// Only the store owner can do this
+    #[error("Only the store owner can do this")]
+    MustBeStoreOwner,
+
+    /// This store's ownership moved to another store, use that one instead
+    #[error("This store's ownership moved to another store, use that one instead")]
+    StoreMigrated,
+
+    /// This offer has expired
+    #[error("This offer has expired")]
+    OfferExpired,
+
+    /// Account does not match the one recorded on the offer
+    #[error("Account does not match the one recorded on the offer")]
+    OfferMismatch,
+
+    /// Creator accounts must be passed in the same order as the metadata's creators
+    #[error("Creator accounts must be passed in the same order as the metadata's creators")]
+    CreatorAccountMismatch,
+
+    /// Only tokens that already had their primary sale can be listed for resale
+    #[error("Only tokens that already had their primary sale can be listed for resale")]
+    PrimarySaleRequired,
+
+    /// This auction was cancelled, its items can only be returned to their original owners
+    #[error("This auction was cancelled, its items can only be returned to their original owners")]
+    AuctionCancelled,
+
+    /// The store was not moved to this new store
+    #[error("The store was not moved to this new store")]
+    StoreSuccessorMismatch,
+
+    /// Every printing v2 prize needs its own edition offset
+    #[error("Every printing v2 prize needs its own edition offset")]
+    MissingEditionOffset,
+
+    /// The auction cache was funded by a different payer
+    #[error("The auction cache was funded by a different payer")]
+    AuctionCachePayerMismatch,
+
+    /// Token metadata program does not match the one the metadata is owned by
+    #[error("Token metadata program does not match the one the metadata is owned by")]
+    TokenMetadataProgramMismatch,
 }
 
 impl PrintProgramError for MetaplexError {
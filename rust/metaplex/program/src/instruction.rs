@@ -1,10 +1,14 @@
 use {
     crate::{
         deprecated_state::AuctionManagerSettingsV1,
-        state::{SafetyDepositConfig, TupleNumericType, PREFIX},
+        state::{
+            FixedPriceListingType, PlatformFee, SafetyDepositConfig, StoreRole, TupleNumericType,
+            CACHE, INDEX, LISTING, OFFER, PREFIX, ROLE,
+        },
     },
     borsh::{BorshDeserialize, BorshSerialize},
     solana_program::{
+        clock::UnixTimestamp,
         instruction::{AccountMeta, Instruction},
         pubkey::Pubkey,
         sysvar,
@@ -16,9 +20,26 @@ pub struct SetStoreArgs {
     pub public: bool,
 }
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetStoreV2Args {
+    pub public: bool,
+    pub platform_fee: Option<PlatformFee>,
+}
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct SetWhitelistedCreatorArgs {
     pub activated: bool,
 }
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetStoreRoleArgs {
+    pub role: StoreRole,
+    /// Set to false to revoke the role
+    pub active: bool,
+}
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct MakeOfferArgs {
+    pub price: u64,
+    /// After this the offer can no longer be accepted, only cancelled
+    pub expires_at: UnixTimestamp,
+}
 
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct EmptyPaymentAccountArgs {
@@ -33,6 +54,28 @@ pub struct EmptyPaymentAccountArgs {
     pub creator_index: Option<u8>,
 }
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct EmptyPlatformFeeArgs {
+    /// Winning config index to pay the fee for, or None for participation NFT contributions.
+    pub winning_config_index: Option<u8>,
+
+    /// If not paying out a participation NFT's contributions, the index into the winning config item's list.
+    pub winning_config_item_index: Option<u8>,
+}
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct CreateFixedPriceListingArgs {
+    /// Price per item, in the mint of the accept payment account
+    pub price: u64,
+    pub listing_type: FixedPriceListingType,
+}
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct EmptyFixedPriceListingPaymentAccountArgs {
+    /// index in the metadata creator list, None for the seller.
+    pub creator_index: Option<u8>,
+
+    /// Pay out the store's platform fee instead, creator index is ignored.
+    pub platform_fee_payout: bool,
+}
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub enum ProxyCallAddress {
     RedeemBid,
     RedeemFullRightsTransferBid,
@@ -49,6 +92,24 @@ pub struct RedeemPrintingV2BidArgs {
     pub win_index: u64,
 }
 
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct RedeemBidsArgs {
+    pub win_index: u64,
+    /// Edition offsets of the printing v2 prizes, in the order they are passed
+    pub edition_offsets: Vec<u64>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct SetStoreIndexArgs {
+    pub page: u64,
+    pub offset: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct RemoveAuctionCacheArgs {
+    pub page: u64,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Clone)]
 pub struct RedeemParticipationBidV3Args {
     pub win_index: Option<u64>,
@@ -278,6 +339,8 @@ pub enum MetaplexInstruction {
     /// For the participation NFT, there is no winning config, but the total is figured by summing the winning bids and subtracting
     /// from the total escrow amount present.
     ///
+    /// If the store takes a platform fee, creators and auctioneer split what is left after it, see EmptyPlatformFee.
+    ///
     ///   0. `[writable]` The accept payment account on the auction manager
     ///   1. `[writable]` The destination account of same mint type as the accept payment account. Must be an Associated Token Account.
     ///   2. `[writable]` Auction manager
@@ -299,9 +362,10 @@ pub enum MetaplexInstruction {
 
     /// Given a signer wallet, create a store with pda ['metaplex', wallet] (if it does not exist) and/or update it
     /// (if it already exists). Stores can be set to open (anybody can publish) or closed (publish only via whitelist).
+    /// An existing store can also be updated by a wallet holding the StoreAdmin role.
     ///
     ///   0. `[writable]` The store key, seed of ['metaplex', admin wallet]
-    ///   1. `[signer]`  The admin wallet
+    ///   1. `[signer]`  The admin wallet, or a StoreAdmin role holder
     ///   2. `[signer]`  Payer
     ///   3. `[]` Token program
     ///   4. `[]` Token vault program
@@ -309,18 +373,20 @@ pub enum MetaplexInstruction {
     ///   6. `[]` Auction program
     ///   7. `[]` System
     ///   8. `[]` Rent sysvar
+    ///   9. `[]` Store role record of the signer, only needed if the signer is not the store owner
     SetStore(SetStoreArgs),
 
     /// Given an existing store, add or update an existing whitelisted creator for the store. This creates
     /// a PDA with seed ['metaplex', store key, creator key] if it does not already exist to store attributes there.
     ///
     ///   0. `[writable]` The whitelisted creator pda key, seed of ['metaplex', store key, creator key]
-    ///   1. `[signer]`  The admin wallet
+    ///   1. `[signer]`  The admin wallet, or a StoreAdmin or CreatorCurator role holder
     ///   2. `[signer]`  Payer
     ///   3. `[]` The creator key
     ///   4. `[]` The store key, seed of ['metaplex', admin wallet]
     ///   5. `[]` System
     ///   6. `[]` Rent sysvar
+    ///   7. `[]` Store role record of the signer, only needed if the signer is not the store owner
     SetWhitelistedCreator(SetWhitelistedCreatorArgs),
 
     /// NOTE: Requires an AuctionManagerV1.
@@ -614,6 +680,331 @@ pub enum MetaplexInstruction {
     ///   27. `[]` Metadata account of token in vault
     //    28. `[]` Auction data extended - pda of ['auction', auction program id, vault key, 'extended'] relative to auction program
     RedeemParticipationBidV3(RedeemParticipationBidV3Args),
+
+    /// Same as SetStore, but also sets the store's platform fee, a cut in basis points of every auction's proceeds
+    /// that goes to the fee destination's associated token account before creators and auctioneer split the rest.
+    /// Pass None to stop taking a fee. Auction managers and fixed price listings keep the fee they were created with,
+    /// so a change only applies to ones created after it.
+    ///
+    ///   0. `[writable]` The store key, seed of ['metaplex', admin wallet]
+    ///   1. `[signer]`  The admin wallet
+    ///   2. `[signer]`  Payer
+    ///   3. `[]` Token program
+    ///   4. `[]` Token vault program
+    ///   5. `[]` Token metadata program
+    ///   6. `[]` Auction program
+    ///   7. `[]` System
+    ///   8. `[]` Rent sysvar
+    SetStoreV2(SetStoreV2Args),
+
+    /// Same as EmptyPaymentAccount, but pays out the store's platform fee on a prize (or on participation NFT
+    /// contributions) to the store's fee destination. The payout ticket uses 'platform' in place of the creator index.
+    ///
+    ///   0. `[writable]` The accept payment account on the auction manager
+    ///   1. `[writable]` The destination account of same mint type as the accept payment account.
+    ///      Must be an associated token account of the store's platform fee destination.
+    ///   2. `[writable]` Auction manager
+    ///   3. `[writable]` Payout ticket info to keep track of this payout,
+    ///      pda of ['metaplex', auction manager, winning config index OR 'participation', winning config item index OR '0',
+    ///      'platform', safety deposit key, destination owner]
+    ///   4. `[signer]` payer
+    ///   5. `[]` The metadata
+    ///   6. `[]` The master edition of the metadata (optional if exists)
+    ///      (pda of ['metadata', program id, metadata mint id, 'edition']) - remember PDA is relative to token metadata program
+    ///   7. `[]` Safety deposit box account
+    ///   8. `[]` The store of the auction manager
+    ///   9. `[]` The vault
+    ///   10. `[]` Auction
+    ///   11. `[]` Token program
+    ///   12. `[]` System program
+    ///   13. `[]` Rent sysvar
+    ///   14. `[]` AuctionWinnerTokenTypeTracker, pda of seed ['metaplex', program id, auction manager key, 'totals']
+    ///   15. `[]` Safety deposit config pda of ['metaplex', program id, auction manager, safety deposit]
+    EmptyPlatformFee(EmptyPlatformFeeArgs),
+
+    /// Lists a token in a store at a fixed price without an auction. The seller's token moves into an escrow
+    /// account owned by the listing. A Sale listing sells that token to one buyer, a Prints listing keeps the
+    /// Master Edition V2 token in escrow and sells one new limited edition print per purchase. A Resale listing
+    /// sells a token that already had its primary sale to one buyer and enforces its royalties on payout.
+    ///
+    ///   0. `[writable]` Uninitialized fixed price listing, pda of ['metaplex', program id, item token account, 'listing']
+    ///   1. `[signer]` Seller, authority of the listing
+    ///   2. `[writable]` Seller's token account holding the token
+    ///   3. `[writable]` Empty item token account of the same mint, owned by the listing
+    ///   4. `[]` Accept payment account of the mint buyers pay in, owned by the listing, with no delegate or close authority
+    ///   5. `[]` Metadata of the token
+    ///   6. `[]` Master edition of the token, pda of ['metadata', token metadata program id, mint, 'edition'].
+    ///           Must be a Master Edition V2 for Prints listings.
+    ///   7. `[]` Store
+    ///   8. `[]` A whitelisted creator entry for the store, pda of ['metaplex', program id, store key, creator key]
+    ///           where creator key comes from the metadata creator list. Unused if the store is public or for Resale listings.
+    ///   9. `[signer]` Payer
+    ///   10. `[]` Token program
+    ///   11. `[]` System program
+    ///   12. `[]` Rent sysvar
+    CreateFixedPriceListing(CreateFixedPriceListingArgs),
+
+    /// Buys one item off an active fixed price listing, paying the price into the listing's accept payment account.
+    /// The proceeds are paid out later with EmptyFixedPriceListingPaymentAccount.
+    ///
+    ///   0. `[writable]` Fixed price listing
+    ///   1. `[writable]` Accept payment account of the listing
+    ///   2. `[writable]` Buyer's token account to pay from
+    ///   3. `[signer]` Transfer authority of the buyer's token account
+    ///   4. `[writable]` Item token account of the listing
+    ///   5. `[writable]` Metadata of the listed token
+    ///   6. `[]` Store
+    ///   7. `[signer]` Payer
+    ///   8. `[]` Token program
+    ///   9. `[]` Token metadata program
+    ///   10. `[]` System program
+    ///   11. `[]` Rent sysvar
+    ///
+    ///   For Sale and Resale listings:
+    ///   12. `[writable]` Destination token account of the listed mint
+    ///
+    ///   For Prints listings:
+    ///   12. `[writable]` New metadata, pda of ['metadata', token metadata program id, new mint]
+    ///   13. `[writable]` New edition, pda of ['metadata', token metadata program id, new mint, 'edition']
+    ///   14. `[writable]` Master edition of the listed token
+    ///   15. `[writable]` New mint with a single token held by the buyer
+    ///   16. `[writable]` Edition marker pda of ['metadata', token metadata program id, master mint, 'edition', edition number / 248]
+    ///   17. `[signer]` Mint authority of the new mint
+    ///   18. `[]` Update authority of the new metadata
+    BuyFixedPriceListing,
+
+    /// Cancels an active fixed price listing and returns the escrowed token to the seller.
+    ///
+    ///   0. `[writable]` Fixed price listing
+    ///   1. `[signer]` Seller, authority of the listing
+    ///   2. `[writable]` Item token account of the listing
+    ///   3. `[writable]` Destination token account of the listed mint
+    ///   4. `[]` Token program
+    CancelFixedPriceListing,
+
+    /// Same as EmptyPaymentAccount, but for the proceeds of a fixed price listing. The seller gets the auctioneer's share.
+    /// For Resale listings the royalty of seller fee basis points is split among the verified creators by share,
+    /// unverified creators can't be paid out, and the seller gets the rest.
+    ///
+    ///   0. `[writable]` The accept payment account of the listing
+    ///   1. `[writable]` The destination account of same mint type as the accept payment account.
+    ///      Must be an associated token account of the creator or platform fee destination, if paying either.
+    ///   2. `[]` Fixed price listing
+    ///   3. `[writable]` Payout ticket info to keep track of this payout,
+    ///      pda of ['metaplex', listing, creator index OR 'auctioneer' OR 'platform', destination owner]
+    ///   4. `[signer]` payer
+    ///   5. `[]` The metadata of the listed token
+    ///   6. `[]` The store of the listing
+    ///   7. `[]` Token program
+    ///   8. `[]` System program
+    ///   9. `[]` Rent sysvar
+    EmptyFixedPriceListingPaymentAccount(EmptyFixedPriceListingPaymentAccountArgs),
+
+    /// Redeems several Token Only Transfer, Full Rights Transfer and Printing V2 prizes of an Auction Manager V2 winner at once.
+    /// The checks common to every prize run once, and the bid redemption ticket gets marked for each prize redeemed.
+    ///
+    ///   0. `[writable]` Auction manager
+    ///   1. `[writable]` Bid redemption key -
+    ///        Just a PDA with seed ['metaplex', auction_key, bidder_metadata_key] that we will allocate to mark that you redeemed your bid
+    ///   2. `[writable]` Vault account
+    ///   3. `[writable]` Fraction mint of the vault
+    ///   4. `[]` Auction
+    ///   5. `[]` Your BidderMetadata account
+    ///   6. `[signer]` Your Bidder account
+    ///   7. `[signer]` Payer
+    ///   8. `[]` Token program
+    ///   9. `[]` Token Vault program
+    ///   10. `[]` Token metadata program
+    ///   11. `[]` Store
+    ///   12. `[]` System
+    ///   13. `[]` Rent sysvar
+    ///   14. `[]` PDA-based Transfer authority to move the tokens from the store to the destination seed ['vault', program_id, vault key]
+    ///        but please note that this is a PDA relative to the Token Vault program, with the 'vault' prefix
+    ///
+    ///   Then, for every prize:
+    ///   0. `[writable]` Safety deposit token storage account
+    ///   1. `[writable]` Destination account. For Printing V2 prizes, your ATA holding the one token of the new mint
+    ///   2. `[writable]` Safety deposit box account
+    ///   3. `[]` Safety deposit config pda of ['metaplex', program id, auction manager, safety deposit]
+    ///
+    ///   Followed, for Full Rights Transfer prizes, by:
+    ///   4. `[writable]` Metadata account of the token in the safety deposit box
+    ///   5. `[]` New authority for Metadata
+    ///
+    ///   Or, for Printing V2 prizes, which each take the next of the edition offsets in the args, by:
+    ///   4. `[writable]` Prize tracking ticket (pda of ['metaplex', program id, auction manager key, metadata mint id])
+    ///   5. `[writable]` New Metadata key (pda of ['metadata', program id, mint id])
+    ///   6. `[writable]` New Edition (pda of ['metadata', program id, mint id, 'edition'])
+    ///   7. `[writable]` Master Edition of token in vault V2 (pda of ['metadata', program id, master metadata mint id, 'edition']) PDA is relative to token metadata.
+    ///   8. `[writable]` Mint of new token
+    ///   9. `[writable]` Edition pda to mark creation (pda of ['metadata', program id, master metadata mint id, 'edition', edition_number])
+    ///        where edition_number = floor(edition/EDITION_MARKER_BIT_SIZE). PDA is relative to token metadata.
+    ///   10. `[signer]` Mint authority of new mint - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY
+    ///   11. `[]` Metadata account of token in vault
+    RedeemBids(RedeemBidsArgs),
+
+    /// Permissionless crank, run after StartAuction, that creates or fills in the auction cache of an auction.
+    /// Call it once per safety deposit box to record the metadata of every item.
+    ///
+    ///   0. `[writable]` Auction cache, pda of ['metaplex', program id, auction, 'cache']
+    ///   1. `[signer]` Payer
+    ///   2. `[]` Auction
+    ///   3. `[]` Safety deposit box account
+    ///   4. `[]` Auction manager
+    ///   5. `[]` Store
+    ///   6. `[]` System
+    ///   7. `[]` Rent sysvar
+    ///   8. `[]` Clock sysvar
+    SetAuctionCache,
+
+    /// Permissionless crank that inserts an auction cache into a page of its store's index, which is kept
+    /// sorted by cache timestamp, oldest first. Once a page is full, caches go into the next page.
+    ///
+    ///   0. `[writable]` Store indexer, pda of ['metaplex', program id, store, 'index', page]
+    ///   1. `[signer]` Payer
+    ///   2. `[writable]` Auction cache, which must not be indexed yet
+    ///   3. `[]` Store
+    ///   4. `[]` System
+    ///   5. `[]` Rent sysvar
+    ///   6. `[optional]` Auction cache currently at offset - 1, required if offset is not 0
+    ///   7. `[optional]` Auction cache currently at offset, required if offset is not the end of the page
+    SetStoreIndex(SetStoreIndexArgs),
+
+    /// Prunes the auction cache of a decommissioned auction manager, or any other auction that ended
+    /// without a bid, from its store index page and closes it, refunding whoever paid for the cache.
+    ///
+    ///   0. `[writable]` Store indexer page holding the cache
+    ///   1. `[writable]` Auction cache
+    ///   2. `[]` Auction manager
+    ///   3. `[]` Auction
+    ///   4. `[]` Store
+    ///   5. `[signer]` Auction manager authority
+    ///   6. `[writable]` Payer that funded the auction cache, receives its lamports
+    RemoveAuctionCache(RemoveAuctionCacheArgs),
+
+    /// Grants or revokes a role on a store. Only the store owner, whose wallet seeds the store, can do this.
+    ///
+    ///   0. `[writable]` Store role record, pda of ['metaplex', program id, store, 'role', role seed, holder]
+    ///      where the role seed is 'admin' for StoreAdmin and 'curator' for CreatorCurator
+    ///   1. `[signer]` The store owner
+    ///   2. `[signer]` Payer
+    ///   3. `[]` The wallet getting or losing the role
+    ///   4. `[]` The store
+    ///   5. `[]` System
+    ///   6. `[]` Rent sysvar
+    SetStoreRole(SetStoreRoleArgs),
+
+    /// Moves a store to a new owner by copying its config into the store pda of the new owner's wallet.
+    /// The old store keeps serving existing auction managers and listings but can no longer be changed
+    /// or used for new ones. Whitelisted creators and roles belong to the old store, MigrateStoreRecords copies them over.
+    ///
+    ///   0. `[writable]` The store, seed of ['metaplex', program id, owner]
+    ///   1. `[signer]` The store owner
+    ///   2. `[writable]` Uninitialized new store, seed of ['metaplex', program id, new owner]
+    ///   3. `[signer]` The new owner
+    ///   4. `[signer]` Payer
+    ///   5. `[]` System
+    ///   6. `[]` Rent sysvar
+    TransferStoreOwnership,
+
+    /// Makes an offer on an NFT, listed or not, by escrowing the price in SOL or an SPL token.
+    /// One offer per buyer per NFT, it has to be accepted or cancelled before making another.
+    ///
+    ///   0. `[writable]` Uninitialized offer, pda of ['metaplex', program id, metadata, 'offer', buyer]
+    ///   1. `[signer, writable]` Buyer, pays the price when offering SOL
+    ///   2. `[signer]` Payer
+    ///   3. `[]` Metadata of the NFT
+    ///   4. `[]` Clock sysvar
+    ///   5. `[]` System
+    ///   6. `[]` Rent sysvar
+    ///   Only when offering SPL tokens instead of SOL:
+    ///   7. `[writable]` Buyer token account to pay from, the buyer must be its owner or delegate
+    ///   8. `[writable]` Escrow token account, owned by the offer with no delegate or close authority
+    ///   9. `[]` Token program
+    MakeOffer(MakeOfferArgs),
+
+    /// Accepts an unexpired offer by sending the NFT to the buyer. Verified creators get all of the proceeds
+    /// by their shares if the primary sale has not happened yet, which this sale then marks as happened, and
+    /// royalties of seller fee basis points otherwise. The seller gets the rest, and the offer and its escrow
+    /// are closed back to the buyer.
+    ///
+    ///   0. `[writable]` Offer, pda of ['metaplex', program id, metadata, 'offer', buyer]
+    ///   1. `[signer, writable]` Owner of the NFT, paid here for SOL offers
+    ///   2. `[writable]` Token account of the owner holding the NFT
+    ///   3. `[writable]` Token account of the buyer to receive the NFT
+    ///   4. `[writable]` Buyer, gets back the rent of the offer and escrow
+    ///   5. `[writable]` Metadata of the NFT
+    ///   6. `[]` Token program
+    ///   7. `[]` Clock sysvar
+    ///   8. `[]` Token metadata program
+    ///   Only for SPL offers:
+    ///   9. `[writable]` Escrow token account
+    ///   10. `[writable]` Token account of the escrowed mint to pay the seller
+    ///   Then one `[writable]` account per creator of the metadata, in the same order: the creator's
+    ///   wallet for SOL offers or a token account of the escrowed mint owned by the creator for SPL offers.
+    ///   Unverified creators are not paid.
+    AcceptOffer,
+
+    /// Cancels an offer at any time before it is accepted, refunding the escrowed amount and rent.
+    ///
+    ///   0. `[writable]` Offer, pda of ['metaplex', program id, metadata, 'offer', buyer]
+    ///   1. `[signer, writable]` Buyer
+    ///   Only for SPL offers:
+    ///   2. `[writable]` Escrow token account
+    ///   3. `[writable]` Token account to refund the escrowed tokens to
+    ///   4. `[]` Token program
+    CancelOffer,
+
+    /// Cancels a validated or running auction before it ends. The auction is ended without any
+    /// winners, so every bidder can reclaim their bidder pot with the auction program's CancelBid,
+    /// and the items are handed back with ReturnCancelledAuctionItem.
+    ///
+    ///   0. `[writable]` Auction Manager
+    ///   1. `[writable]` Auction
+    ///   2. `[signer]` Authority of the Auction Manager
+    ///   3. `[]` Store
+    ///   4. `[]` Auction program
+    ///   5. `[]` Clock sysvar
+    CancelAuctionManager,
+
+    /// Returns the contents of one safety deposit box of a cancelled auction to its original owner.
+    /// Items whose metadata update authority was given to the auction manager go back to the original
+    /// authority recorded in the OriginalAuthorityLookup, along with the update authority. Everything
+    /// else goes back to the auction manager authority. Call once per safety deposit box.
+    ///
+    ///   0. `[writable]` Auction manager
+    ///   1. `[writable]` Safety deposit token storage account
+    ///   2. `[writable]` Destination account of the original owner, for the safety deposit box's mint.
+    ///   3. `[writable]` Safety deposit box account
+    ///   4. `[writable]` Vault account
+    ///   5. `[writable]` Fraction mint of the vault
+    ///   6. `[]` Auction
+    ///   7. `[]` Store
+    ///   8. `[]` Token program
+    ///   9. `[]` Token Vault program
+    ///   10. `[]` Token metadata program
+    ///   11. `[]` Rent sysvar
+    ///   12. `[writable]` Metadata of the safety deposit box's mint
+    ///   13. `[]` Original owner
+    ///   14. `[]` Original authority lookup, pda of ['metaplex', auction key, metadata key]
+    ///   15. `[]` PDA-based Transfer authority to move the tokens from the store to the destination seed ['vault', program_id, vault key]
+    ///   16. `[]` Safety deposit config pda of ['metaplex', program id, auction manager, safety deposit]
+    ///   17. `[writable]` Printing mint of a MasterEditionV1 whose mint authority the auction manager holds (Optional)
+    ReturnCancelledAuctionItem,
+
+    /// Copies whitelisted creators and store roles of a store moved with TransferStoreOwnership to the new store.
+    /// The old records stay in place for the auction managers of the old store. Records that already exist
+    /// on the new store are left as they are.
+    ///
+    ///   0. `[]` The old store, seed of ['metaplex', program id, old owner]
+    ///   1. `[]` The new store, seed of ['metaplex', program id, new owner]
+    ///   2. `[signer]` The new owner
+    ///   3. `[signer]` Payer
+    ///   4. `[]` System
+    ///   5. `[]` Rent sysvar
+    ///   6..6+2N Pairs of a whitelisted creator or store role record of the old store, `[]`, and the
+    ///      `[writable]` pda of the same record under the new store
+    MigrateStoreRecords,
 }
 
 /// Creates an DeprecatedInitAuctionManager instruction
@@ -1042,6 +1433,38 @@ pub fn create_set_store_instruction(
     }
 }
 
+/// Creates an SetStoreV2 instruction
+pub fn create_set_store_v2_instruction(
+    program_id: Pubkey,
+    store: Pubkey,
+    admin: Pubkey,
+    payer: Pubkey,
+    public: bool,
+    platform_fee: Option<PlatformFee>,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(store, false),
+        AccountMeta::new_readonly(admin, true),
+        AccountMeta::new_readonly(payer, true),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_token_vault::id(), false),
+        AccountMeta::new_readonly(spl_token_metadata::id(), false),
+        AccountMeta::new_readonly(spl_auction::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::SetStoreV2(SetStoreV2Args {
+            public,
+            platform_fee,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
 pub fn create_deprecated_populate_participation_printing_account_instruction(
     program_id: Pubkey,
     safety_deposit_token_store: Pubkey,
@@ -1444,3 +1867,1003 @@ pub fn create_redeem_participation_bid_v3_instruction(
         .unwrap(),
     }
 }
+
+/// Derives the fixed price listing pda for an item token account
+pub fn find_fixed_price_listing_address(
+    program_id: &Pubkey,
+    item_token_account: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            item_token_account.as_ref(),
+            LISTING.as_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Creates an CreateFixedPriceListing instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_create_fixed_price_listing_instruction(
+    program_id: Pubkey,
+    seller: Pubkey,
+    source: Pubkey,
+    item_token_account: Pubkey,
+    accept_payment: Pubkey,
+    metadata: Pubkey,
+    master_edition: Pubkey,
+    store: Pubkey,
+    whitelisted_creator: Pubkey,
+    payer: Pubkey,
+    price: u64,
+    listing_type: FixedPriceListingType,
+) -> Instruction {
+    let listing = find_fixed_price_listing_address(&program_id, &item_token_account);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(listing, false),
+            AccountMeta::new_readonly(seller, true),
+            AccountMeta::new(source, false),
+            AccountMeta::new(item_token_account, false),
+            AccountMeta::new_readonly(accept_payment, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(master_edition, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(whitelisted_creator, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetaplexInstruction::CreateFixedPriceListing(CreateFixedPriceListingArgs {
+            price,
+            listing_type,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Creates an BuyFixedPriceListing instruction for a Sale or Resale listing
+#[allow(clippy::too_many_arguments)]
+pub fn create_buy_fixed_price_listing_instruction(
+    program_id: Pubkey,
+    listing: Pubkey,
+    accept_payment: Pubkey,
+    paying_token_account: Pubkey,
+    transfer_authority: Pubkey,
+    item_token_account: Pubkey,
+    metadata: Pubkey,
+    store: Pubkey,
+    payer: Pubkey,
+    destination: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(listing, false),
+            AccountMeta::new(accept_payment, false),
+            AccountMeta::new(paying_token_account, false),
+            AccountMeta::new_readonly(transfer_authority, true),
+            AccountMeta::new(item_token_account, false),
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_token_metadata::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new(destination, false),
+        ],
+        data: MetaplexInstruction::BuyFixedPriceListing
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an BuyFixedPriceListing instruction for a Prints listing, the print will be
+/// edition number `edition`, which must be the master edition's current supply + 1
+#[allow(clippy::too_many_arguments)]
+pub fn create_buy_fixed_price_listing_print_instruction(
+    program_id: Pubkey,
+    listing: Pubkey,
+    accept_payment: Pubkey,
+    paying_token_account: Pubkey,
+    transfer_authority: Pubkey,
+    item_token_account: Pubkey,
+    store: Pubkey,
+    payer: Pubkey,
+    original_mint: Pubkey,
+    new_mint: Pubkey,
+    new_mint_authority: Pubkey,
+    new_metadata_update_authority: Pubkey,
+    edition: u64,
+) -> Instruction {
+    let metadata_program_id = spl_token_metadata::id();
+    let metadata_prefix = spl_token_metadata::state::PREFIX.as_bytes();
+    let edition_prefix = spl_token_metadata::state::EDITION.as_bytes();
+
+    let (metadata, _) = Pubkey::find_program_address(
+        &[
+            metadata_prefix,
+            metadata_program_id.as_ref(),
+            original_mint.as_ref(),
+        ],
+        &metadata_program_id,
+    );
+
+    let (master_edition, _) = Pubkey::find_program_address(
+        &[
+            metadata_prefix,
+            metadata_program_id.as_ref(),
+            original_mint.as_ref(),
+            edition_prefix,
+        ],
+        &metadata_program_id,
+    );
+
+    let (new_metadata, _) = Pubkey::find_program_address(
+        &[
+            metadata_prefix,
+            metadata_program_id.as_ref(),
+            new_mint.as_ref(),
+        ],
+        &metadata_program_id,
+    );
+
+    let (new_edition, _) = Pubkey::find_program_address(
+        &[
+            metadata_prefix,
+            metadata_program_id.as_ref(),
+            new_mint.as_ref(),
+            edition_prefix,
+        ],
+        &metadata_program_id,
+    );
+
+    let edition_number = edition.checked_div(EDITION_MARKER_BIT_SIZE).unwrap();
+
+    let (edition_mark_pda, _) = Pubkey::find_program_address(
+        &[
+            metadata_prefix,
+            metadata_program_id.as_ref(),
+            original_mint.as_ref(),
+            edition_prefix,
+            edition_number.to_string().as_bytes(),
+        ],
+        &metadata_program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(listing, false),
+            AccountMeta::new(accept_payment, false),
+            AccountMeta::new(paying_token_account, false),
+            AccountMeta::new_readonly(transfer_authority, true),
+            AccountMeta::new(item_token_account, false),
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(metadata_program_id, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new(new_metadata, false),
+            AccountMeta::new(new_edition, false),
+            AccountMeta::new(master_edition, false),
+            AccountMeta::new(new_mint, false),
+            AccountMeta::new(edition_mark_pda, false),
+            AccountMeta::new_readonly(new_mint_authority, true),
+            AccountMeta::new_readonly(new_metadata_update_authority, false),
+        ],
+        data: MetaplexInstruction::BuyFixedPriceListing
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an CancelFixedPriceListing instruction
+pub fn create_cancel_fixed_price_listing_instruction(
+    program_id: Pubkey,
+    listing: Pubkey,
+    seller: Pubkey,
+    item_token_account: Pubkey,
+    destination: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(listing, false),
+            AccountMeta::new_readonly(seller, true),
+            AccountMeta::new(item_token_account, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: MetaplexInstruction::CancelFixedPriceListing
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an EmptyFixedPriceListingPaymentAccount instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_empty_fixed_price_listing_payment_account_instruction(
+    program_id: Pubkey,
+    accept_payment: Pubkey,
+    destination: Pubkey,
+    destination_owner: Pubkey,
+    listing: Pubkey,
+    payer: Pubkey,
+    metadata: Pubkey,
+    store: Pubkey,
+    creator_index: Option<u8>,
+    platform_fee_payout: bool,
+) -> Instruction {
+    let creator_index_key: String = match creator_index {
+        _ if platform_fee_payout => "platform".to_owned(),
+        Some(val) => val.to_string(),
+        None => "auctioneer".to_owned(),
+    };
+
+    let (payout_ticket, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            listing.as_ref(),
+            creator_index_key.as_bytes(),
+            destination_owner.as_ref(),
+        ],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(accept_payment, false),
+            AccountMeta::new(destination, false),
+            AccountMeta::new_readonly(listing, false),
+            AccountMeta::new(payout_ticket, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetaplexInstruction::EmptyFixedPriceListingPaymentAccount(
+            EmptyFixedPriceListingPaymentAccountArgs {
+                creator_index,
+                platform_fee_payout,
+            },
+        )
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// A prize for create_redeem_bids_instruction, with the accounts its winning config type needs
+#[derive(Clone, Debug)]
+pub enum RedeemBidsPrize {
+    TokenOnlyTransfer {
+        safety_deposit_token_store: Pubkey,
+        destination: Pubkey,
+        safety_deposit_box: Pubkey,
+    },
+    FullRightsTransfer {
+        safety_deposit_token_store: Pubkey,
+        destination: Pubkey,
+        safety_deposit_box: Pubkey,
+        metadata: Pubkey,
+        new_metadata_authority: Pubkey,
+    },
+    /// edition is the edition number the print gets, used to find its edition marker,
+    /// and edition_offset its offset within the editions won at this winning index
+    PrintingV2 {
+        safety_deposit_token_store: Pubkey,
+        new_edition_token_account: Pubkey,
+        safety_deposit_box: Pubkey,
+        original_mint: Pubkey,
+        new_mint: Pubkey,
+        new_mint_authority: Pubkey,
+        edition_offset: u64,
+        edition: u64,
+    },
+}
+
+/// Creates an RedeemBids instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_redeem_bids_instruction(
+    program_id: Pubkey,
+    auction_manager: Pubkey,
+    vault: Pubkey,
+    fraction_mint: Pubkey,
+    auction: Pubkey,
+    bidder_metadata: Pubkey,
+    bidder: Pubkey,
+    payer: Pubkey,
+    store: Pubkey,
+    transfer_authority: Pubkey,
+    prizes: Vec<RedeemBidsPrize>,
+    win_index: u64,
+) -> Instruction {
+    let (bid_redemption, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            auction.as_ref(),
+            bidder_metadata.as_ref(),
+        ],
+        &program_id,
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new(auction_manager, false),
+        AccountMeta::new(bid_redemption, false),
+        AccountMeta::new(vault, false),
+        AccountMeta::new(fraction_mint, false),
+        AccountMeta::new_readonly(auction, false),
+        AccountMeta::new_readonly(bidder_metadata, false),
+        AccountMeta::new_readonly(bidder, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_token_vault::id(), false),
+        AccountMeta::new_readonly(spl_token_metadata::id(), false),
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(transfer_authority, false),
+    ];
+    let mut edition_offsets = vec![];
+
+    for prize in prizes {
+        let (safety_deposit_token_store, destination, safety_deposit_box) = match prize {
+            RedeemBidsPrize::TokenOnlyTransfer {
+                safety_deposit_token_store,
+                destination,
+                safety_deposit_box,
+            }
+            | RedeemBidsPrize::FullRightsTransfer {
+                safety_deposit_token_store,
+                destination,
+                safety_deposit_box,
+                ..
+            } => (safety_deposit_token_store, destination, safety_deposit_box),
+            RedeemBidsPrize::PrintingV2 {
+                safety_deposit_token_store,
+                new_edition_token_account,
+                safety_deposit_box,
+                ..
+            } => (
+                safety_deposit_token_store,
+                new_edition_token_account,
+                safety_deposit_box,
+            ),
+        };
+        let (config, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                auction_manager.as_ref(),
+                safety_deposit_box.as_ref(),
+            ],
+            &program_id,
+        );
+
+        accounts.push(AccountMeta::new(safety_deposit_token_store, false));
+        accounts.push(AccountMeta::new(destination, false));
+        accounts.push(AccountMeta::new(safety_deposit_box, false));
+        accounts.push(AccountMeta::new_readonly(config, false));
+
+        match prize {
+            RedeemBidsPrize::TokenOnlyTransfer { .. } => {}
+            RedeemBidsPrize::FullRightsTransfer {
+                metadata,
+                new_metadata_authority,
+                ..
+            } => {
+                accounts.push(AccountMeta::new(metadata, false));
+                accounts.push(AccountMeta::new_readonly(new_metadata_authority, false));
+            }
+            RedeemBidsPrize::PrintingV2 {
+                original_mint,
+                new_mint,
+                new_mint_authority,
+                edition_offset,
+                edition,
+                ..
+            } => {
+                let (prize_tracking_ticket, _) = Pubkey::find_program_address(
+                    &[
+                        PREFIX.as_bytes(),
+                        program_id.as_ref(),
+                        auction_manager.as_ref(),
+                        original_mint.as_ref(),
+                    ],
+                    &program_id,
+                );
+
+                let edition_number = edition.checked_div(EDITION_MARKER_BIT_SIZE).unwrap();
+
+                let (edition_mark_pda, _) = Pubkey::find_program_address(
+                    &[
+                        spl_token_metadata::state::PREFIX.as_bytes(),
+                        spl_token_metadata::id().as_ref(),
+                        original_mint.as_ref(),
+                        spl_token_metadata::state::EDITION.as_bytes(),
+                        edition_number.to_string().as_bytes(),
+                    ],
+                    &spl_token_metadata::id(),
+                );
+
+                let (metadata, _) = Pubkey::find_program_address(
+                    &[
+                        spl_token_metadata::state::PREFIX.as_bytes(),
+                        spl_token_metadata::id().as_ref(),
+                        original_mint.as_ref(),
+                    ],
+                    &spl_token_metadata::id(),
+                );
+
+                let (master_edition, _) = Pubkey::find_program_address(
+                    &[
+                        spl_token_metadata::state::PREFIX.as_bytes(),
+                        spl_token_metadata::id().as_ref(),
+                        original_mint.as_ref(),
+                        spl_token_metadata::state::EDITION.as_bytes(),
+                    ],
+                    &spl_token_metadata::id(),
+                );
+
+                let (new_metadata, _) = Pubkey::find_program_address(
+                    &[
+                        spl_token_metadata::state::PREFIX.as_bytes(),
+                        spl_token_metadata::id().as_ref(),
+                        new_mint.as_ref(),
+                    ],
+                    &spl_token_metadata::id(),
+                );
+
+                let (new_edition, _) = Pubkey::find_program_address(
+                    &[
+                        spl_token_metadata::state::PREFIX.as_bytes(),
+                        spl_token_metadata::id().as_ref(),
+                        new_mint.as_ref(),
+                        spl_token_metadata::state::EDITION.as_bytes(),
+                    ],
+                    &spl_token_metadata::id(),
+                );
+
+                accounts.push(AccountMeta::new(prize_tracking_ticket, false));
+                accounts.push(AccountMeta::new(new_metadata, false));
+                accounts.push(AccountMeta::new(new_edition, false));
+                accounts.push(AccountMeta::new(master_edition, false));
+                accounts.push(AccountMeta::new(new_mint, false));
+                accounts.push(AccountMeta::new(edition_mark_pda, false));
+                accounts.push(AccountMeta::new_readonly(new_mint_authority, true));
+                accounts.push(AccountMeta::new_readonly(metadata, false));
+                edition_offsets.push(edition_offset);
+            }
+        }
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::RedeemBids(RedeemBidsArgs {
+            win_index,
+            edition_offsets,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Derives the auction cache pda of an auction
+pub fn find_auction_cache_address(program_id: &Pubkey, auction: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction.as_ref(),
+            CACHE.as_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Derives the store indexer pda of a page of a store's index
+pub fn find_store_indexer_address(program_id: &Pubkey, store: &Pubkey, page: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store.as_ref(),
+            INDEX.as_bytes(),
+            page.to_string().as_bytes(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Creates an SetAuctionCache instruction
+pub fn create_set_auction_cache_instruction(
+    program_id: Pubkey,
+    payer: Pubkey,
+    auction: Pubkey,
+    safety_deposit_box: Pubkey,
+    auction_manager: Pubkey,
+    store: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(find_auction_cache_address(&program_id, &auction), false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(auction, false),
+            AccountMeta::new_readonly(safety_deposit_box, false),
+            AccountMeta::new_readonly(auction_manager, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: MetaplexInstruction::SetAuctionCache.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an SetStoreIndex instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_set_store_index_instruction(
+    program_id: Pubkey,
+    payer: Pubkey,
+    auction_cache: Pubkey,
+    store: Pubkey,
+    previous_cache: Option<Pubkey>,
+    next_cache: Option<Pubkey>,
+    page: u64,
+    offset: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(find_store_indexer_address(&program_id, &store, page), false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new(auction_cache, false),
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    if let Some(key) = previous_cache {
+        accounts.push(AccountMeta::new_readonly(key, false));
+    }
+
+    if let Some(key) = next_cache {
+        accounts.push(AccountMeta::new_readonly(key, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::SetStoreIndex(SetStoreIndexArgs { page, offset })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an RemoveAuctionCache instruction
+pub fn create_remove_auction_cache_instruction(
+    program_id: Pubkey,
+    auction_manager: Pubkey,
+    auction: Pubkey,
+    store: Pubkey,
+    auction_manager_authority: Pubkey,
+    payer: Pubkey,
+    page: u64,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(find_store_indexer_address(&program_id, &store, page), false),
+            AccountMeta::new(find_auction_cache_address(&program_id, &auction), false),
+            AccountMeta::new_readonly(auction_manager, false),
+            AccountMeta::new_readonly(auction, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(auction_manager_authority, true),
+            AccountMeta::new(payer, false),
+        ],
+        data: MetaplexInstruction::RemoveAuctionCache(RemoveAuctionCacheArgs { page })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Derives the pda of the record of a role a wallet holds on a store
+pub fn find_store_role_address(
+    program_id: &Pubkey,
+    store: &Pubkey,
+    role: StoreRole,
+    holder: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store.as_ref(),
+            ROLE.as_bytes(),
+            role.seed().as_bytes(),
+            holder.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Creates an SetWhitelistedCreator instruction, pass the role record when the admin is not the store owner
+#[allow(clippy::too_many_arguments)]
+pub fn create_set_whitelisted_creator_instruction(
+    program_id: Pubkey,
+    whitelisted_creator: Pubkey,
+    admin: Pubkey,
+    payer: Pubkey,
+    creator: Pubkey,
+    store: Pubkey,
+    role_record: Option<Pubkey>,
+    activated: bool,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(whitelisted_creator, false),
+        AccountMeta::new_readonly(admin, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(creator, false),
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    if let Some(key) = role_record {
+        accounts.push(AccountMeta::new_readonly(key, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::SetWhitelistedCreator(SetWhitelistedCreatorArgs { activated })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an SetStoreRole instruction
+pub fn create_set_store_role_instruction(
+    program_id: Pubkey,
+    owner: Pubkey,
+    payer: Pubkey,
+    holder: Pubkey,
+    store: Pubkey,
+    role: StoreRole,
+    active: bool,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(
+                find_store_role_address(&program_id, &store, role, &holder),
+                false,
+            ),
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(holder, false),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetaplexInstruction::SetStoreRole(SetStoreRoleArgs { role, active })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an TransferStoreOwnership instruction
+pub fn create_transfer_store_ownership_instruction(
+    program_id: Pubkey,
+    store: Pubkey,
+    owner: Pubkey,
+    new_owner: Pubkey,
+    payer: Pubkey,
+) -> Instruction {
+    let (new_store, _) = Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), program_id.as_ref(), new_owner.as_ref()],
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(store, false),
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(new_store, false),
+            AccountMeta::new_readonly(new_owner, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetaplexInstruction::TransferStoreOwnership
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Derives the pda of a buyer's offer on an NFT
+pub fn find_offer_address(program_id: &Pubkey, metadata: &Pubkey, buyer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.as_ref(),
+            OFFER.as_bytes(),
+            buyer.as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Creates an MakeOffer instruction, pass (buyer token account, escrow) to offer SPL tokens instead of SOL
+pub fn create_make_offer_instruction(
+    program_id: Pubkey,
+    buyer: Pubkey,
+    payer: Pubkey,
+    metadata: Pubkey,
+    spl_payment: Option<(Pubkey, Pubkey)>,
+    price: u64,
+    expires_at: UnixTimestamp,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(find_offer_address(&program_id, &metadata, &buyer), false),
+        AccountMeta::new(buyer, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    if let Some((buyer_token_account, escrow)) = spl_payment {
+        accounts.push(AccountMeta::new(buyer_token_account, false));
+        accounts.push(AccountMeta::new(escrow, false));
+        accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::MakeOffer(MakeOfferArgs { price, expires_at })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an AcceptOffer instruction, pass (escrow, seller payment account) for SPL offers.
+/// Creator accounts follow the order of the metadata's creators.
+#[allow(clippy::too_many_arguments)]
+pub fn create_accept_offer_instruction(
+    program_id: Pubkey,
+    seller: Pubkey,
+    seller_token_account: Pubkey,
+    buyer_token_account: Pubkey,
+    buyer: Pubkey,
+    metadata: Pubkey,
+    spl_payment: Option<(Pubkey, Pubkey)>,
+    creator_accounts: Vec<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(find_offer_address(&program_id, &metadata, &buyer), false),
+        AccountMeta::new(seller, true),
+        AccountMeta::new(seller_token_account, false),
+        AccountMeta::new(buyer_token_account, false),
+        AccountMeta::new(buyer, false),
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token_metadata::id(), false),
+    ];
+
+    if let Some((escrow, seller_payment_account)) = spl_payment {
+        accounts.push(AccountMeta::new(escrow, false));
+        accounts.push(AccountMeta::new(seller_payment_account, false));
+    }
+
+    for creator_account in creator_accounts {
+        accounts.push(AccountMeta::new(creator_account, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::AcceptOffer.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an CancelOffer instruction, pass (escrow, refund token account) for SPL offers
+pub fn create_cancel_offer_instruction(
+    program_id: Pubkey,
+    buyer: Pubkey,
+    metadata: Pubkey,
+    spl_refund: Option<(Pubkey, Pubkey)>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(find_offer_address(&program_id, &metadata, &buyer), false),
+        AccountMeta::new(buyer, true),
+    ];
+
+    if let Some((escrow, destination)) = spl_refund {
+        accounts.push(AccountMeta::new(escrow, false));
+        accounts.push(AccountMeta::new(destination, false));
+        accounts.push(AccountMeta::new_readonly(spl_token::id(), false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::CancelOffer.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an CancelAuctionManager instruction
+pub fn create_cancel_auction_manager_instruction(
+    program_id: Pubkey,
+    auction_manager: Pubkey,
+    auction: Pubkey,
+    authority: Pubkey,
+    store: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(auction_manager, false),
+            AccountMeta::new(auction, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(store, false),
+            AccountMeta::new_readonly(spl_auction::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+        data: MetaplexInstruction::CancelAuctionManager
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an ReturnCancelledAuctionItem instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_return_cancelled_auction_item_instruction(
+    program_id: Pubkey,
+    auction_manager: Pubkey,
+    safety_deposit_token_store: Pubkey,
+    destination: Pubkey,
+    safety_deposit_box: Pubkey,
+    vault: Pubkey,
+    fraction_mint: Pubkey,
+    auction: Pubkey,
+    store: Pubkey,
+    metadata: Pubkey,
+    original_owner: Pubkey,
+    transfer_authority: Pubkey,
+    printing_mint: Option<Pubkey>,
+) -> Instruction {
+    let (original_authority_lookup, _) = Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), auction.as_ref(), metadata.as_ref()],
+        &program_id,
+    );
+    let (safety_deposit_config, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction_manager.as_ref(),
+            safety_deposit_box.as_ref(),
+        ],
+        &program_id,
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new(auction_manager, false),
+        AccountMeta::new(safety_deposit_token_store, false),
+        AccountMeta::new(destination, false),
+        AccountMeta::new(safety_deposit_box, false),
+        AccountMeta::new(vault, false),
+        AccountMeta::new(fraction_mint, false),
+        AccountMeta::new_readonly(auction, false),
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_token_vault::id(), false),
+        AccountMeta::new_readonly(spl_token_metadata::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(original_owner, false),
+        AccountMeta::new_readonly(original_authority_lookup, false),
+        AccountMeta::new_readonly(transfer_authority, false),
+        AccountMeta::new_readonly(safety_deposit_config, false),
+    ];
+
+    if let Some(printing_mint) = printing_mint {
+        accounts.push(AccountMeta::new(printing_mint, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::ReturnCancelledAuctionItem
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Creates an MigrateStoreRecords instruction for the given whitelisted creator addresses and role holders
+pub fn create_migrate_store_records_instruction(
+    program_id: Pubkey,
+    store: Pubkey,
+    new_owner: Pubkey,
+    payer: Pubkey,
+    whitelisted_creators: Vec<Pubkey>,
+    roles: Vec<(StoreRole, Pubkey)>,
+) -> Instruction {
+    let (new_store, _) = Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), program_id.as_ref(), new_owner.as_ref()],
+        &program_id,
+    );
+    let find_whitelisted_creator = |store: &Pubkey, creator: &Pubkey| {
+        Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                store.as_ref(),
+                creator.as_ref(),
+            ],
+            &program_id,
+        )
+        .0
+    };
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(store, false),
+        AccountMeta::new_readonly(new_store, false),
+        AccountMeta::new_readonly(new_owner, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    for creator in whitelisted_creators {
+        accounts.push(AccountMeta::new_readonly(
+            find_whitelisted_creator(&store, &creator),
+            false,
+        ));
+        accounts.push(AccountMeta::new(
+            find_whitelisted_creator(&new_store, &creator),
+            false,
+        ));
+    }
+
+    for (role, holder) in roles {
+        accounts.push(AccountMeta::new_readonly(
+            find_store_role_address(&program_id, &store, role, &holder),
+            false,
+        ));
+        accounts.push(AccountMeta::new(
+            find_store_role_address(&program_id, &new_store, role, &holder),
+            false,
+        ));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetaplexInstruction::MigrateStoreRecords
+            .try_to_vec()
+            .unwrap(),
+    }
+}
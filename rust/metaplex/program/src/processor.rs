@@ -1,43 +1,75 @@
 use {
-    crate::instruction::MetaplexInstruction,
+    crate::instruction::{EmptyPaymentAccountArgs, MetaplexInstruction},
+    accept_offer::process_accept_offer,
     borsh::BorshDeserialize,
+    buy_fixed_price_listing::process_buy_fixed_price_listing,
+    cancel_auction_manager::process_cancel_auction_manager,
+    cancel_fixed_price_listing::process_cancel_fixed_price_listing,
+    cancel_offer::process_cancel_offer,
     claim_bid::process_claim_bid,
+    create_fixed_price_listing::process_create_fixed_price_listing,
     decommission_auction_manager::process_decommission_auction_manager,
     deprecated_init_auction_manager_v1::process_deprecated_init_auction_manager_v1,
     deprecated_populate_participation_printing_account::process_deprecated_populate_participation_printing_account,
     deprecated_validate_participation::process_deprecated_validate_participation,
     deprecated_validate_safety_deposit_box_v1::process_deprecated_validate_safety_deposit_box_v1,
+    empty_fixed_price_listing_payment_account::process_empty_fixed_price_listing_payment_account,
     empty_payment_account::process_empty_payment_account,
     init_auction_manager_v2::process_init_auction_manager_v2,
+    make_offer::process_make_offer,
+    migrate_store_records::process_migrate_store_records,
     redeem_bid::process_redeem_bid,
+    redeem_bids::process_redeem_bids,
     redeem_full_rights_transfer_bid::process_full_rights_transfer_bid,
     redeem_participation_bid::process_redeem_participation_bid,
     redeem_printing_v2_bid::process_redeem_printing_v2_bid,
     redeem_unused_winning_config_items_as_auctioneer::process_redeem_unused_winning_config_items_as_auctioneer,
+    remove_auction_cache::process_remove_auction_cache,
+    return_cancelled_auction_item::process_return_cancelled_auction_item,
+    set_auction_cache::process_set_auction_cache,
     set_store::process_set_store,
+    set_store_index::process_set_store_index,
+    set_store_role::process_set_store_role,
     set_whitelisted_creator::process_set_whitelisted_creator,
     solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey},
     start_auction::process_start_auction,
+    transfer_store_ownership::process_transfer_store_ownership,
     validate_safety_deposit_box_v2::process_validate_safety_deposit_box_v2,
     withdraw_master_edition::process_withdraw_master_edition,
 };
 
+pub mod accept_offer;
+pub mod buy_fixed_price_listing;
+pub mod cancel_auction_manager;
+pub mod cancel_fixed_price_listing;
+pub mod cancel_offer;
 pub mod claim_bid;
+pub mod create_fixed_price_listing;
 pub mod decommission_auction_manager;
 pub mod deprecated_init_auction_manager_v1;
 pub mod deprecated_populate_participation_printing_account;
 pub mod deprecated_validate_participation;
 pub mod deprecated_validate_safety_deposit_box_v1;
+pub mod empty_fixed_price_listing_payment_account;
 pub mod empty_payment_account;
 pub mod init_auction_manager_v2;
+pub mod make_offer;
+pub mod migrate_store_records;
 pub mod redeem_bid;
+pub mod redeem_bids;
 pub mod redeem_full_rights_transfer_bid;
 pub mod redeem_participation_bid;
 pub mod redeem_printing_v2_bid;
 pub mod redeem_unused_winning_config_items_as_auctioneer;
+pub mod remove_auction_cache;
+pub mod return_cancelled_auction_item;
+pub mod set_auction_cache;
 pub mod set_store;
+pub mod set_store_index;
+pub mod set_store_role;
 pub mod set_whitelisted_creator;
 pub mod start_auction;
+pub mod transfer_store_ownership;
 pub mod validate_safety_deposit_box_v2;
 pub mod withdraw_master_edition;
 
@@ -82,11 +114,11 @@ pub fn process_instruction<'a>(
         }
         MetaplexInstruction::EmptyPaymentAccount(args) => {
             msg!("Instruction: Empty Payment Account");
-            process_empty_payment_account(program_id, accounts, args)
+            process_empty_payment_account(program_id, accounts, args, false)
         }
         MetaplexInstruction::SetStore(args) => {
             msg!("Instruction: Set Store");
-            process_set_store(program_id, accounts, args.public)
+            process_set_store(program_id, accounts, args.public, None)
         }
         MetaplexInstruction::SetWhitelistedCreator(args) => {
             msg!("Instruction: Set Whitelisted Creator");
@@ -143,5 +175,86 @@ pub fn process_instruction<'a>(
             msg!("Instruction: Redeem Participation Bid V3");
             process_redeem_participation_bid(program_id, accounts, false, args.win_index)
         }
+        MetaplexInstruction::SetStoreV2(args) => {
+            msg!("Instruction: Set Store V2");
+            process_set_store(program_id, accounts, args.public, Some(args.platform_fee))
+        }
+        MetaplexInstruction::EmptyPlatformFee(args) => {
+            msg!("Instruction: Empty Platform Fee");
+            process_empty_payment_account(
+                program_id,
+                accounts,
+                EmptyPaymentAccountArgs {
+                    winning_config_index: args.winning_config_index,
+                    winning_config_item_index: args.winning_config_item_index,
+                    creator_index: None,
+                },
+                true,
+            )
+        }
+        MetaplexInstruction::CreateFixedPriceListing(args) => {
+            msg!("Instruction: Create Fixed Price Listing");
+            process_create_fixed_price_listing(program_id, accounts, args.price, args.listing_type)
+        }
+        MetaplexInstruction::BuyFixedPriceListing => {
+            msg!("Instruction: Buy Fixed Price Listing");
+            process_buy_fixed_price_listing(program_id, accounts)
+        }
+        MetaplexInstruction::CancelFixedPriceListing => {
+            msg!("Instruction: Cancel Fixed Price Listing");
+            process_cancel_fixed_price_listing(program_id, accounts)
+        }
+        MetaplexInstruction::EmptyFixedPriceListingPaymentAccount(args) => {
+            msg!("Instruction: Empty Fixed Price Listing Payment Account");
+            process_empty_fixed_price_listing_payment_account(program_id, accounts, args)
+        }
+        MetaplexInstruction::RedeemBids(args) => {
+            msg!("Instruction: Redeem Bids");
+            process_redeem_bids(program_id, accounts, args)
+        }
+        MetaplexInstruction::SetAuctionCache => {
+            msg!("Instruction: Set Auction Cache");
+            process_set_auction_cache(program_id, accounts)
+        }
+        MetaplexInstruction::SetStoreIndex(args) => {
+            msg!("Instruction: Set Store Index");
+            process_set_store_index(program_id, accounts, args.page, args.offset)
+        }
+        MetaplexInstruction::RemoveAuctionCache(args) => {
+            msg!("Instruction: Remove Auction Cache");
+            process_remove_auction_cache(program_id, accounts, args.page)
+        }
+        MetaplexInstruction::SetStoreRole(args) => {
+            msg!("Instruction: Set Store Role");
+            process_set_store_role(program_id, accounts, args.role, args.active)
+        }
+        MetaplexInstruction::TransferStoreOwnership => {
+            msg!("Instruction: Transfer Store Ownership");
+            process_transfer_store_ownership(program_id, accounts)
+        }
+        MetaplexInstruction::MakeOffer(args) => {
+            msg!("Instruction: Make Offer");
+            process_make_offer(program_id, accounts, args.price, args.expires_at)
+        }
+        MetaplexInstruction::AcceptOffer => {
+            msg!("Instruction: Accept Offer");
+            process_accept_offer(program_id, accounts)
+        }
+        MetaplexInstruction::CancelOffer => {
+            msg!("Instruction: Cancel Offer");
+            process_cancel_offer(program_id, accounts)
+        }
+        MetaplexInstruction::CancelAuctionManager => {
+            msg!("Instruction: Cancel Auction Manager");
+            process_cancel_auction_manager(program_id, accounts)
+        }
+        MetaplexInstruction::ReturnCancelledAuctionItem => {
+            msg!("Instruction: Return Cancelled Auction Item");
+            process_return_cancelled_auction_item(program_id, accounts)
+        }
+        MetaplexInstruction::MigrateStoreRecords => {
+            msg!("Instruction: Migrate Store Records");
+            process_migrate_store_records(program_id, accounts)
+        }
     }
 }
@@ -0,0 +1,277 @@
+use {
+    crate::{
+        error::MetaplexError,
+        processor::{
+            cancel_offer::close_offer_escrow,
+            empty_payment_account::get_verified_creator_payout_multipliers,
+        },
+        state::{Offer, OFFER, PREFIX},
+        utils::{
+            assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+            close_program_account, get_amount_from_token_account, spl_token_transfer,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::Clock,
+        entrypoint::ProgramResult,
+        program::invoke,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+    spl_token::state::Account,
+    spl_token_metadata::{instruction::update_primary_sale_happened_via_token, state::Metadata},
+};
+
+/// Pays `amount` out of an offer, either as tokens from its escrow or as lamports from the
+/// offer account itself.
+fn pay_from_offer<'a>(
+    offer_info: &AccountInfo<'a>,
+    escrow_info: Option<&AccountInfo<'a>>,
+    destination_info: &AccountInfo<'a>,
+    token_program_info: &AccountInfo<'a>,
+    amount: u64,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    match escrow_info {
+        Some(escrow_info) => spl_token_transfer(
+            escrow_info.clone(),
+            destination_info.clone(),
+            amount,
+            offer_info.clone(),
+            signer_seeds,
+            token_program_info.clone(),
+        ),
+        None => {
+            **offer_info.lamports.borrow_mut() = offer_info
+                .lamports()
+                .checked_sub(amount)
+                .ok_or(MetaplexError::NumericalOverflowError)?;
+            **destination_info.lamports.borrow_mut() = destination_info
+                .lamports()
+                .checked_add(amount)
+                .ok_or(MetaplexError::NumericalOverflowError)?;
+            Ok(())
+        }
+    }
+}
+
+pub fn process_accept_offer<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let offer_info = next_account_info(account_info_iter)?;
+    let seller_info = next_account_info(account_info_iter)?;
+    let seller_token_info = next_account_info(account_info_iter)?;
+    let buyer_token_info = next_account_info(account_info_iter)?;
+    let buyer_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let token_metadata_program_info = next_account_info(account_info_iter)?;
+
+    let clock = Clock::from_account_info(clock_info)?;
+
+    assert_signer(seller_info)?;
+    assert_owned_by(offer_info, program_id)?;
+    assert_owned_by(metadata_info, &spl_token_metadata::id())?;
+    assert_owned_by(seller_token_info, &spl_token::id())?;
+    assert_owned_by(buyer_token_info, &spl_token::id())?;
+
+    if *token_program_info.key != spl_token::id() {
+        return Err(MetaplexError::TokenProgramMismatch.into());
+    }
+
+    if *token_metadata_program_info.key != spl_token_metadata::id() {
+        return Err(MetaplexError::TokenMetadataProgramMismatch.into());
+    }
+
+    let offer = Offer::from_account_info(offer_info)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    if offer.metadata != *metadata_info.key || offer.buyer != *buyer_info.key {
+        return Err(MetaplexError::OfferMismatch.into());
+    }
+
+    if clock.unix_timestamp > offer.expires_at {
+        return Err(MetaplexError::OfferExpired.into());
+    }
+
+    let seller_token: Account = assert_initialized(seller_token_info)?;
+    if seller_token.mint != metadata.mint {
+        return Err(MetaplexError::OfferMismatch.into());
+    }
+
+    if seller_token.owner != *seller_info.key {
+        return Err(MetaplexError::IncorrectOwner.into());
+    }
+
+    let buyer_token: Account = assert_initialized(buyer_token_info)?;
+    if buyer_token.mint != metadata.mint {
+        return Err(MetaplexError::DestinationMintMismatch.into());
+    }
+
+    if buyer_token.owner != *buyer_info.key {
+        return Err(MetaplexError::IncorrectOwner.into());
+    }
+
+    let bump_seed = assert_derivation(
+        program_id,
+        offer_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata_info.key.as_ref(),
+            OFFER.as_bytes(),
+            buyer_info.key.as_ref(),
+        ],
+    )?;
+    let authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        metadata_info.key.as_ref(),
+        OFFER.as_bytes(),
+        buyer_info.key.as_ref(),
+        &[bump_seed],
+    ];
+
+    // SPL offers pay the seller into a token account of the escrowed mint, SOL offers pay
+    // the seller wallet directly.
+    let (escrow_info, seller_payment_info, payment_mint) = match offer.escrow {
+        Some(escrow) => {
+            let escrow_info = next_account_info(account_info_iter)?;
+            let seller_payment_info = next_account_info(account_info_iter)?;
+            if escrow != *escrow_info.key {
+                return Err(MetaplexError::OfferMismatch.into());
+            }
+
+            let escrow_account: Account = assert_initialized(escrow_info)?;
+            let seller_payment: Account = assert_initialized(seller_payment_info)?;
+            if seller_payment.mint != escrow_account.mint {
+                return Err(MetaplexError::DestinationMintMismatch.into());
+            }
+
+            (
+                Some(escrow_info),
+                seller_payment_info,
+                Some(escrow_account.mint),
+            )
+        }
+        None => (None, seller_info, None),
+    };
+
+    // Creators get all of the proceeds of the primary sale, which this sale is if none happened
+    // yet, and royalties of every sale after it.
+    if !metadata.primary_sale_happened {
+        invoke(
+            &update_primary_sale_happened_via_token(
+                *token_metadata_program_info.key,
+                *metadata_info.key,
+                *seller_info.key,
+                *seller_token_info.key,
+            ),
+            &[
+                token_metadata_program_info.clone(),
+                metadata_info.clone(),
+                seller_info.clone(),
+                seller_token_info.clone(),
+            ],
+        )?;
+    }
+
+    spl_token_transfer(
+        seller_token_info.clone(),
+        buyer_token_info.clone(),
+        1,
+        seller_info.clone(),
+        &[],
+        token_program_info.clone(),
+    )?;
+
+    let mut paid_to_creators: u64 = 0;
+    if let Some(creators) = &metadata.data.creators {
+        for (index, creator) in creators.iter().enumerate() {
+            let creator_info = next_account_info(account_info_iter)?;
+            match payment_mint {
+                Some(mint) => {
+                    let creator_account: Account = assert_initialized(creator_info)?;
+                    if creator_account.owner != creator.address || creator_account.mint != mint {
+                        return Err(MetaplexError::CreatorAccountMismatch.into());
+                    }
+                }
+                None => {
+                    if *creator_info.key != creator.address {
+                        return Err(MetaplexError::CreatorAccountMismatch.into());
+                    }
+                }
+            }
+
+            if !creator.verified {
+                continue;
+            }
+
+            let (numerator, artist_further_multiplier) = get_verified_creator_payout_multipliers(
+                &metadata,
+                metadata.primary_sale_happened,
+                &Some(index as u8),
+                false,
+            )?;
+            let amount = (offer.price as u128)
+                .checked_mul(numerator)
+                .ok_or(MetaplexError::NumericalOverflowError)?
+                .checked_mul(artist_further_multiplier)
+                .ok_or(MetaplexError::NumericalOverflowError)?
+                .checked_div(10000 * 10000)
+                .ok_or(MetaplexError::NumericalOverflowError)? as u64;
+
+            pay_from_offer(
+                offer_info,
+                escrow_info,
+                creator_info,
+                token_program_info,
+                amount,
+                authority_seeds,
+            )?;
+
+            paid_to_creators = paid_to_creators
+                .checked_add(amount)
+                .ok_or(MetaplexError::NumericalOverflowError)?;
+        }
+    }
+
+    // The seller gets everything that isn't royalties, including rounding dust and anything else
+    // sent to the escrow, which has to be empty before it can be closed.
+    let escrowed = match escrow_info {
+        Some(escrow_info) => get_amount_from_token_account(escrow_info)?,
+        None => offer.price,
+    };
+    pay_from_offer(
+        offer_info,
+        escrow_info,
+        seller_payment_info,
+        token_program_info,
+        escrowed
+            .checked_sub(paid_to_creators)
+            .ok_or(MetaplexError::NumericalOverflowError)?,
+        authority_seeds,
+    )?;
+
+    if let Some(escrow_info) = escrow_info {
+        close_offer_escrow(
+            escrow_info,
+            buyer_info,
+            offer_info,
+            token_program_info,
+            authority_seeds,
+        )?;
+    }
+
+    close_program_account(offer_info, buyer_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            FixedPriceListing, FixedPriceListingStatus, FixedPriceListingType, Store, LISTING,
+            PREFIX,
+        },
+        utils::{
+            assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+            spl_token_transfer,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        pubkey::Pubkey,
+    },
+    spl_token::state::Account,
+    spl_token_metadata::{
+        instruction::{
+            mint_new_edition_from_master_edition_via_token, update_primary_sale_happened_via_token,
+        },
+        state::Metadata,
+        utils::get_supply_off_master_edition,
+    },
+};
+
+pub fn process_buy_fixed_price_listing<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let listing_info = next_account_info(account_info_iter)?;
+    let accept_payment_info = next_account_info(account_info_iter)?;
+    let paying_token_account_info = next_account_info(account_info_iter)?;
+    let transfer_authority_info = next_account_info(account_info_iter)?;
+    let item_token_account_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let token_metadata_program_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(transfer_authority_info)?;
+    assert_signer(payer_info)?;
+    assert_owned_by(listing_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let mut listing = FixedPriceListing::from_account_info(listing_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    if listing.status != FixedPriceListingStatus::Active {
+        return Err(MetaplexError::FixedPriceListingNotActive.into());
+    }
+
+    if listing.store != *store_info.key
+        || listing.accept_payment != *accept_payment_info.key
+        || listing.item_token_account != *item_token_account_info.key
+        || listing.metadata != *metadata_info.key
+    {
+        return Err(MetaplexError::FixedPriceListingMismatch.into());
+    }
+
+    if *token_program_info.key != store.token_program {
+        return Err(MetaplexError::TokenProgramMismatch.into());
+    }
+
+    if *token_metadata_program_info.key != store.token_metadata_program {
+        return Err(MetaplexError::AuctionManagerTokenMetadataProgramMismatch.into());
+    }
+
+    let paying_token_account: Account = assert_initialized(paying_token_account_info)?;
+    let accept_payment: Account = assert_initialized(accept_payment_info)?;
+    if paying_token_account.mint != accept_payment.mint {
+        return Err(MetaplexError::AcceptPaymentMintMismatch.into());
+    }
+
+    let bump_seed = assert_derivation(
+        program_id,
+        listing_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            item_token_account_info.key.as_ref(),
+            LISTING.as_bytes(),
+        ],
+    )?;
+    let authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        item_token_account_info.key.as_ref(),
+        LISTING.as_bytes(),
+        &[bump_seed],
+    ];
+
+    if listing.price > 0 {
+        spl_token_transfer(
+            paying_token_account_info.clone(),
+            accept_payment_info.clone(),
+            listing.price,
+            transfer_authority_info.clone(),
+            &[],
+            token_program_info.clone(),
+        )?;
+    }
+
+    match listing.listing_type {
+        FixedPriceListingType::Sale | FixedPriceListingType::Resale => {
+            let destination_info = next_account_info(account_info_iter)?;
+            let metadata = Metadata::from_account_info(metadata_info)?;
+
+            if !metadata.primary_sale_happened {
+                invoke_signed(
+                    &update_primary_sale_happened_via_token(
+                        *token_metadata_program_info.key,
+                        *metadata_info.key,
+                        *listing_info.key,
+                        *item_token_account_info.key,
+                    ),
+                    &[
+                        token_metadata_program_info.clone(),
+                        metadata_info.clone(),
+                        listing_info.clone(),
+                        item_token_account_info.clone(),
+                    ],
+                    &[authority_seeds],
+                )?;
+            }
+
+            spl_token_transfer(
+                item_token_account_info.clone(),
+                destination_info.clone(),
+                1,
+                listing_info.clone(),
+                authority_seeds,
+                token_program_info.clone(),
+            )?;
+
+            listing.status = FixedPriceListingStatus::Sold;
+        }
+        FixedPriceListingType::Prints => {
+            let new_metadata_info = next_account_info(account_info_iter)?;
+            let new_edition_info = next_account_info(account_info_iter)?;
+            let master_edition_info = next_account_info(account_info_iter)?;
+            let new_mint_info = next_account_info(account_info_iter)?;
+            let edition_marker_info = next_account_info(account_info_iter)?;
+            let new_mint_authority_info = next_account_info(account_info_iter)?;
+            let new_metadata_update_authority_info = next_account_info(account_info_iter)?;
+
+            assert_owned_by(master_edition_info, &store.token_metadata_program)?;
+            let item_token_account: Account = assert_initialized(item_token_account_info)?;
+
+            let edition = get_supply_off_master_edition(master_edition_info)?
+                .checked_add(1)
+                .ok_or(MetaplexError::NumericalOverflowError)?;
+
+            invoke_signed(
+                &mint_new_edition_from_master_edition_via_token(
+                    *token_metadata_program_info.key,
+                    *new_metadata_info.key,
+                    *new_edition_info.key,
+                    *master_edition_info.key,
+                    *new_mint_info.key,
+                    *new_mint_authority_info.key,
+                    *payer_info.key,
+                    *listing_info.key,
+                    *item_token_account_info.key,
+                    *new_metadata_update_authority_info.key,
+                    *metadata_info.key,
+                    item_token_account.mint,
+                    edition,
+                ),
+                &[
+                    token_metadata_program_info.clone(),
+                    new_metadata_info.clone(),
+                    new_edition_info.clone(),
+                    master_edition_info.clone(),
+                    new_mint_info.clone(),
+                    edition_marker_info.clone(),
+                    new_mint_authority_info.clone(),
+                    payer_info.clone(),
+                    listing_info.clone(),
+                    item_token_account_info.clone(),
+                    new_metadata_update_authority_info.clone(),
+                    metadata_info.clone(),
+                    token_program_info.clone(),
+                    system_info.clone(),
+                    rent_info.clone(),
+                ],
+                &[authority_seeds],
+            )?;
+        }
+    }
+
+    listing.sold = listing
+        .sold
+        .checked_add(1)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    listing.total_collected = listing
+        .total_collected
+        .checked_add(listing.price)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+
+    listing.serialize(&mut *listing_info.data.borrow_mut())?;
+
+    Ok(())
+}
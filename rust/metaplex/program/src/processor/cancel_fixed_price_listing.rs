@@ -0,0 +1,80 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{FixedPriceListing, FixedPriceListingStatus, LISTING, PREFIX},
+        utils::{
+            assert_derivation, assert_owned_by, assert_signer, get_amount_from_token_account,
+            spl_token_transfer,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_cancel_fixed_price_listing<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let listing_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let item_token_account_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(authority_info)?;
+    assert_owned_by(listing_info, program_id)?;
+
+    let mut listing = FixedPriceListing::from_account_info(listing_info)?;
+
+    if listing.authority != *authority_info.key {
+        return Err(MetaplexError::InvalidTransferAuthority.into());
+    }
+
+    if listing.item_token_account != *item_token_account_info.key {
+        return Err(MetaplexError::FixedPriceListingMismatch.into());
+    }
+
+    if listing.status != FixedPriceListingStatus::Active {
+        return Err(MetaplexError::FixedPriceListingNotActive.into());
+    }
+
+    let bump_seed = assert_derivation(
+        program_id,
+        listing_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            item_token_account_info.key.as_ref(),
+            LISTING.as_bytes(),
+        ],
+    )?;
+    let authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        item_token_account_info.key.as_ref(),
+        LISTING.as_bytes(),
+        &[bump_seed],
+    ];
+
+    let amount = get_amount_from_token_account(item_token_account_info)?;
+    if amount > 0 {
+        spl_token_transfer(
+            item_token_account_info.clone(),
+            destination_info.clone(),
+            amount,
+            listing_info.clone(),
+            authority_seeds,
+            token_program_info.clone(),
+        )?;
+    }
+
+    listing.status = FixedPriceListingStatus::Cancelled;
+    listing.serialize(&mut *listing_info.data.borrow_mut())?;
+
+    Ok(())
+}
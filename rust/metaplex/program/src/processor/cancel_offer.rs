@@ -0,0 +1,127 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{Offer, OFFER, PREFIX},
+        utils::{
+            assert_derivation, assert_owned_by, assert_signer, close_program_account,
+            get_amount_from_token_account, spl_token_transfer,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program::invoke_signed,
+        pubkey::Pubkey,
+    },
+};
+
+/// Closes an emptied escrow token account of an offer, returning its rent to the buyer.
+pub fn close_offer_escrow<'a>(
+    escrow_info: &AccountInfo<'a>,
+    buyer_info: &AccountInfo<'a>,
+    offer_info: &AccountInfo<'a>,
+    token_program_info: &AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &spl_token::instruction::close_account(
+            token_program_info.key,
+            escrow_info.key,
+            buyer_info.key,
+            offer_info.key,
+            &[],
+        )?,
+        &[
+            escrow_info.clone(),
+            buyer_info.clone(),
+            offer_info.clone(),
+            token_program_info.clone(),
+        ],
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+pub fn process_cancel_offer<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let offer_info = next_account_info(account_info_iter)?;
+    let buyer_info = next_account_info(account_info_iter)?;
+    // Only present when the offer is in SPL tokens
+    let escrow_info = next_account_info(account_info_iter).ok();
+    let destination_info = next_account_info(account_info_iter).ok();
+    let token_program_info = next_account_info(account_info_iter).ok();
+
+    assert_signer(buyer_info)?;
+    assert_owned_by(offer_info, program_id)?;
+
+    let offer = Offer::from_account_info(offer_info)?;
+
+    if offer.buyer != *buyer_info.key {
+        return Err(MetaplexError::OfferMismatch.into());
+    }
+
+    let bump_seed = assert_derivation(
+        program_id,
+        offer_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            offer.metadata.as_ref(),
+            OFFER.as_bytes(),
+            buyer_info.key.as_ref(),
+        ],
+    )?;
+    let authority_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        offer.metadata.as_ref(),
+        OFFER.as_bytes(),
+        buyer_info.key.as_ref(),
+        &[bump_seed],
+    ];
+
+    if let Some(escrow) = offer.escrow {
+        let (escrow_info, destination_info, token_program_info) =
+            match (escrow_info, destination_info, token_program_info) {
+                (Some(e), Some(d), Some(t)) => (e, d, t),
+                _ => return Err(MetaplexError::InvalidOperation.into()),
+            };
+
+        if escrow != *escrow_info.key {
+            return Err(MetaplexError::OfferMismatch.into());
+        }
+
+        if *token_program_info.key != spl_token::id() {
+            return Err(MetaplexError::TokenProgramMismatch.into());
+        }
+
+        let amount = get_amount_from_token_account(escrow_info)?;
+        if amount > 0 {
+            spl_token_transfer(
+                escrow_info.clone(),
+                destination_info.clone(),
+                amount,
+                offer_info.clone(),
+                authority_seeds,
+                token_program_info.clone(),
+            )?;
+        }
+
+        close_offer_escrow(
+            escrow_info,
+            buyer_info,
+            offer_info,
+            token_program_info,
+            authority_seeds,
+        )?;
+    }
+
+    // SOL offers hold the escrowed amount as lamports, so this refunds it along with the rent.
+    close_program_account(offer_info, buyer_info)?;
+
+    Ok(())
+}
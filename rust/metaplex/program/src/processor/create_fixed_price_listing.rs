@@ -0,0 +1,186 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            FixedPriceListing, FixedPriceListingStatus, FixedPriceListingType, Key, Store, LISTING,
+            MAX_FIXED_PRICE_LISTING_SIZE, PREFIX,
+        },
+        utils::{
+            assert_at_least_one_creator_matches_or_store_public_and_all_verified,
+            assert_derivation, assert_edition_valid, assert_initialized, assert_owned_by,
+            assert_rent_exempt, assert_signer, create_or_allocate_account_raw, spl_token_transfer,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        program_option::COption,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_token::state::Account,
+    spl_token_metadata::state::Metadata,
+};
+
+pub fn process_create_fixed_price_listing<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    price: u64,
+    listing_type: FixedPriceListingType,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let listing_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let source_info = next_account_info(account_info_iter)?;
+    let item_token_account_info = next_account_info(account_info_iter)?;
+    let accept_payment_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let whitelisted_creator_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    let rent = &Rent::from_account_info(&rent_info)?;
+
+    assert_signer(authority_info)?;
+    assert_signer(payer_info)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let store = Store::from_account_info(store_info)?;
+    if store.successor.is_some() {
+        return Err(MetaplexError::StoreMigrated.into());
+    }
+
+    if *token_program_info.key != store.token_program {
+        return Err(MetaplexError::TokenProgramMismatch.into());
+    }
+
+    assert_owned_by(metadata_info, &store.token_metadata_program)?;
+    assert_owned_by(source_info, &store.token_program)?;
+    assert_owned_by(item_token_account_info, &store.token_program)?;
+    assert_owned_by(accept_payment_info, &store.token_program)?;
+    assert_rent_exempt(rent, item_token_account_info)?;
+    assert_rent_exempt(rent, accept_payment_info)?;
+
+    if !listing_info.data_is_empty() {
+        return Err(MetaplexError::AlreadyInitialized.into());
+    }
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    let item_token_account: Account = assert_initialized(item_token_account_info)?;
+    let accept_payment: Account = assert_initialized(accept_payment_info)?;
+
+    let bump_seed = assert_derivation(
+        program_id,
+        listing_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            item_token_account_info.key.as_ref(),
+            LISTING.as_bytes(),
+        ],
+    )?;
+
+    if item_token_account.mint != metadata.mint {
+        return Err(MetaplexError::SafetyDepositBoxMetadataMismatch.into());
+    }
+
+    if item_token_account.owner != *listing_info.key {
+        return Err(MetaplexError::IncorrectOwner.into());
+    }
+
+    if item_token_account.amount != 0 {
+        return Err(MetaplexError::InvalidOperation.into());
+    }
+
+    if item_token_account.delegate != COption::None {
+        return Err(MetaplexError::DelegateShouldBeNone.into());
+    }
+
+    if item_token_account.close_authority != COption::None {
+        return Err(MetaplexError::CloseAuthorityShouldBeNone.into());
+    }
+
+    if accept_payment.owner != *listing_info.key {
+        return Err(MetaplexError::AcceptPaymentOwnerMismatch.into());
+    }
+
+    if accept_payment.delegate != COption::None {
+        return Err(MetaplexError::DelegateShouldBeNone.into());
+    }
+
+    if accept_payment.close_authority != COption::None {
+        return Err(MetaplexError::CloseAuthorityShouldBeNone.into());
+    }
+
+    if listing_type == FixedPriceListingType::Resale && !metadata.primary_sale_happened {
+        return Err(MetaplexError::PrimarySaleRequired.into());
+    }
+
+    if listing_type == FixedPriceListingType::Prints {
+        assert_owned_by(edition_info, &store.token_metadata_program)?;
+        assert_edition_valid(&store.token_metadata_program, &metadata.mint, edition_info)?;
+        if edition_info.data.borrow()[0] != spl_token_metadata::state::Key::MasterEditionV2 as u8 {
+            return Err(MetaplexError::MasterEditionV2Required.into());
+        }
+    }
+
+    // Any holder can resell, the whitelist only decides whose work gets its primary sale here
+    if listing_type != FixedPriceListingType::Resale {
+        assert_at_least_one_creator_matches_or_store_public_and_all_verified(
+            program_id,
+            &metadata,
+            whitelisted_creator_info,
+            store_info,
+        )?;
+    }
+
+    create_or_allocate_account_raw(
+        *program_id,
+        listing_info,
+        rent_info,
+        system_info,
+        payer_info,
+        MAX_FIXED_PRICE_LISTING_SIZE,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            item_token_account_info.key.as_ref(),
+            LISTING.as_bytes(),
+            &[bump_seed],
+        ],
+    )?;
+
+    spl_token_transfer(
+        source_info.clone(),
+        item_token_account_info.clone(),
+        1,
+        authority_info.clone(),
+        &[],
+        token_program_info.clone(),
+    )?;
+
+    let mut listing = FixedPriceListing::from_account_info(listing_info)?;
+    listing.key = Key::FixedPriceListingV1;
+    listing.store = *store_info.key;
+    listing.authority = *authority_info.key;
+    listing.metadata = *metadata_info.key;
+    listing.item_token_account = *item_token_account_info.key;
+    listing.accept_payment = *accept_payment_info.key;
+    listing.price = price;
+    listing.listing_type = listing_type;
+    listing.status = FixedPriceListingStatus::Active;
+    listing.primary_sale_happened = metadata.primary_sale_happened;
+    listing.sold = 0;
+    listing.total_collected = 0;
+    listing.platform_fee = store.platform_fee;
+
+    listing.serialize(&mut *listing_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -78,7 +78,7 @@ pub fn process_deprecated_init_auction_manager_v1(
                 safety_deposit_box_found_lookup[item.safety_deposit_box_index as usize] = true
             }
 
-            if item.safety_deposit_box_index > vault.token_type_count {
+            if item.safety_deposit_box_index as u32 > vault.token_type_count {
                 return Err(MetaplexError::InvalidSafetyDepositBox.into());
             }
 
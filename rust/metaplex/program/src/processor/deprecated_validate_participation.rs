@@ -95,7 +95,6 @@ pub fn process_deprecated_validate_participation(
     // Check creators
     assert_at_least_one_creator_matches_or_store_public_and_all_verified(
         program_id,
-        &auction_manager,
         &open_edition_metadata,
         whitelisted_creator_info,
         store_info,
@@ -145,11 +144,11 @@ pub fn process_deprecated_validate_participation(
     }
 
     if let Some(participation_config) = &auction_manager.settings.participation_config {
-        if participation_config.safety_deposit_box_index > vault.token_type_count {
+        if participation_config.safety_deposit_box_index as u32 > vault.token_type_count {
             return Err(MetaplexError::InvalidSafetyDepositBox.into());
         }
 
-        if participation_config.safety_deposit_box_index != safety_deposit.order {
+        if participation_config.safety_deposit_box_index as u32 != safety_deposit.order {
             return Err(MetaplexError::SafetyDepositIndexMismatch.into());
         }
 
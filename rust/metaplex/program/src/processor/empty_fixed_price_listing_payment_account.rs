@@ -0,0 +1,226 @@
+use {
+    crate::{
+        error::MetaplexError,
+        instruction::EmptyFixedPriceListingPaymentAccountArgs,
+        processor::empty_payment_account::{
+            apply_platform_fee, assert_destination_ownership_validity, get_payout_multipliers,
+            get_verified_creator_payout_multipliers,
+        },
+        state::{
+            FixedPriceListing, FixedPriceListingType, Key, PayoutTicket, Store, LISTING,
+            MAX_PAYOUT_TICKET_SIZE, PREFIX,
+        },
+        utils::{
+            assert_derivation, assert_initialized, assert_owned_by, assert_rent_exempt,
+            create_or_allocate_account_raw, spl_token_transfer,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_token::state::Account,
+    spl_token_metadata::state::Metadata,
+};
+
+fn calculate_owed_amount(
+    listing: &FixedPriceListing,
+    metadata: &Metadata,
+    creator_index: &Option<u8>,
+    platform_fee_basis_points: Option<u16>,
+    platform_fee_payout: bool,
+) -> Result<u64, ProgramError> {
+    let amount_available_to_split = apply_platform_fee(
+        listing.total_collected as u128,
+        platform_fee_basis_points,
+        platform_fee_payout,
+    )?;
+
+    let (numerator, artist_further_multiplier) =
+        if listing.listing_type == FixedPriceListingType::Resale {
+            get_verified_creator_payout_multipliers(
+                metadata,
+                listing.primary_sale_happened,
+                creator_index,
+                platform_fee_payout,
+            )?
+        } else {
+            get_payout_multipliers(
+                metadata,
+                listing.primary_sale_happened,
+                creator_index,
+                platform_fee_payout,
+            )?
+        };
+
+    let final_amount_available_to_split = amount_available_to_split
+        .checked_mul(numerator)
+        .ok_or(MetaplexError::NumericalOverflowError)?
+        .checked_mul(artist_further_multiplier)
+        .ok_or(MetaplexError::NumericalOverflowError)?
+        .checked_div(10000 * 10000)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    msg!("Final amount mult {:?}", final_amount_available_to_split);
+
+    Ok(final_amount_available_to_split as u64)
+}
+
+pub fn process_empty_fixed_price_listing_payment_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: EmptyFixedPriceListingPaymentAccountArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let accept_payment_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let listing_info = next_account_info(account_info_iter)?;
+    let payout_ticket_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    let rent = &Rent::from_account_info(&rent_info)?;
+
+    assert_owned_by(listing_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    if !payout_ticket_info.data_is_empty() {
+        assert_owned_by(payout_ticket_info, program_id)?;
+    }
+
+    let listing = FixedPriceListing::from_account_info(listing_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    if *token_program_info.key != store.token_program {
+        return Err(MetaplexError::TokenProgramMismatch.into());
+    }
+
+    assert_owned_by(destination_info, token_program_info.key)?;
+    assert_owned_by(accept_payment_info, token_program_info.key)?;
+    assert_owned_by(metadata_info, &store.token_metadata_program)?;
+    assert_rent_exempt(rent, destination_info)?;
+
+    if listing.store != *store_info.key
+        || listing.accept_payment != *accept_payment_info.key
+        || listing.metadata != *metadata_info.key
+    {
+        return Err(MetaplexError::FixedPriceListingMismatch.into());
+    }
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    let destination: Account = assert_initialized(destination_info)?;
+    let accept_payment: Account = assert_initialized(accept_payment_info)?;
+
+    if destination.mint != accept_payment.mint {
+        return Err(MetaplexError::AcceptPaymentMintMismatch.into());
+    }
+
+    assert_destination_ownership_validity(
+        &listing.authority,
+        &metadata,
+        destination_info,
+        &destination,
+        &store,
+        listing.platform_fee,
+        args.creator_index,
+        args.platform_fee_payout,
+    )?;
+
+    let creator_index_key: String = match args.creator_index {
+        _ if args.platform_fee_payout => "platform".to_owned(),
+        Some(val) => val.to_string(),
+        None => "auctioneer".to_owned(),
+    };
+
+    let payout_bump = assert_derivation(
+        program_id,
+        payout_ticket_info,
+        &[
+            PREFIX.as_bytes(),
+            listing_info.key.as_ref(),
+            creator_index_key.as_bytes(),
+            &destination.owner.as_ref(),
+        ],
+    )?;
+
+    if payout_ticket_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            payout_ticket_info,
+            rent_info,
+            system_info,
+            payer_info,
+            MAX_PAYOUT_TICKET_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                listing_info.key.as_ref(),
+                creator_index_key.as_bytes(),
+                &destination.owner.as_ref(),
+                &[payout_bump],
+            ],
+        )?;
+    }
+
+    let mut payout_ticket = PayoutTicket::from_account_info(payout_ticket_info)?;
+    payout_ticket.recipient = destination.owner;
+    payout_ticket.key = Key::PayoutTicketV1;
+
+    let amount = calculate_owed_amount(
+        &listing,
+        &metadata,
+        &args.creator_index,
+        listing.platform_fee.map(|fee| fee.basis_points),
+        args.platform_fee_payout,
+    )?;
+
+    let final_amount = amount
+        .checked_sub(payout_ticket.amount_paid)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+
+    if final_amount > 0 {
+        payout_ticket.amount_paid = payout_ticket
+            .amount_paid
+            .checked_add(final_amount)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+
+        let bump_seed = assert_derivation(
+            program_id,
+            listing_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                listing.item_token_account.as_ref(),
+                LISTING.as_bytes(),
+            ],
+        )?;
+
+        let authority_seeds = &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            listing.item_token_account.as_ref(),
+            LISTING.as_bytes(),
+            &[bump_seed],
+        ];
+
+        spl_token_transfer(
+            accept_payment_info.clone(),
+            destination_info.clone(),
+            final_amount,
+            listing_info.clone(),
+            authority_seeds,
+            token_program_info.clone(),
+        )?;
+    }
+
+    payout_ticket.serialize(&mut *payout_ticket_info.data.borrow_mut())?;
+
+    Ok(())
+}
@@ -5,8 +5,8 @@ use {
         error::MetaplexError,
         instruction::EmptyPaymentAccountArgs,
         state::{
-            get_auction_manager, AuctionManager, Key, PayoutTicket, Store, MAX_PAYOUT_TICKET_SIZE,
-            PREFIX, TOTALS,
+            get_auction_manager, AuctionManager, Key, PayoutTicket, PlatformFee, Store,
+            MAX_PAYOUT_TICKET_SIZE, PREFIX, TOTALS,
         },
         utils::{
             assert_derivation, assert_initialized, assert_is_ata, assert_owned_by,
@@ -30,15 +30,31 @@ use {
     spl_token_vault::state::SafetyDepositBox,
 };
 
-fn assert_destination_ownership_validity(
-    auction_manager: &Box<dyn AuctionManager>,
+/// Shared by auction managers and fixed price listings, where the auctioneer is the listing's seller
+/// and platform_fee is the one snapshotted when they were created
+#[allow(clippy::too_many_arguments)]
+pub fn assert_destination_ownership_validity(
+    auctioneer: &Pubkey,
     metadata: &Metadata,
     destination_info: &AccountInfo,
     destination: &Account,
     store: &Store,
+    platform_fee: Option<PlatformFee>,
     creator_index: Option<u8>,
+    platform_fee_payout: bool,
 ) -> ProgramResult {
-    if let Some(creators) = &metadata.data.creators {
+    if platform_fee_payout {
+        let platform_fee = platform_fee.ok_or(MetaplexError::NoPlatformFee)?;
+        if destination.owner != platform_fee.destination {
+            return Err(MetaplexError::IncorrectOwner.into());
+        }
+        assert_is_ata(
+            destination_info,
+            &platform_fee.destination,
+            &store.token_program,
+            &destination.mint,
+        )?;
+    } else if let Some(creators) = &metadata.data.creators {
         if let Some(index) = creator_index {
             if (index as usize) < creators.len() {
                 let creator = &creators[index as usize];
@@ -57,10 +73,10 @@ fn assert_destination_ownership_validity(
             } else {
                 return Err(MetaplexError::InvalidCreatorIndex.into());
             }
-        } else if destination.owner != auction_manager.authority() {
+        } else if destination.owner != *auctioneer {
             return Err(MetaplexError::IncorrectOwner.into());
         }
-    } else if destination.owner != auction_manager.authority() {
+    } else if destination.owner != *auctioneer {
         return Err(MetaplexError::IncorrectOwner.into());
     }
 
@@ -75,44 +91,46 @@ fn assert_destination_ownership_validity(
     Ok(())
 }
 
-fn calculate_owed_amount(
-    auction_token_tracker_info: Option<&AccountInfo>,
-    safety_deposit_config_info: Option<&AccountInfo>,
-    auction_manager: &Box<dyn AuctionManager>,
-    auction: &AuctionData,
-    metadata: &Metadata,
-    winning_config_index: &Option<u8>,
-    winning_config_item_index: &Option<u8>,
-    creator_index: &Option<u8>,
-) -> Result<u64, ProgramError> {
-    let primary_sale_happened = auction_manager.get_primary_sale_happened(
-        metadata,
-        *winning_config_index,
-        *winning_config_item_index,
-    )?;
-
-    let mut amount_available_to_split: u128 = match winning_config_index {
-        Some(index) => auction.bid_state.amount(*index as usize) as u128,
-        None => {
-            // this means the amount owed is the amount collected from participation nft bids.
-            auction_manager.get_collected_to_accept_payment(safety_deposit_config_info)?
-        }
+/// The store's platform fee comes off the top, creators and auctioneer split the rest as usual.
+/// Returns the fee itself when paying out the platform, otherwise what is left after it.
+pub fn apply_platform_fee(
+    amount: u128,
+    platform_fee_basis_points: Option<u16>,
+    platform_fee_payout: bool,
+) -> Result<u128, ProgramError> {
+    let basis_points = match platform_fee_basis_points {
+        Some(val) => val,
+        None => return Ok(amount),
     };
 
-    if winning_config_index.is_some() {
-        msg!("Winning config index {:?}", winning_config_index.unwrap());
-    }
-    if winning_config_item_index.is_some() {
-        msg!(
-            "Winning config item index {:?}",
-            winning_config_item_index.unwrap()
-        );
+    let platform_fee_amount = amount
+        .checked_mul(basis_points as u128)
+        .ok_or(MetaplexError::NumericalOverflowError)?
+        .checked_div(10000)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    msg!("Platform fee {:?}", platform_fee_amount);
+
+    if platform_fee_payout {
+        Ok(platform_fee_amount)
+    } else {
+        Ok(amount
+            .checked_sub(platform_fee_amount)
+            .ok_or(MetaplexError::NumericalOverflowError)?)
     }
-    if creator_index.is_some() {
-        msg!("Creator index {:?}", creator_index.unwrap());
+}
+
+/// Returns the numerator and artist further multiplier, both in basis points, that whittle the
+/// proceeds down to one payee's cut.
+pub fn get_payout_multipliers(
+    metadata: &Metadata,
+    primary_sale_happened: bool,
+    creator_index: &Option<u8>,
+    platform_fee_payout: bool,
+) -> Result<(u128, u128), ProgramError> {
+    if platform_fee_payout {
+        return Ok((10000, 10000));
     }
 
-    msg!("Amount available to split {:?}", amount_available_to_split);
     let numerator: u128 = match creator_index {
         Some(_) => {
             if primary_sale_happened {
@@ -150,6 +168,112 @@ fn calculate_owed_amount(
 
     msg!("Artist further multiplier {:?}", artist_further_multiplier);
 
+    Ok((numerator, artist_further_multiplier))
+}
+
+/// Same as get_payout_multipliers, but only verified creators get paid, by their share of the
+/// verified total, and whoever sold gets everything else, including the royalties when no creator
+/// verified. Used wherever anyone, not just a whitelisted creator, can sell the item.
+pub fn get_verified_creator_payout_multipliers(
+    metadata: &Metadata,
+    primary_sale_happened: bool,
+    creator_index: &Option<u8>,
+    platform_fee_payout: bool,
+) -> Result<(u128, u128), ProgramError> {
+    if platform_fee_payout {
+        return Ok((10000, 10000));
+    }
+
+    let verified_shares: u128 = match &metadata.data.creators {
+        Some(creators) => creators
+            .iter()
+            .filter(|creator| creator.verified)
+            .map(|creator| creator.share as u128)
+            .sum(),
+        None => 0,
+    };
+
+    match creator_index {
+        Some(index) => {
+            let creator = match &metadata.data.creators {
+                Some(creators) => &creators[*index as usize],
+                None => return Err(MetaplexError::CreatorIndexExpected.into()),
+            };
+
+            if !creator.verified {
+                return Err(MetaplexError::CreatorHasNotVerifiedMetadata.into());
+            }
+
+            let numerator = if primary_sale_happened {
+                metadata.data.seller_fee_basis_points as u128
+            } else {
+                10000
+            };
+
+            Ok((numerator, (creator.share as u128) * 10000 / verified_shares))
+        }
+        None if verified_shares == 0 => Ok((10000, 10000)),
+        None if primary_sale_happened => Ok((
+            (10000 - metadata.data.seller_fee_basis_points) as u128,
+            10000,
+        )),
+        None => Ok((0, 10000)),
+    }
+}
+
+fn calculate_owed_amount(
+    auction_token_tracker_info: Option<&AccountInfo>,
+    safety_deposit_config_info: Option<&AccountInfo>,
+    auction_manager: &Box<dyn AuctionManager>,
+    auction: &AuctionData,
+    metadata: &Metadata,
+    winning_config_index: &Option<u8>,
+    winning_config_item_index: &Option<u8>,
+    creator_index: &Option<u8>,
+    platform_fee_basis_points: Option<u16>,
+    platform_fee_payout: bool,
+) -> Result<u64, ProgramError> {
+    let primary_sale_happened = auction_manager.get_primary_sale_happened(
+        metadata,
+        *winning_config_index,
+        *winning_config_item_index,
+    )?;
+
+    let mut amount_available_to_split: u128 = match winning_config_index {
+        Some(index) => auction.bid_state.amount(*index as usize) as u128,
+        None => {
+            // this means the amount owed is the amount collected from participation nft bids.
+            auction_manager.get_collected_to_accept_payment(safety_deposit_config_info)?
+        }
+    };
+
+    if winning_config_index.is_some() {
+        msg!("Winning config index {:?}", winning_config_index.unwrap());
+    }
+    if winning_config_item_index.is_some() {
+        msg!(
+            "Winning config item index {:?}",
+            winning_config_item_index.unwrap()
+        );
+    }
+    if creator_index.is_some() {
+        msg!("Creator index {:?}", creator_index.unwrap());
+    }
+
+    amount_available_to_split = apply_platform_fee(
+        amount_available_to_split,
+        platform_fee_basis_points,
+        platform_fee_payout,
+    )?;
+
+    msg!("Amount available to split {:?}", amount_available_to_split);
+    let (numerator, artist_further_multiplier) = get_payout_multipliers(
+        metadata,
+        primary_sale_happened,
+        creator_index,
+        platform_fee_payout,
+    )?;
+
     // Numerator represents the whittling to cut the artist or auctioneer's piece  off of the
     // total amount available. So if it's the auctioneer and they get 90% in a secondary sale, this would
     // be (9000/10000) * bid amount, numerator is 9000. Or if it's the artists collective cut, this would
@@ -216,6 +340,7 @@ pub fn process_empty_payment_account(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     args: EmptyPaymentAccountArgs,
+    platform_fee_payout: bool,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let accept_payment_info = next_account_info(account_info_iter)?;
@@ -310,12 +435,14 @@ pub fn process_empty_payment_account(
     // assert the destination account matches the ownership expected to creator or auction manager authority
     // given in the argument's creator index
     assert_destination_ownership_validity(
-        &auction_manager,
+        &auction_manager.authority(),
         &metadata,
         destination_info,
         &destination,
         &store,
+        auction_manager.platform_fee(),
         args.creator_index,
+        platform_fee_payout,
     )?;
 
     // further assert that the vault and safety deposit are correctly matched to the auction manager
@@ -369,6 +496,7 @@ pub fn process_empty_payment_account(
     };
 
     let creator_index_key: String = match args.creator_index {
+        _ if platform_fee_payout => "platform".to_owned(),
         Some(val) => val.to_string(),
         None => "auctioneer".to_owned(),
     };
@@ -423,6 +551,8 @@ pub fn process_empty_payment_account(
         &args.winning_config_index,
         &args.winning_config_item_index,
         &args.creator_index,
+        auction_manager.platform_fee().map(|fee| fee.basis_points),
+        platform_fee_payout,
     )?;
 
     let final_amount = amount
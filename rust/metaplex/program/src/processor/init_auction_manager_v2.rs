@@ -41,6 +41,10 @@ pub fn assert_common_checks(
     assert_owned_by(store_info, program_id)?;
     assert_owned_by(accept_payment_info, &store.token_program)?;
 
+    if store.successor.is_some() {
+        return Err(MetaplexError::StoreMigrated.into());
+    }
+
     if auction.authority != *auction_manager_info.key && auction.authority != *authority_info.key {
         return Err(MetaplexError::AuctionAuthorityMismatch.into());
     }
@@ -138,6 +142,7 @@ pub fn process_init_auction_manager_v2(
         authority_seeds,
     )?;
 
+    let store = Store::from_account_info(store_info)?;
     let mut auction_manager = AuctionManagerV2::from_account_info(auction_manager_info)?;
 
     auction_manager.key = Key::AuctionManagerV2;
@@ -149,6 +154,7 @@ pub fn process_init_auction_manager_v2(
     auction_manager.accept_payment = *accept_payment_info.key;
     auction_manager.state.safety_config_items_validated = 0;
     auction_manager.state.bids_pushed_to_accept_payment = 0;
+    auction_manager.platform_fee = store.platform_fee;
 
     auction_manager.serialize(&mut *auction_manager_info.data.borrow_mut())?;
 
@@ -0,0 +1,142 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{Key, Offer, MAX_OFFER_SIZE, OFFER, PREFIX},
+        utils::{
+            assert_derivation, assert_initialized, assert_owned_by, assert_signer,
+            create_or_allocate_account_raw, spl_token_transfer,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::{Clock, UnixTimestamp},
+        entrypoint::ProgramResult,
+        program::invoke,
+        program_option::COption,
+        pubkey::Pubkey,
+        system_instruction,
+        sysvar::Sysvar,
+    },
+    spl_token::state::Account,
+    spl_token_metadata::state::Metadata,
+};
+
+pub fn process_make_offer<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    price: u64,
+    expires_at: UnixTimestamp,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let offer_info = next_account_info(account_info_iter)?;
+    let buyer_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    // Only present when offering SPL tokens instead of SOL
+    let buyer_token_info = next_account_info(account_info_iter).ok();
+    let escrow_info = next_account_info(account_info_iter).ok();
+    let token_program_info = next_account_info(account_info_iter).ok();
+
+    let clock = Clock::from_account_info(clock_info)?;
+
+    assert_signer(buyer_info)?;
+    assert_signer(payer_info)?;
+    assert_owned_by(metadata_info, &spl_token_metadata::id())?;
+    Metadata::from_account_info(metadata_info)?;
+
+    if !offer_info.data_is_empty() {
+        return Err(MetaplexError::AlreadyInitialized.into());
+    }
+
+    if expires_at <= clock.unix_timestamp {
+        return Err(MetaplexError::OfferExpired.into());
+    }
+
+    let bump_seed = assert_derivation(
+        program_id,
+        offer_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata_info.key.as_ref(),
+            OFFER.as_bytes(),
+            buyer_info.key.as_ref(),
+        ],
+    )?;
+
+    create_or_allocate_account_raw(
+        *program_id,
+        offer_info,
+        rent_info,
+        system_info,
+        payer_info,
+        MAX_OFFER_SIZE,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata_info.key.as_ref(),
+            OFFER.as_bytes(),
+            buyer_info.key.as_ref(),
+            &[bump_seed],
+        ],
+    )?;
+
+    let escrow = match (buyer_token_info, escrow_info, token_program_info) {
+        (Some(buyer_token_info), Some(escrow_info), Some(token_program_info)) => {
+            if *token_program_info.key != spl_token::id() {
+                return Err(MetaplexError::TokenProgramMismatch.into());
+            }
+            assert_owned_by(escrow_info, &spl_token::id())?;
+
+            let escrow: Account = assert_initialized(escrow_info)?;
+            if escrow.owner != *offer_info.key {
+                return Err(MetaplexError::IncorrectOwner.into());
+            }
+
+            if escrow.delegate != COption::None {
+                return Err(MetaplexError::DelegateShouldBeNone.into());
+            }
+
+            if escrow.close_authority != COption::None {
+                return Err(MetaplexError::CloseAuthorityShouldBeNone.into());
+            }
+
+            spl_token_transfer(
+                buyer_token_info.clone(),
+                escrow_info.clone(),
+                price,
+                buyer_info.clone(),
+                &[],
+                token_program_info.clone(),
+            )?;
+
+            Some(*escrow_info.key)
+        }
+        (None, None, None) => {
+            // The offer itself holds the SOL on top of its rent.
+            invoke(
+                &system_instruction::transfer(buyer_info.key, offer_info.key, price),
+                &[buyer_info.clone(), offer_info.clone(), system_info.clone()],
+            )?;
+
+            None
+        }
+        _ => return Err(MetaplexError::InvalidOperation.into()),
+    };
+
+    let mut offer = Offer::from_account_info(offer_info)?;
+    offer.key = Key::OfferV1;
+    offer.metadata = *metadata_info.key;
+    offer.buyer = *buyer_info.key;
+    offer.escrow = escrow;
+    offer.price = price;
+    offer.expires_at = expires_at;
+
+    offer.serialize(&mut *offer_info.data.borrow_mut())?;
+
+    Ok(())
+}
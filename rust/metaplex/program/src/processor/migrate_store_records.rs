@@ -0,0 +1,201 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            Key, Store, StoreRoleRecord, WhitelistedCreator, MAX_STORE_ROLE_SIZE,
+            MAX_WHITELISTED_CREATOR_SIZE, PREFIX, ROLE,
+        },
+        utils::{
+            assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
+            is_store_owner,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+#[allow(clippy::too_many_arguments)]
+fn migrate_whitelisted_creator<'a>(
+    program_id: &Pubkey,
+    store_info: &AccountInfo<'a>,
+    new_store_info: &AccountInfo<'a>,
+    record_info: &AccountInfo<'a>,
+    new_record_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    system_info: &AccountInfo<'a>,
+    rent_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let whitelisted_creator = WhitelistedCreator::from_account_info(record_info)?;
+    assert_derivation(
+        program_id,
+        record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store_info.key.as_ref(),
+            whitelisted_creator.address.as_ref(),
+        ],
+    )?;
+    let new_bump = assert_derivation(
+        program_id,
+        new_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            new_store_info.key.as_ref(),
+            whitelisted_creator.address.as_ref(),
+        ],
+    )?;
+
+    create_or_allocate_account_raw(
+        *program_id,
+        new_record_info,
+        rent_info,
+        system_info,
+        payer_info,
+        MAX_WHITELISTED_CREATOR_SIZE,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            new_store_info.key.as_ref(),
+            whitelisted_creator.address.as_ref(),
+            &[new_bump],
+        ],
+    )?;
+
+    whitelisted_creator.serialize(&mut *new_record_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn migrate_store_role<'a>(
+    program_id: &Pubkey,
+    store_info: &AccountInfo<'a>,
+    new_store_info: &AccountInfo<'a>,
+    record_info: &AccountInfo<'a>,
+    new_record_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    system_info: &AccountInfo<'a>,
+    rent_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let mut record = StoreRoleRecord::from_account_info(record_info)?;
+    assert_derivation(
+        program_id,
+        record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store_info.key.as_ref(),
+            ROLE.as_bytes(),
+            record.role.seed().as_bytes(),
+            record.holder.as_ref(),
+        ],
+    )?;
+    let new_bump = assert_derivation(
+        program_id,
+        new_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            new_store_info.key.as_ref(),
+            ROLE.as_bytes(),
+            record.role.seed().as_bytes(),
+            record.holder.as_ref(),
+        ],
+    )?;
+
+    create_or_allocate_account_raw(
+        *program_id,
+        new_record_info,
+        rent_info,
+        system_info,
+        payer_info,
+        MAX_STORE_ROLE_SIZE,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            new_store_info.key.as_ref(),
+            ROLE.as_bytes(),
+            record.role.seed().as_bytes(),
+            record.holder.as_ref(),
+            &[new_bump],
+        ],
+    )?;
+
+    record.store = *new_store_info.key;
+    record.serialize(&mut *new_record_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+pub fn process_migrate_store_records<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let store_info = next_account_info(account_info_iter)?;
+    let new_store_info = next_account_info(account_info_iter)?;
+    let new_owner_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(new_owner_info)?;
+    assert_signer(payer_info)?;
+    assert_owned_by(store_info, program_id)?;
+    assert_owned_by(new_store_info, program_id)?;
+
+    if !is_store_owner(program_id, new_store_info, new_owner_info.key) {
+        return Err(MetaplexError::MustBeStoreOwner.into());
+    }
+
+    let store = Store::from_account_info(store_info)?;
+    if store.successor != Some(*new_store_info.key) {
+        return Err(MetaplexError::StoreSuccessorMismatch.into());
+    }
+
+    while let Ok(record_info) = next_account_info(account_info_iter) {
+        let new_record_info = next_account_info(account_info_iter)?;
+
+        assert_owned_by(record_info, program_id)?;
+        // Already carried over, or set again on the new store, which wins
+        if !new_record_info.data_is_empty() {
+            continue;
+        }
+
+        let key = record_info.data.borrow()[0];
+        if key == Key::WhitelistedCreatorV1 as u8 {
+            migrate_whitelisted_creator(
+                program_id,
+                store_info,
+                new_store_info,
+                record_info,
+                new_record_info,
+                payer_info,
+                system_info,
+                rent_info,
+            )?;
+        } else if key == Key::StoreRoleV1 as u8 {
+            migrate_store_role(
+                program_id,
+                store_info,
+                new_store_info,
+                record_info,
+                new_record_info,
+                payer_info,
+                system_info,
+                rent_info,
+            )?;
+        } else {
+            return Err(MetaplexError::DataTypeMismatch.into());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,347 @@
+use {
+    crate::{
+        error::MetaplexError,
+        instruction::RedeemBidsArgs,
+        processor::redeem_printing_v2_bid::{create_or_update_prize_tracking, mint_edition},
+        state::{
+            BidRedemptionTicket, CommonWinningIndexChecks, CommonWinningIndexReturn, Key,
+            PrintingV2CalculationCheckReturn, PrintingV2CalculationChecks, WinningConfigType,
+            PREFIX,
+        },
+        utils::{
+            assert_derivation, assert_is_ata, assert_owned_by, common_redeem_checks,
+            common_redeem_item_checks, create_or_allocate_account_raw,
+            get_amount_from_token_account, transfer_metadata_ownership,
+            transfer_safety_deposit_box_items, CommonRedeemCheckArgs, CommonRedeemReturn,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+    },
+    spl_auction::processor::AuctionData,
+    spl_token_metadata::state::Metadata,
+    spl_token_vault::state::{SafetyDepositBox, Vault},
+};
+
+/// Every prize starts with its safety deposit token store, destination, safety deposit box
+/// and safety deposit config, followed by the extra accounts its winning config type needs.
+fn next_prize_accounts<'a, 'b>(
+    account_info_iter: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+) -> Result<[&'a AccountInfo<'b>; 4], ProgramError> {
+    Ok([
+        next_account_info(account_info_iter)?,
+        next_account_info(account_info_iter)?,
+        next_account_info(account_info_iter)?,
+        next_account_info(account_info_iter)?,
+    ])
+}
+
+pub fn process_redeem_bids<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RedeemBidsArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let auction_manager_info = next_account_info(account_info_iter)?;
+    let bid_redemption_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let fraction_mint_info = next_account_info(account_info_iter)?;
+    let auction_info = next_account_info(account_info_iter)?;
+    let bidder_metadata_info = next_account_info(account_info_iter)?;
+    let bidder_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let token_vault_program_info = next_account_info(account_info_iter)?;
+    let token_metadata_program_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let transfer_authority_info = next_account_info(account_info_iter)?;
+
+    let win_index = args.win_index;
+    let mut edition_offsets = args.edition_offsets.into_iter();
+
+    let first_prize = next_prize_accounts(account_info_iter)?;
+
+    // The shared checks are run once, against the first prize. Redeemed items are checked
+    // per prize below, as printing v2 prizes can be redeemed more than once.
+    let CommonRedeemReturn {
+        auction_manager,
+        redemption_bump_seed,
+        cancelled,
+        rent,
+        win_index: _w,
+        token_metadata_program,
+    } = common_redeem_checks(CommonRedeemCheckArgs {
+        program_id,
+        auction_manager_info,
+        safety_deposit_token_store_info: first_prize[0],
+        destination_info: first_prize[1],
+        bid_redemption_info,
+        safety_deposit_info: first_prize[2],
+        vault_info,
+        auction_info,
+        bidder_metadata_info,
+        bidder_info,
+        token_program_info,
+        token_vault_program_info,
+        token_metadata_program_info,
+        rent_info,
+        store_info,
+        safety_deposit_config_info: Some(first_prize[3]),
+        is_participation: false,
+        user_provided_win_index: Some(Some(win_index as usize)),
+        overwrite_win_index: None,
+        assert_bidder_signer: true,
+        ignore_bid_redeemed_item_check: true,
+    })?;
+
+    // Only V2 redemption tickets keep track of which prizes were redeemed
+    if auction_manager.key() != Key::AuctionManagerV2 || cancelled {
+        return Err(MetaplexError::InvalidOperation.into());
+    }
+
+    let auction_key = auction_manager.auction();
+    let auction_bump_seed = assert_derivation(
+        program_id,
+        auction_manager_info,
+        &[PREFIX.as_bytes(), auction_key.as_ref()],
+    )?;
+    let auction_auth_seeds = &[
+        PREFIX.as_bytes(),
+        auction_key.as_ref(),
+        &[auction_bump_seed],
+    ];
+
+    if bid_redemption_info.data_is_empty() {
+        let token_type_count = Vault::get_token_type_count(vault_info)
+            .checked_div(8)
+            .ok_or(MetaplexError::NumericalOverflowError)?;
+
+        create_or_allocate_account_raw(
+            *program_id,
+            bid_redemption_info,
+            rent_info,
+            system_info,
+            payer_info,
+            1 + 9 + 32 + 1 + token_type_count as usize,
+            &[
+                PREFIX.as_bytes(),
+                auction_key.as_ref(),
+                bidder_metadata_info.key.as_ref(),
+                &[redemption_bump_seed],
+            ],
+        )?;
+    }
+
+    let mut prize_accounts = Some(first_prize);
+    let mut redeemed: usize = 0;
+    while let Some(prize) = prize_accounts {
+        let [safety_deposit_token_store_info, destination_info, safety_deposit_info, safety_deposit_config_info] =
+            prize;
+        if redeemed > 0 {
+            common_redeem_item_checks(
+                program_id,
+                auction_manager_info,
+                auction_manager.as_ref(),
+                safety_deposit_token_store_info,
+                destination_info,
+                safety_deposit_info,
+                vault_info,
+                Some(safety_deposit_config_info),
+                token_program_info,
+                token_vault_program_info.key,
+                &rent,
+            )?;
+        }
+
+        let CommonWinningIndexReturn {
+            amount,
+            winning_config_type,
+            winning_config_item_index: _wi,
+        } = auction_manager.common_winning_index_checks(CommonWinningIndexChecks {
+            safety_deposit_info,
+            winning_index: win_index as usize,
+            auction_manager_v1_ignore_claim: false,
+            safety_deposit_config_info: Some(safety_deposit_config_info),
+        })?;
+
+        if amount == 0 {
+            return Err(MetaplexError::NoTokensForThisWinner.into());
+        }
+
+        if winning_config_type != WinningConfigType::PrintingV2 {
+            // Also catches the same prize being passed twice, as the ticket was saved for the one before
+            BidRedemptionTicket::check_ticket(
+                bid_redemption_info,
+                false,
+                Some(safety_deposit_config_info),
+            )?;
+        }
+
+        match winning_config_type {
+            WinningConfigType::TokenOnlyTransfer => {
+                transfer_safety_deposit_box_items(
+                    token_vault_program_info.clone(),
+                    destination_info.clone(),
+                    safety_deposit_info.clone(),
+                    safety_deposit_token_store_info.clone(),
+                    vault_info.clone(),
+                    fraction_mint_info.clone(),
+                    auction_manager_info.clone(),
+                    transfer_authority_info.clone(),
+                    rent_info.clone(),
+                    amount,
+                    auction_auth_seeds,
+                )?;
+            }
+            WinningConfigType::FullRightsTransfer => {
+                let metadata_info = next_account_info(account_info_iter)?;
+                let new_metadata_authority_info = next_account_info(account_info_iter)?;
+
+                assert_owned_by(metadata_info, &token_metadata_program)?;
+                let metadata = Metadata::from_account_info(metadata_info)?;
+                let safety_deposit = SafetyDepositBox::from_account_info(safety_deposit_info)?;
+                if metadata.mint != safety_deposit.token_mint {
+                    return Err(MetaplexError::SafetyDepositBoxMetadataMismatch.into());
+                }
+
+                // The metadata transfer may not have happened for a broken auction manager
+                // that was forced to disbursing, same as in RedeemFullRightsTransferBid.
+                if metadata.update_authority == *auction_manager_info.key {
+                    msg!("Transferring metadata authority!");
+                    transfer_metadata_ownership(
+                        token_metadata_program_info.clone(),
+                        metadata_info.clone(),
+                        auction_manager_info.clone(),
+                        new_metadata_authority_info.clone(),
+                        auction_auth_seeds,
+                    )?;
+                }
+
+                transfer_safety_deposit_box_items(
+                    token_vault_program_info.clone(),
+                    destination_info.clone(),
+                    safety_deposit_info.clone(),
+                    safety_deposit_token_store_info.clone(),
+                    vault_info.clone(),
+                    fraction_mint_info.clone(),
+                    auction_manager_info.clone(),
+                    transfer_authority_info.clone(),
+                    rent_info.clone(),
+                    1,
+                    auction_auth_seeds,
+                )?;
+            }
+            WinningConfigType::PrintingV2 => {
+                let prize_tracking_ticket_info = next_account_info(account_info_iter)?;
+                let new_metadata_account_info = next_account_info(account_info_iter)?;
+                let new_edition_account_info = next_account_info(account_info_iter)?;
+                let master_edition_account_info = next_account_info(account_info_iter)?;
+                let mint_info = next_account_info(account_info_iter)?;
+                let edition_marker_info = next_account_info(account_info_iter)?;
+                let mint_authority_info = next_account_info(account_info_iter)?;
+                let metadata_account_info = next_account_info(account_info_iter)?;
+
+                let edition_offset = edition_offsets
+                    .next()
+                    .ok_or(MetaplexError::MissingEditionOffset)?;
+
+                assert_is_ata(
+                    destination_info,
+                    bidder_info.key,
+                    token_program_info.key,
+                    mint_info.key,
+                )?;
+                if get_amount_from_token_account(destination_info)? != 1 {
+                    return Err(MetaplexError::ProvidedAccountDoesNotContainOneToken.into());
+                }
+                assert_owned_by(metadata_account_info, &token_metadata_program)?;
+
+                let PrintingV2CalculationCheckReturn {
+                    expected_redemptions,
+                    winning_config_type: _wct,
+                    winning_config_item_index: _wi,
+                } = auction_manager.printing_v2_calculation_checks(
+                    PrintingV2CalculationChecks {
+                        safety_deposit_info,
+                        winning_index: win_index as usize,
+                        auction_manager_v1_ignore_claim: true,
+                        winners: AuctionData::get_num_winners(auction_info),
+                        short_circuit_total: !prize_tracking_ticket_info.data_is_empty(),
+                        safety_deposit_config_info: Some(safety_deposit_config_info),
+                        edition_offset,
+                    },
+                )?;
+
+                let supply_snapshot = create_or_update_prize_tracking(
+                    program_id,
+                    auction_manager_info,
+                    prize_tracking_ticket_info,
+                    metadata_account_info,
+                    payer_info,
+                    rent_info,
+                    system_info,
+                    master_edition_account_info,
+                    expected_redemptions,
+                )?;
+
+                let actual_edition = edition_offset
+                    .checked_add(supply_snapshot)
+                    .ok_or(MetaplexError::NumericalOverflowError)?;
+
+                mint_edition(
+                    token_metadata_program_info,
+                    token_vault_program_info,
+                    new_metadata_account_info,
+                    new_edition_account_info,
+                    master_edition_account_info,
+                    edition_marker_info,
+                    mint_info,
+                    mint_authority_info,
+                    payer_info,
+                    auction_manager_info,
+                    safety_deposit_token_store_info,
+                    safety_deposit_info,
+                    vault_info,
+                    bidder_info,
+                    metadata_account_info,
+                    token_program_info,
+                    system_info,
+                    rent_info,
+                    actual_edition,
+                    auction_auth_seeds,
+                )?;
+            }
+            _ => return Err(MetaplexError::WrongBidEndpointForPrize.into()),
+        }
+
+        BidRedemptionTicket::save(
+            bid_redemption_info,
+            false,
+            Some(safety_deposit_config_info),
+            Some(win_index as usize),
+            *auction_manager_info.key,
+            auction_manager.key(),
+        )?;
+
+        redeemed += 1;
+        prize_accounts = if account_info_iter.as_slice().is_empty() {
+            None
+        } else {
+            Some(next_prize_accounts(account_info_iter)?)
+        };
+    }
+
+    msg!("Redeemed {:?} prizes", redeemed);
+
+    // Saves the disbursing status set by the common checks
+    auction_manager.fast_save(auction_manager_info, win_index as usize, 0);
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{get_auction_manager, AuctionCache, Store, StoreIndexer, INDEX, PREFIX},
+        utils::{assert_derivation, assert_owned_by, assert_signer, close_program_account},
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+    spl_auction::processor::{AuctionData, AuctionState, BidState},
+};
+
+pub fn process_remove_auction_cache<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    page: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let store_index_info = next_account_info(account_info_iter)?;
+    let auction_cache_info = next_account_info(account_info_iter)?;
+    let auction_manager_info = next_account_info(account_info_iter)?;
+    let auction_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+
+    assert_signer(authority_info)?;
+    assert_owned_by(store_index_info, program_id)?;
+    assert_owned_by(auction_cache_info, program_id)?;
+    assert_owned_by(auction_manager_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let store = Store::from_account_info(store_info)?;
+    assert_owned_by(auction_info, &store.auction_program)?;
+
+    let auction_cache = AuctionCache::from_account_info(auction_cache_info)?;
+    let mut indexer = StoreIndexer::from_account_info(store_index_info)?;
+    let auction_manager = get_auction_manager(auction_manager_info)?;
+
+    assert_derivation(
+        program_id,
+        store_index_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store_info.key.as_ref(),
+            INDEX.as_bytes(),
+            page.to_string().as_bytes(),
+        ],
+    )?;
+
+    if auction_cache.store != *store_info.key
+        || auction_cache.auction_manager != *auction_manager_info.key
+        || auction_cache.auction != *auction_info.key
+    {
+        return Err(MetaplexError::InvalidOperation.into());
+    }
+
+    if auction_manager.authority() != *authority_info.key {
+        return Err(MetaplexError::AuctionManagerAuthorityMismatch.into());
+    }
+
+    if auction_cache.payer != *payer_info.key {
+        return Err(MetaplexError::AuctionCachePayerMismatch.into());
+    }
+
+    // A decommissioned auction manager ends its auction before anyone could bid
+    let auction = AuctionData::from_account_info(auction_info)?;
+    let has_bids = match &auction.bid_state {
+        BidState::EnglishAuction { bids, .. }
+        | BidState::OpenEdition { bids, .. }
+        | BidState::Raffle { bids, .. } => !bids.is_empty(),
+    };
+    if auction.state != AuctionState::Ended || has_bids {
+        return Err(MetaplexError::AuctionCacheStillLive.into());
+    }
+
+    let position = indexer
+        .auction_caches
+        .iter()
+        .position(|key| key == auction_cache_info.key)
+        .ok_or(MetaplexError::AuctionCacheNotIndexed)?;
+    indexer.auction_caches.remove(position);
+
+    indexer.serialize(&mut *store_index_info.data.borrow_mut())?;
+
+    close_program_account(auction_cache_info, payer_info)?;
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            get_auction_manager, AuctionManagerStatus, OriginalAuthorityLookup, Store, PREFIX,
+        },
+        utils::{
+            assert_derivation, assert_initialized, assert_owned_by, common_redeem_item_checks,
+            get_amount_from_token_account, shift_authority_back_to_originating_user,
+            transfer_metadata_ownership, transfer_safety_deposit_box_items,
+        },
+    },
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        msg,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
+    },
+    spl_token::state::Account,
+    spl_token_metadata::state::Metadata,
+};
+
+pub fn process_return_cancelled_auction_item<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let auction_manager_info = next_account_info(account_info_iter)?;
+    let safety_deposit_token_store_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let safety_deposit_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let fraction_mint_info = next_account_info(account_info_iter)?;
+    let auction_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let token_vault_program_info = next_account_info(account_info_iter)?;
+    let token_metadata_program_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let original_owner_info = next_account_info(account_info_iter)?;
+    let original_authority_lookup_info = next_account_info(account_info_iter)?;
+    let transfer_authority_info = next_account_info(account_info_iter)?;
+    let safety_deposit_config_info = next_account_info(account_info_iter)?;
+    // Only for MasterEditionV1s whose printing mint authority the auction manager holds
+    let printing_mint_info = next_account_info(account_info_iter).ok();
+
+    let rent = &Rent::from_account_info(rent_info)?;
+
+    assert_owned_by(auction_manager_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+
+    let auction_manager = get_auction_manager(auction_manager_info)?;
+    let store = Store::from_account_info(store_info)?;
+
+    if auction_manager.status() != AuctionManagerStatus::Cancelled {
+        return Err(MetaplexError::InvalidStatus.into());
+    }
+
+    if auction_manager.store() != *store_info.key {
+        return Err(MetaplexError::AuctionManagerStoreMismatch.into());
+    }
+
+    if auction_manager.auction() != *auction_info.key {
+        return Err(MetaplexError::AuctionManagerAuctionMismatch.into());
+    }
+
+    if store.token_program != *token_program_info.key {
+        return Err(MetaplexError::AuctionManagerTokenProgramMismatch.into());
+    }
+
+    if store.token_vault_program != *token_vault_program_info.key {
+        return Err(MetaplexError::AuctionManagerTokenVaultProgramMismatch.into());
+    }
+
+    if store.token_metadata_program != *token_metadata_program_info.key {
+        return Err(MetaplexError::AuctionManagerTokenMetadataProgramMismatch.into());
+    }
+
+    common_redeem_item_checks(
+        program_id,
+        auction_manager_info,
+        auction_manager.as_ref(),
+        safety_deposit_token_store_info,
+        destination_info,
+        safety_deposit_info,
+        vault_info,
+        Some(safety_deposit_config_info),
+        token_program_info,
+        &store.token_vault_program,
+        rent,
+    )?;
+
+    assert_owned_by(metadata_info, &store.token_metadata_program)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    let token_store: Account = assert_initialized(safety_deposit_token_store_info)?;
+    if metadata.mint != token_store.mint {
+        return Err(MetaplexError::SafetyDepositBoxMetadataMismatch.into());
+    }
+
+    let auction_key = auction_manager.auction();
+    let bump_seed = assert_derivation(
+        program_id,
+        auction_manager_info,
+        &[PREFIX.as_bytes(), auction_key.as_ref()],
+    )?;
+    let authority_seeds = &[PREFIX.as_bytes(), auction_key.as_ref(), &[bump_seed]];
+
+    // Items whose metadata was handed to the auction manager go back to whoever held the metadata
+    // before, everything else goes back to the auctioneer who put it in the vault.
+    if metadata.update_authority == *auction_manager_info.key {
+        assert_derivation(
+            program_id,
+            original_authority_lookup_info,
+            &[
+                PREFIX.as_bytes(),
+                auction_key.as_ref(),
+                metadata_info.key.as_ref(),
+            ],
+        )?;
+        let original_authority_lookup =
+            OriginalAuthorityLookup::from_account_info(original_authority_lookup_info)?;
+        if original_authority_lookup.original_authority != *original_owner_info.key {
+            return Err(MetaplexError::OriginalAuthorityMismatch.into());
+        }
+
+        msg!("Returning metadata authority");
+        transfer_metadata_ownership(
+            token_metadata_program_info.clone(),
+            metadata_info.clone(),
+            auction_manager_info.clone(),
+            original_owner_info.clone(),
+            authority_seeds,
+        )?;
+    } else if auction_manager.authority() != *original_owner_info.key {
+        return Err(MetaplexError::OriginalAuthorityMismatch.into());
+    }
+
+    if let Some(printing_mint_info) = printing_mint_info {
+        shift_authority_back_to_originating_user(
+            program_id,
+            auction_manager.as_ref(),
+            auction_manager_info,
+            metadata_info,
+            original_owner_info,
+            original_authority_lookup_info,
+            printing_mint_info,
+            token_program_info,
+            authority_seeds,
+        )?;
+    }
+
+    let destination: Account = assert_initialized(destination_info)?;
+    if destination.mint != token_store.mint {
+        return Err(MetaplexError::DestinationMintMismatch.into());
+    }
+
+    if destination.owner != *original_owner_info.key {
+        return Err(MetaplexError::IncorrectOwner.into());
+    }
+
+    // Returning a box twice is a no-op instead of an error, so a crank can't get stuck on it.
+    let amount = get_amount_from_token_account(safety_deposit_token_store_info)?;
+    if amount > 0 {
+        transfer_safety_deposit_box_items(
+            token_vault_program_info.clone(),
+            destination_info.clone(),
+            safety_deposit_info.clone(),
+            safety_deposit_token_store_info.clone(),
+            vault_info.clone(),
+            fraction_mint_info.clone(),
+            auction_manager_info.clone(),
+            transfer_authority_info.clone(),
+            rent_info.clone(),
+            amount,
+            authority_seeds,
+        )?;
+    }
+
+    Ok(())
+}
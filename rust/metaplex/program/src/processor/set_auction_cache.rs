@@ -0,0 +1,158 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            get_auction_manager, AuctionCache, AuctionManager, AuctionManagerStatus, Key, Store,
+            CACHE, MAX_AUCTION_CACHE_SIZE, MAX_METADATA_PER_CACHE, PREFIX,
+        },
+        utils::{
+            assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        clock::{Clock, UnixTimestamp},
+        entrypoint::ProgramResult,
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvar::Sysvar,
+    },
+    spl_auction::processor::{AuctionData, AuctionState},
+    spl_token_vault::state::SafetyDepositBox,
+};
+
+/// Auction managers started before the start time was recorded only give it away through
+/// the end time of a timed auction that is still running, otherwise the crank's time is used.
+fn get_auction_start(
+    auction_manager: &dyn AuctionManager,
+    auction_info: &AccountInfo,
+    clock_info: &AccountInfo,
+) -> Result<UnixTimestamp, ProgramError> {
+    if let Some(started_at) = auction_manager.started_at() {
+        return Ok(started_at);
+    }
+
+    let auction = AuctionData::from_account_info(auction_info)?;
+    if let (AuctionState::Started, Some(ended_at), Some(end_auction_at)) =
+        (auction.state, auction.ended_at, auction.end_auction_at)
+    {
+        return Ok(ended_at
+            .checked_sub(end_auction_at)
+            .ok_or(MetaplexError::NumericalOverflowError)?);
+    }
+
+    Ok(Clock::from_account_info(clock_info)?.unix_timestamp)
+}
+
+pub fn process_set_auction_cache<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let auction_cache_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let auction_info = next_account_info(account_info_iter)?;
+    let safety_deposit_info = next_account_info(account_info_iter)?;
+    let auction_manager_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+    let clock_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_owned_by(auction_manager_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    if !auction_cache_info.data_is_empty() {
+        assert_owned_by(auction_cache_info, program_id)?;
+    }
+
+    let store = Store::from_account_info(store_info)?;
+    let auction_manager = get_auction_manager(auction_manager_info)?;
+
+    assert_owned_by(auction_info, &store.auction_program)?;
+    assert_owned_by(safety_deposit_info, &store.token_vault_program)?;
+
+    if auction_manager.store() != *store_info.key {
+        return Err(MetaplexError::AuctionManagerStoreMismatch.into());
+    }
+
+    if auction_manager.auction() != *auction_info.key {
+        return Err(MetaplexError::AuctionManagerAuctionMismatch.into());
+    }
+
+    // Only auctions that have been started get cached
+    if auction_manager.status() == AuctionManagerStatus::Initialized
+        || auction_manager.status() == AuctionManagerStatus::Validated
+    {
+        return Err(MetaplexError::InvalidStatus.into());
+    }
+
+    let safety_deposit = SafetyDepositBox::from_account_info(safety_deposit_info)?;
+    if safety_deposit.vault != auction_manager.vault() {
+        return Err(MetaplexError::SafetyDepositBoxVaultMismatch.into());
+    }
+
+    let bump_seed = assert_derivation(
+        program_id,
+        auction_cache_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            auction_info.key.as_ref(),
+            CACHE.as_bytes(),
+        ],
+    )?;
+
+    if auction_cache_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            auction_cache_info,
+            rent_info,
+            system_info,
+            payer_info,
+            MAX_AUCTION_CACHE_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                auction_info.key.as_ref(),
+                CACHE.as_bytes(),
+                &[bump_seed],
+            ],
+        )?;
+    }
+
+    let mut auction_cache = AuctionCache::from_account_info(auction_cache_info)?;
+    if auction_cache.key == Key::Uninitialized {
+        auction_cache.key = Key::AuctionCacheV1;
+        auction_cache.store = *store_info.key;
+        auction_cache.timestamp =
+            get_auction_start(auction_manager.as_ref(), auction_info, clock_info)?;
+        auction_cache.auction = *auction_info.key;
+        auction_cache.vault = auction_manager.vault();
+        auction_cache.auction_manager = *auction_manager_info.key;
+        auction_cache.payer = *payer_info.key;
+    }
+
+    let (metadata_key, _) = Pubkey::find_program_address(
+        &[
+            spl_token_metadata::state::PREFIX.as_bytes(),
+            store.token_metadata_program.as_ref(),
+            safety_deposit.token_mint.as_ref(),
+        ],
+        &store.token_metadata_program,
+    );
+
+    if !auction_cache.metadata.contains(&metadata_key) {
+        if auction_cache.metadata.len() < MAX_METADATA_PER_CACHE {
+            auction_cache.metadata.push(metadata_key);
+        } else {
+            msg!("Auction cache already holds the maximum number of metadata");
+        }
+    }
+
+    auction_cache.serialize(&mut *auction_cache_info.data.borrow_mut())?;
+
+    Ok(())
+}
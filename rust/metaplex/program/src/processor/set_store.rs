@@ -1,9 +1,10 @@
 use {
     crate::{
         error::MetaplexError,
-        state::{Key, Store, MAX_STORE_SIZE, PREFIX},
+        state::{Key, PlatformFee, Store, StoreRole, MAX_STORE_SIZE, PREFIX},
         utils::{
-            assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
+            assert_derivation, assert_owned_by, assert_signer, assert_store_authority,
+            create_or_allocate_account_raw, is_store_owner,
         },
     },
     borsh::BorshSerialize,
@@ -14,10 +15,13 @@ use {
     },
 };
 
+/// platform_fee is only Some for SetStoreV2, SetStore leaves the store's fee as it is.
+/// Store admins can update an existing store, but only the owner can create it or change its fee.
 pub fn process_set_store<'a>(
     program_id: &'a Pubkey,
     accounts: &'a [AccountInfo<'a>],
     public: bool,
+    platform_fee: Option<Option<PlatformFee>>,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
 
@@ -30,24 +34,22 @@ pub fn process_set_store<'a>(
     let auction_program_info = next_account_info(account_info_iter)?;
     let system_info = next_account_info(account_info_iter)?;
     let rent_info = next_account_info(account_info_iter)?;
+    let role_record_info = next_account_info(account_info_iter).ok();
 
     assert_signer(payer_info)?;
     assert_signer(admin_wallet_info)?;
-    if !store_info.data_is_empty() {
-        assert_owned_by(store_info, program_id)?;
-    }
-
-    let store_bump = assert_derivation(
-        program_id,
-        store_info,
-        &[
-            PREFIX.as_bytes(),
-            program_id.as_ref(),
-            admin_wallet_info.key.as_ref(),
-        ],
-    )?;
 
     if store_info.data_is_empty() {
+        let store_bump = assert_derivation(
+            program_id,
+            store_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                admin_wallet_info.key.as_ref(),
+            ],
+        )?;
+
         create_or_allocate_account_raw(
             *program_id,
             store_info,
@@ -62,9 +64,21 @@ pub fn process_set_store<'a>(
                 &[store_bump],
             ],
         )?;
+    } else {
+        assert_owned_by(store_info, program_id)?;
+        assert_store_authority(
+            program_id,
+            store_info,
+            admin_wallet_info,
+            role_record_info,
+            &[StoreRole::StoreAdmin],
+        )?;
     }
 
     let mut store = Store::from_account_info(store_info)?;
+    if store.successor.is_some() {
+        return Err(MetaplexError::StoreMigrated.into());
+    }
     store.key = Key::StoreV1;
     store.public = public;
     // Keys can only be set once, once set from all 0s, they are immutable.
@@ -85,6 +99,17 @@ pub fn process_set_store<'a>(
     if store.auction_program == solana_program::system_program::id() {
         store.auction_program = *auction_program_info.key;
     }
+    if let Some(platform_fee) = platform_fee {
+        if !is_store_owner(program_id, store_info, admin_wallet_info.key) {
+            return Err(MetaplexError::MustBeStoreOwner.into());
+        }
+        if let Some(fee) = &platform_fee {
+            if fee.basis_points > 10000 {
+                return Err(MetaplexError::InvalidBasisPoints.into());
+            }
+        }
+        store.platform_fee = platform_fee;
+    }
     store.serialize(&mut *store_info.data.borrow_mut())?;
     Ok(())
 }
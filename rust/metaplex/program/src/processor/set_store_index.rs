@@ -0,0 +1,132 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{
+            AuctionCache, Key, StoreIndexer, INDEX, MAX_INDEXED_ELEMENTS, MAX_STORE_INDEXER_SIZE,
+            PREFIX,
+        },
+        utils::{
+            assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_store_index<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    page: u64,
+    offset: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let store_index_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let auction_cache_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_owned_by(auction_cache_info, program_id)?;
+    assert_owned_by(store_info, program_id)?;
+    if !store_index_info.data_is_empty() {
+        assert_owned_by(store_index_info, program_id)?;
+    }
+
+    let mut auction_cache = AuctionCache::from_account_info(auction_cache_info)?;
+    if auction_cache.store != *store_info.key {
+        return Err(MetaplexError::AuctionManagerStoreMismatch.into());
+    }
+
+    // The cache remembers its page, so it can't be indexed again on this or any other page
+    if auction_cache.page.is_some() {
+        return Err(MetaplexError::AuctionCacheAlreadyIndexed.into());
+    }
+
+    let page_str = page.to_string();
+    let bump_seed = assert_derivation(
+        program_id,
+        store_index_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store_info.key.as_ref(),
+            INDEX.as_bytes(),
+            page_str.as_bytes(),
+        ],
+    )?;
+
+    if store_index_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            store_index_info,
+            rent_info,
+            system_info,
+            payer_info,
+            MAX_STORE_INDEXER_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                store_info.key.as_ref(),
+                INDEX.as_bytes(),
+                page_str.as_bytes(),
+                &[bump_seed],
+            ],
+        )?;
+    }
+
+    let mut indexer = StoreIndexer::from_account_info(store_index_info)?;
+    if indexer.key == Key::Uninitialized {
+        indexer.key = Key::StoreIndexerV1;
+        indexer.store = *store_info.key;
+        indexer.page = page;
+    }
+
+    if indexer.auction_caches.len() >= MAX_INDEXED_ELEMENTS {
+        return Err(MetaplexError::StoreIndexerPageFull.into());
+    }
+
+    let offset = offset as usize;
+    if offset > indexer.auction_caches.len() {
+        return Err(MetaplexError::AuctionCacheOutOfOrder.into());
+    }
+
+    // The neighbours on either side of the offset prove the page stays sorted
+    if offset > 0 {
+        let previous_cache_info = next_account_info(account_info_iter)?;
+        if *previous_cache_info.key != indexer.auction_caches[offset - 1] {
+            return Err(MetaplexError::AuctionCacheOutOfOrder.into());
+        }
+        let previous_cache = AuctionCache::from_account_info(previous_cache_info)?;
+        if previous_cache.timestamp > auction_cache.timestamp {
+            return Err(MetaplexError::AuctionCacheOutOfOrder.into());
+        }
+    }
+
+    if offset < indexer.auction_caches.len() {
+        let next_cache_info = next_account_info(account_info_iter)?;
+        if *next_cache_info.key != indexer.auction_caches[offset] {
+            return Err(MetaplexError::AuctionCacheOutOfOrder.into());
+        }
+        let next_cache = AuctionCache::from_account_info(next_cache_info)?;
+        if next_cache.timestamp < auction_cache.timestamp {
+            return Err(MetaplexError::AuctionCacheOutOfOrder.into());
+        }
+    }
+
+    indexer
+        .auction_caches
+        .insert(offset, *auction_cache_info.key);
+
+    indexer.serialize(&mut *store_index_info.data.borrow_mut())?;
+
+    auction_cache.page = Some(page);
+    auction_cache.serialize(&mut *auction_cache_info.data.borrow_mut())?;
+
+    Ok(())
+}
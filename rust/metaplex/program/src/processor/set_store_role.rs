@@ -0,0 +1,92 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{Key, Store, StoreRole, StoreRoleRecord, MAX_STORE_ROLE_SIZE, PREFIX, ROLE},
+        utils::{
+            assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
+            is_store_owner,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_set_store_role<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    role: StoreRole,
+    active: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let role_record_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let holder_info = next_account_info(account_info_iter)?;
+    let store_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(payer_info)?;
+    assert_signer(owner_info)?;
+    if !role_record_info.data_is_empty() {
+        assert_owned_by(role_record_info, program_id)?;
+    }
+    assert_owned_by(store_info, program_id)?;
+
+    // Roles are granted by the owner only, never by other role holders
+    if !is_store_owner(program_id, store_info, owner_info.key) {
+        return Err(MetaplexError::MustBeStoreOwner.into());
+    }
+
+    if Store::from_account_info(store_info)?.successor.is_some() {
+        return Err(MetaplexError::StoreMigrated.into());
+    }
+
+    let role_bump = assert_derivation(
+        program_id,
+        role_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            store_info.key.as_ref(),
+            ROLE.as_bytes(),
+            role.seed().as_bytes(),
+            holder_info.key.as_ref(),
+        ],
+    )?;
+
+    if role_record_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            role_record_info,
+            rent_info,
+            system_info,
+            payer_info,
+            MAX_STORE_ROLE_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                store_info.key.as_ref(),
+                ROLE.as_bytes(),
+                role.seed().as_bytes(),
+                holder_info.key.as_ref(),
+                &[role_bump],
+            ],
+        )?;
+    }
+
+    let mut record = StoreRoleRecord::from_account_info(role_record_info)?;
+    record.key = Key::StoreRoleV1;
+    record.store = *store_info.key;
+    record.holder = *holder_info.key;
+    record.role = role;
+    record.active = active;
+
+    record.serialize(&mut *role_record_info.data.borrow_mut())?;
+    Ok(())
+}
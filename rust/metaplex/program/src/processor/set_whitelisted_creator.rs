@@ -1,8 +1,10 @@
 use {
     crate::{
-        state::{Key, WhitelistedCreator, MAX_WHITELISTED_CREATOR_SIZE, PREFIX},
+        error::MetaplexError,
+        state::{Key, Store, StoreRole, WhitelistedCreator, MAX_WHITELISTED_CREATOR_SIZE, PREFIX},
         utils::{
-            assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
+            assert_derivation, assert_owned_by, assert_signer, assert_store_authority,
+            create_or_allocate_account_raw,
         },
     },
     borsh::BorshSerialize,
@@ -27,24 +29,26 @@ pub fn process_set_whitelisted_creator<'a>(
     let store_info = next_account_info(account_info_iter)?;
     let system_info = next_account_info(account_info_iter)?;
     let rent_info = next_account_info(account_info_iter)?;
+    let role_record_info = next_account_info(account_info_iter).ok();
 
     assert_signer(payer_info)?;
-    assert_signer(admin_wallet_info)?;
     if !whitelisted_creator_info.data_is_empty() {
         assert_owned_by(whitelisted_creator_info, program_id)?;
     }
     assert_owned_by(store_info, program_id)?;
 
-    assert_derivation(
+    assert_store_authority(
         program_id,
         store_info,
-        &[
-            PREFIX.as_bytes(),
-            program_id.as_ref(),
-            admin_wallet_info.key.as_ref(),
-        ],
+        admin_wallet_info,
+        role_record_info,
+        &[StoreRole::StoreAdmin, StoreRole::CreatorCurator],
     )?;
 
+    if Store::from_account_info(store_info)?.successor.is_some() {
+        return Err(MetaplexError::StoreMigrated.into());
+    }
+
     let creator_bump = assert_derivation(
         program_id,
         whitelisted_creator_info,
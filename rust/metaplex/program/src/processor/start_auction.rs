@@ -6,9 +6,11 @@ use {
     },
     solana_program::{
         account_info::{next_account_info, AccountInfo},
+        clock::Clock,
         entrypoint::ProgramResult,
         program::invoke_signed,
         pubkey::Pubkey,
+        sysvar::Sysvar,
     },
     spl_auction::{
         instruction::{start_auction_instruction, StartAuctionArgs},
@@ -90,6 +92,7 @@ pub fn process_start_auction(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
     )?;
 
     auction_manager.set_status(AuctionManagerStatus::Running);
+    auction_manager.set_started_at(Clock::from_account_info(clock_info)?.unix_timestamp);
 
     auction_manager.save(auction_manager_info)?;
 
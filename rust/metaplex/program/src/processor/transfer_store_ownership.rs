@@ -0,0 +1,82 @@
+use {
+    crate::{
+        error::MetaplexError,
+        state::{Store, MAX_STORE_SIZE, PREFIX},
+        utils::{
+            assert_derivation, assert_owned_by, assert_signer, create_or_allocate_account_raw,
+            is_store_owner,
+        },
+    },
+    borsh::BorshSerialize,
+    solana_program::{
+        account_info::{next_account_info, AccountInfo},
+        entrypoint::ProgramResult,
+        pubkey::Pubkey,
+    },
+};
+
+pub fn process_transfer_store_ownership<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let store_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let new_store_info = next_account_info(account_info_iter)?;
+    let new_owner_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(owner_info)?;
+    assert_signer(new_owner_info)?;
+    assert_signer(payer_info)?;
+    assert_owned_by(store_info, program_id)?;
+
+    if !is_store_owner(program_id, store_info, owner_info.key) {
+        return Err(MetaplexError::MustBeStoreOwner.into());
+    }
+
+    if !new_store_info.data_is_empty() {
+        return Err(MetaplexError::AlreadyInitialized.into());
+    }
+
+    let mut store = Store::from_account_info(store_info)?;
+    if store.successor.is_some() {
+        return Err(MetaplexError::StoreMigrated.into());
+    }
+
+    let new_store_bump = assert_derivation(
+        program_id,
+        new_store_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            new_owner_info.key.as_ref(),
+        ],
+    )?;
+
+    create_or_allocate_account_raw(
+        *program_id,
+        new_store_info,
+        rent_info,
+        system_info,
+        payer_info,
+        MAX_STORE_SIZE,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            new_owner_info.key.as_ref(),
+            &[new_store_bump],
+        ],
+    )?;
+
+    store.serialize(&mut *new_store_info.data.borrow_mut())?;
+
+    // The old store stays readable for the auction managers that point at it, but is frozen
+    store.successor = Some(*new_store_info.key);
+    store.serialize(&mut *store_info.data.borrow_mut())?;
+
+    Ok(())
+}
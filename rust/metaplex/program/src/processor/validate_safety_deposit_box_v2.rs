@@ -21,7 +21,7 @@ use {
     },
     spl_token::state::{Account, Mint},
     spl_token_metadata::{
-        state::{MasterEditionV1, MasterEditionV2, Metadata},
+        state::{MasterEditionV1, MasterEditionV2, Metadata, TokenStandard},
         utils::assert_update_authority_is_correct,
     },
     spl_token_vault::state::{SafetyDepositBox, Vault},
@@ -156,7 +156,6 @@ pub fn assert_common_checks(args: CommonCheckArgs) -> ProgramResult {
     )?;
     assert_at_least_one_creator_matches_or_store_public_and_all_verified(
         program_id,
-        auction_manager,
         &metadata,
         whitelisted_creator_info,
         auction_manager_store_info,
@@ -184,6 +183,61 @@ pub fn assert_common_checks(args: CommonCheckArgs) -> ProgramResult {
     Ok(())
 }
 
+/// The metadata's token standard. Metadata from before token standards were recorded, or whose master
+/// edition was created with a read-only metadata account, has none, so it is read off the edition instead.
+pub fn get_token_standard(
+    metadata: &Metadata,
+    edition_info: &AccountInfo,
+    edition_key: &Pubkey,
+    store: &Store,
+) -> Option<TokenStandard> {
+    if metadata.token_standard.is_some() {
+        return metadata.token_standard;
+    }
+
+    if edition_info.key != edition_key
+        || edition_info.owner != &store.token_metadata_program
+        || edition_info.data_is_empty()
+    {
+        return None;
+    }
+
+    let key = edition_info.data.borrow()[0];
+    if key == spl_token_metadata::state::Key::EditionV1 as u8 {
+        Some(TokenStandard::NonFungibleEdition)
+    } else if key == spl_token_metadata::state::Key::MasterEditionV1 as u8
+        || key == spl_token_metadata::state::Key::MasterEditionV2 as u8
+    {
+        Some(TokenStandard::NonFungible)
+    } else {
+        None
+    }
+}
+
+/// Checks the token standard fits the winning config type. Non fungible standards pin the supply to one,
+/// so the supply logic checks only need the token to be in the safety deposit box.
+pub fn assert_token_standard_fits_winning_config_type(
+    token_standard: Option<TokenStandard>,
+    winning_config_type: &WinningConfigType,
+) -> ProgramResult {
+    let fits = match winning_config_type {
+        WinningConfigType::TokenOnlyTransfer => true,
+        WinningConfigType::FullRightsTransfer => {
+            token_standard == Some(TokenStandard::NonFungible)
+                || token_standard == Some(TokenStandard::NonFungibleEdition)
+        }
+        WinningConfigType::PrintingV1
+        | WinningConfigType::PrintingV2
+        | WinningConfigType::Participation => token_standard == Some(TokenStandard::NonFungible),
+    };
+
+    if !fits {
+        return Err(MetaplexError::InvalidTokenStandard.into());
+    }
+
+    Ok(())
+}
+
 pub struct SupplyLogicCheckArgs<'a, 'b> {
     pub program_id: &'a Pubkey,
     pub auction_manager_info: &'a AccountInfo<'a>,
@@ -237,6 +291,11 @@ pub fn assert_supply_logic_check(args: SupplyLogicCheckArgs) -> ProgramResult {
     let (edition_key, _) =
         Pubkey::find_program_address(edition_seeds, &store.token_metadata_program);
 
+    assert_token_standard_fits_winning_config_type(
+        get_token_standard(metadata, edition_info, &edition_key, store),
+        winning_config_type,
+    )?;
+
     let auction_key = auction_manager.auction();
     let seeds = &[PREFIX.as_bytes(), auction_key.as_ref()];
     let (_, bump_seed) = Pubkey::find_program_address(seeds, &program_id);
@@ -253,7 +312,7 @@ pub fn assert_supply_logic_check(args: SupplyLogicCheckArgs) -> ProgramResult {
                 return Err(MetaplexError::InvalidEditionAddress.into());
             }
 
-            if safety_deposit_token_store.amount != 1 {
+            if safety_deposit_token_store.amount == 0 {
                 return Err(MetaplexError::StoreIsEmpty.into());
             }
 
@@ -341,7 +400,7 @@ pub fn assert_supply_logic_check(args: SupplyLogicCheckArgs) -> ProgramResult {
                 return Err(MetaplexError::SafetyDepositBoxMetadataMismatch.into());
             }
 
-            if safety_deposit_token_store.amount != 1 {
+            if safety_deposit_token_store.amount == 0 {
                 return Err(MetaplexError::NotEnoughTokensToSupplyWinners.into());
             }
 
@@ -367,7 +426,7 @@ pub fn assert_supply_logic_check(args: SupplyLogicCheckArgs) -> ProgramResult {
                 return Err(MetaplexError::SafetyDepositBoxMetadataMismatch.into());
             }
 
-            if safety_deposit_token_store.amount != 1 {
+            if safety_deposit_token_store.amount == 0 {
                 return Err(MetaplexError::NotEnoughTokensToSupplyWinners.into());
             }
 
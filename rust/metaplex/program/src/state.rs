@@ -5,8 +5,8 @@ use {
     arrayref::{array_mut_ref, array_ref, mut_array_refs},
     borsh::{BorshDeserialize, BorshSerialize},
     solana_program::{
-        account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-        pubkey::Pubkey,
+        account_info::AccountInfo, clock::UnixTimestamp, entrypoint::ProgramResult,
+        program_error::ProgramError, pubkey::Pubkey,
     },
     spl_auction::processor::AuctionData,
     spl_token_metadata::state::Metadata,
@@ -16,6 +16,13 @@ use {
 /// prefix used for PDAs to avoid certain collision attacks (https://en.wikipedia.org/wiki/Collision_attack#Chosen-prefix_collision_attack)
 pub const PREFIX: &str = "metaplex";
 pub const TOTALS: &str = "totals";
+pub const LISTING: &str = "listing";
+pub const CACHE: &str = "cache";
+pub const ROLE: &str = "role";
+pub const OFFER: &str = "offer";
+pub const INDEX: &str = "index";
+pub const MAX_INDEXED_ELEMENTS: usize = 100;
+pub const MAX_METADATA_PER_CACHE: usize = 10;
 pub const BASE_TRACKER_SIZE: usize = 1 + 1 + 1 + 4;
 
 pub const MAX_AUCTION_MANAGER_V2_SIZE: usize = 1 + //key
@@ -27,13 +34,54 @@ pub const MAX_AUCTION_MANAGER_V2_SIZE: usize = 1 + //key
 1 + // has participation
 1 + //status
 8 + // winning configs validated
-200; // padding
-pub const MAX_STORE_SIZE: usize = 2 + 32 + 32 + 32 + 32 + 100;
+MAX_PLATFORM_FEE_SIZE + // platform fee
+9 + // started at
+156; // padding
+pub const MAX_PLATFORM_FEE_SIZE: usize = 1 + 2 + 32;
+// The platform fee and successor live in what used to be padding, so existing stores read them as None.
+pub const MAX_STORE_SIZE: usize = 2 + 32 + 32 + 32 + 32 + MAX_PLATFORM_FEE_SIZE + 33 + 32;
+pub const MAX_STORE_ROLE_SIZE: usize = 1 + 32 + 32 + 1 + 1 + 30;
 pub const MAX_WHITELISTED_CREATOR_SIZE: usize = 2 + 32 + 10;
 pub const MAX_PAYOUT_TICKET_SIZE: usize = 1 + 32 + 8;
 pub const MAX_BID_REDEMPTION_TICKET_SIZE: usize = 3;
 pub const MAX_AUTHORITY_LOOKUP_SIZE: usize = 33;
 pub const MAX_PRIZE_TRACKING_TICKET_SIZE: usize = 1 + 32 + 8 + 8 + 8 + 50;
+pub const MAX_FIXED_PRICE_LISTING_SIZE: usize = 1 + // key
+32 + // store
+32 + // authority
+32 + // metadata
+32 + // item token account
+32 + // accept payment
+8 + // price
+1 + // listing type
+1 + // status
+1 + // primary sale happened
+8 + // sold
+8 + // total collected
+MAX_PLATFORM_FEE_SIZE + // platform fee
+15; // padding
+pub const MAX_AUCTION_CACHE_SIZE: usize = 1 + // key
+32 + // store
+8 + // timestamp
+4 + (32 * MAX_METADATA_PER_CACHE) + // metadata
+32 + // auction
+32 + // vault
+32 + // auction manager
+32 + // payer
+9 + // page
+59; // padding
+pub const MAX_OFFER_SIZE: usize = 1 + // key
+32 + // metadata
+32 + // buyer
+33 + // escrow
+8 + // price
+8 + // expires at
+50; // padding
+pub const MAX_STORE_INDEXER_SIZE: usize = 1 + // key
+32 + // store
+8 + // page
+4 + (32 * MAX_INDEXED_ELEMENTS) + // auction caches
+100; // padding
 pub const BASE_SAFETY_CONFIG_SIZE: usize = 1 +// Key
  32 + // auction manager lookup
  8 + // order
@@ -65,6 +113,11 @@ pub enum Key {
     AuctionManagerV2,
     BidRedemptionTicketV2,
     AuctionWinnerTokenTypeTrackerV1,
+    FixedPriceListingV1,
+    AuctionCacheV1,
+    StoreIndexerV1,
+    StoreRoleV1,
+    OfferV1,
 }
 
 pub struct CommonWinningIndexChecks<'a> {
@@ -103,6 +156,9 @@ pub trait AuctionManager {
     fn auction(&self) -> Pubkey;
     fn vault(&self) -> Pubkey;
     fn accept_payment(&self) -> Pubkey;
+    fn platform_fee(&self) -> Option<PlatformFee>;
+    fn started_at(&self) -> Option<UnixTimestamp>;
+    fn set_started_at(&mut self, started_at: UnixTimestamp);
     fn status(&self) -> AuctionManagerStatus;
     fn set_status(&mut self, status: AuctionManagerStatus);
     fn configs_validated(&self) -> u64;
@@ -204,6 +260,12 @@ pub struct AuctionManagerV2 {
     pub accept_payment: Pubkey,
 
     pub state: AuctionManagerStateV2,
+
+    /// The store's platform fee when this auction manager was created, which its payouts use
+    pub platform_fee: Option<PlatformFee>,
+
+    /// When StartAuction ran, None for auction managers started before it was recorded
+    pub started_at: Option<UnixTimestamp>,
 }
 
 impl AuctionManager for AuctionManagerV2 {
@@ -231,6 +293,18 @@ impl AuctionManager for AuctionManagerV2 {
         self.accept_payment
     }
 
+    fn platform_fee(&self) -> Option<PlatformFee> {
+        self.platform_fee
+    }
+
+    fn started_at(&self) -> Option<UnixTimestamp> {
+        self.started_at
+    }
+
+    fn set_started_at(&mut self, started_at: UnixTimestamp) {
+        self.started_at = Some(started_at)
+    }
+
     fn status(&self) -> AuctionManagerStatus {
         self.state.status
     }
@@ -605,6 +679,7 @@ pub enum AuctionManagerStatus {
     Running,
     Disbursing,
     Finished,
+    Cancelled,
 }
 
 #[repr(C)]
@@ -655,6 +730,18 @@ pub struct Store {
     pub token_vault_program: Pubkey,
     pub token_metadata_program: Pubkey,
     pub token_program: Pubkey,
+    /// Cut of every auction's proceeds the store operator takes before creators and auctioneer are paid
+    pub platform_fee: Option<PlatformFee>,
+    /// Set once ownership has moved to a new store, after which this one can no longer be changed
+    pub successor: Option<Pubkey>,
+}
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Copy, PartialEq, Debug)]
+pub struct PlatformFee {
+    pub basis_points: u16,
+    /// Wallet whose associated token account for the accept payment mint receives the fee
+    pub destination: Pubkey,
 }
 
 impl Store {
@@ -666,6 +753,45 @@ impl Store {
     }
 }
 
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Copy, PartialEq, Debug)]
+pub enum StoreRole {
+    /// May change the store's settings, except for its platform fee, and curate creators
+    StoreAdmin,
+    /// May add and remove whitelisted creators
+    CreatorCurator,
+}
+
+impl StoreRole {
+    pub fn seed(&self) -> &'static str {
+        match self {
+            StoreRole::StoreAdmin => "admin",
+            StoreRole::CreatorCurator => "curator",
+        }
+    }
+}
+
+/// A role the store owner granted to another wallet,
+/// pda of ['metaplex', program id, store, 'role', role seed, holder]
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Copy)]
+pub struct StoreRoleRecord {
+    pub key: Key,
+    pub store: Pubkey,
+    pub holder: Pubkey,
+    pub role: StoreRole,
+    pub active: bool,
+}
+
+impl StoreRoleRecord {
+    pub fn from_account_info(a: &AccountInfo) -> Result<StoreRoleRecord, ProgramError> {
+        let record: StoreRoleRecord =
+            try_from_slice_checked(&a.data.borrow_mut(), Key::StoreRoleV1, MAX_STORE_ROLE_SIZE)?;
+
+        Ok(record)
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, Copy)]
 pub struct WhitelistedCreator {
@@ -708,6 +834,142 @@ impl PrizeTrackingTicket {
     }
 }
 
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Copy, PartialEq, Debug)]
+pub enum FixedPriceListingType {
+    /// The listed token itself goes to the one buyer
+    Sale,
+    /// The listed token is a Master Edition V2 and every buyer gets a new print of it
+    Prints,
+    /// A token that already had its primary sale, resold by its holder. Royalties go to the
+    /// verified creators by share and the seller gets the rest
+    Resale,
+}
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Copy, PartialEq, Debug)]
+pub enum FixedPriceListingStatus {
+    Active,
+    Sold,
+    Cancelled,
+}
+
+/// A buy-now listing in a store, pda of ['metaplex', program id, item token account, 'listing'].
+/// The listing owns the item token account and the accept payment account, and pays out of the
+/// latter the same way auction managers do, via payout tickets.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Copy, Debug)]
+pub struct FixedPriceListing {
+    pub key: Key,
+    pub store: Pubkey,
+    /// The seller, who gets the auctioneer's share of the proceeds
+    pub authority: Pubkey,
+    pub metadata: Pubkey,
+    pub item_token_account: Pubkey,
+    pub accept_payment: Pubkey,
+    pub price: u64,
+    pub listing_type: FixedPriceListingType,
+    pub status: FixedPriceListingStatus,
+    /// Snapshot of the metadata's primary sale flag at listing time, which decides the royalty split
+    pub primary_sale_happened: bool,
+    pub sold: u64,
+    pub total_collected: u64,
+    /// The store's platform fee when the listing was created, which its payouts use
+    pub platform_fee: Option<PlatformFee>,
+}
+
+impl FixedPriceListing {
+    pub fn from_account_info(a: &AccountInfo) -> Result<FixedPriceListing, ProgramError> {
+        let listing: FixedPriceListing = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::FixedPriceListingV1,
+            MAX_FIXED_PRICE_LISTING_SIZE,
+        )?;
+
+        Ok(listing)
+    }
+}
+
+/// An escrowed offer on an NFT that doesn't need to be listed,
+/// pda of ['metaplex', program id, metadata, 'offer', buyer].
+/// Closed back to the buyer when accepted or cancelled.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Copy, Debug)]
+pub struct Offer {
+    pub key: Key,
+    pub metadata: Pubkey,
+    pub buyer: Pubkey,
+    /// Token account owned by the offer holding the escrowed tokens,
+    /// None when the offer is in SOL held as lamports by the offer itself
+    pub escrow: Option<Pubkey>,
+    pub price: u64,
+    pub expires_at: UnixTimestamp,
+}
+
+impl Offer {
+    pub fn from_account_info(a: &AccountInfo) -> Result<Offer, ProgramError> {
+        let offer: Offer =
+            try_from_slice_checked(&a.data.borrow_mut(), Key::OfferV1, MAX_OFFER_SIZE)?;
+
+        Ok(offer)
+    }
+}
+
+/// Keys needed to display an auction without scanning the chain,
+/// pda of ['metaplex', program id, auction, 'cache']
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct AuctionCache {
+    pub key: Key,
+    pub store: Pubkey,
+    /// When the auction started
+    pub timestamp: UnixTimestamp,
+    /// Metadata of the items in the auction's safety deposit boxes
+    pub metadata: Vec<Pubkey>,
+    pub auction: Pubkey,
+    pub vault: Pubkey,
+    pub auction_manager: Pubkey,
+    /// Who funded the cache, refunded when it is removed
+    pub payer: Pubkey,
+    /// The store index page the cache was inserted into, a cache is only ever indexed once
+    pub page: Option<u64>,
+}
+
+impl AuctionCache {
+    pub fn from_account_info(a: &AccountInfo) -> Result<AuctionCache, ProgramError> {
+        let cache: AuctionCache = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::AuctionCacheV1,
+            MAX_AUCTION_CACHE_SIZE,
+        )?;
+
+        Ok(cache)
+    }
+}
+
+/// A page of a store's auction caches sorted by timestamp, oldest first,
+/// pda of ['metaplex', program id, store, 'index', page]
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct StoreIndexer {
+    pub key: Key,
+    pub store: Pubkey,
+    pub page: u64,
+    pub auction_caches: Vec<Pubkey>,
+}
+
+impl StoreIndexer {
+    pub fn from_account_info(a: &AccountInfo) -> Result<StoreIndexer, ProgramError> {
+        let indexer: StoreIndexer = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::StoreIndexerV1,
+            MAX_STORE_INDEXER_SIZE,
+        )?;
+
+        Ok(indexer)
+    }
+}
+
 #[repr(C)]
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize, Copy)]
 pub struct AmountRange(pub u64, pub u64);
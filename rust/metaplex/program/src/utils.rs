@@ -3,7 +3,7 @@ use {
         error::MetaplexError,
         state::{
             get_auction_manager, AuctionManager, AuctionManagerStatus, BidRedemptionTicket, Key,
-            OriginalAuthorityLookup, Store, WhitelistedCreator, PREFIX,
+            OriginalAuthorityLookup, Store, StoreRole, StoreRoleRecord, WhitelistedCreator, PREFIX,
         },
     },
     arrayref::array_ref,
@@ -22,8 +22,11 @@ use {
         sysvar::{rent::Rent, Sysvar},
     },
     spl_auction::{
-        instruction::end_auction_instruction,
-        processor::{end_auction::EndAuctionArgs, AuctionData, AuctionState},
+        instruction::{cancel_auction_instruction, end_auction_instruction},
+        processor::{
+            cancel_auction::CancelAuctionArgs, end_auction::EndAuctionArgs, AuctionData,
+            AuctionState,
+        },
     },
     spl_token::instruction::{set_authority, AuthorityType},
     spl_token_metadata::{
@@ -113,7 +116,6 @@ pub fn assert_store_safety_vault_manager_match(
 
 pub fn assert_at_least_one_creator_matches_or_store_public_and_all_verified(
     program_id: &Pubkey,
-    auction_manager: &dyn AuctionManager,
     metadata: &Metadata,
     whitelisted_creator_info: &AccountInfo,
     store_info: &AccountInfo,
@@ -141,7 +143,7 @@ pub fn assert_at_least_one_creator_matches_or_store_public_and_all_verified(
                 &[
                     PREFIX.as_bytes(),
                     program_id.as_ref(),
-                    auction_manager.store().as_ref(),
+                    store_info.key.as_ref(),
                     creator.address.as_ref(),
                 ],
                 program_id,
@@ -163,6 +165,45 @@ pub fn assert_at_least_one_creator_matches_or_store_public_and_all_verified(
     Err(MetaplexError::InvalidWhitelistedCreator.into())
 }
 
+/// True if the wallet is the one whose key seeds the store pda
+pub fn is_store_owner(program_id: &Pubkey, store_info: &AccountInfo, wallet: &Pubkey) -> bool {
+    let (store_key, _) = Pubkey::find_program_address(
+        &[PREFIX.as_bytes(), program_id.as_ref(), wallet.as_ref()],
+        program_id,
+    );
+
+    store_key == *store_info.key
+}
+
+/// Passes if the signer owns the store, or holds one of the given roles through its role record
+pub fn assert_store_authority(
+    program_id: &Pubkey,
+    store_info: &AccountInfo,
+    authority_info: &AccountInfo,
+    role_record_info: Option<&AccountInfo>,
+    roles: &[StoreRole],
+) -> ProgramResult {
+    assert_signer(authority_info)?;
+
+    if is_store_owner(program_id, store_info, authority_info.key) {
+        return Ok(());
+    }
+
+    let role_record_info = role_record_info.ok_or(MetaplexError::MissingStoreRole)?;
+    assert_owned_by(role_record_info, program_id)?;
+    let record = StoreRoleRecord::from_account_info(role_record_info)?;
+
+    if record.store != *store_info.key
+        || record.holder != *authority_info.key
+        || !record.active
+        || !roles.contains(&record.role)
+    {
+        return Err(MetaplexError::MissingStoreRole.into());
+    }
+
+    Ok(())
+}
+
 pub fn assert_authority_correct(
     auction_manager_authority: &Pubkey,
     authority_info: &AccountInfo,
@@ -473,6 +514,45 @@ pub fn assert_safety_deposit_config_valid(
     Ok(())
 }
 
+/// The checks in common_redeem_checks that concern a single prize, split out so that redeeming
+/// several prizes at once only repeats these per safety deposit box.
+#[allow(clippy::too_many_arguments)]
+pub fn common_redeem_item_checks(
+    program_id: &Pubkey,
+    auction_manager_info: &AccountInfo,
+    auction_manager: &dyn AuctionManager,
+    safety_deposit_token_store_info: &AccountInfo,
+    destination_info: &AccountInfo,
+    safety_deposit_info: &AccountInfo,
+    vault_info: &AccountInfo,
+    safety_deposit_config_info: Option<&AccountInfo>,
+    token_program_info: &AccountInfo,
+    token_vault_program: &Pubkey,
+    rent: &Rent,
+) -> ProgramResult {
+    assert_owned_by(destination_info, token_program_info.key)?;
+    assert_owned_by(safety_deposit_token_store_info, token_program_info.key)?;
+    assert_owned_by(safety_deposit_info, token_vault_program)?;
+
+    assert_store_safety_vault_manager_match(
+        &auction_manager.vault(),
+        safety_deposit_info,
+        vault_info,
+        token_vault_program,
+    )?;
+    assert_safety_deposit_config_valid(
+        program_id,
+        auction_manager_info,
+        safety_deposit_info,
+        safety_deposit_config_info,
+        &auction_manager.key(),
+    )?;
+    // looking out for you!
+    assert_rent_exempt(rent, destination_info)?;
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn common_redeem_checks(
     args: CommonRedeemCheckArgs,
@@ -580,32 +660,27 @@ pub fn common_redeem_checks(
         assert_signer(bidder_info)?;
     }
 
-    assert_owned_by(&destination_info, token_program_info.key)?;
     assert_owned_by(&auction_manager_info, &program_id)?;
-    assert_owned_by(safety_deposit_token_store_info, token_program_info.key)?;
     if !bid_redemption_info.data_is_empty() {
         assert_owned_by(bid_redemption_info, &program_id)?;
     }
-    assert_owned_by(safety_deposit_info, &token_vault_program)?;
     assert_owned_by(vault_info, &token_vault_program)?;
     assert_owned_by(auction_info, &auction_program)?;
     assert_owned_by(store_info, &program_id)?;
 
-    assert_store_safety_vault_manager_match(
-        &auction_manager.vault(),
-        &safety_deposit_info,
-        &vault_info,
-        &token_vault_program,
-    )?;
-    assert_safety_deposit_config_valid(
+    common_redeem_item_checks(
         program_id,
         auction_manager_info,
+        auction_manager.as_ref(),
+        safety_deposit_token_store_info,
+        destination_info,
         safety_deposit_info,
+        vault_info,
         safety_deposit_config_info,
-        &auction_manager.key(),
+        token_program_info,
+        &token_vault_program,
+        rent,
     )?;
-    // looking out for you!
-    assert_rent_exempt(rent, &destination_info)?;
 
     if auction_manager.auction() != *auction_info.key {
         return Err(MetaplexError::AuctionManagerAuctionMismatch.into());
@@ -631,6 +706,10 @@ pub fn common_redeem_checks(
         return Err(MetaplexError::AuctionHasNotEnded.into());
     }
 
+    if auction_manager.status() == AuctionManagerStatus::Cancelled {
+        return Err(MetaplexError::AuctionCancelled.into());
+    }
+
     // No-op if already set.
     auction_manager.set_status(AuctionManagerStatus::Disbursing);
 
@@ -853,6 +932,19 @@ pub fn spl_token_mint_to<'a: 'b, 'b>(
     result.map_err(|_| MetaplexError::TokenMintToFailed.into())
 }
 
+/// Zero out a program-owned account and move all of its lamports to the receiver.
+pub fn close_program_account(account: &AccountInfo, receiver: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **receiver.lamports.borrow_mut() = receiver
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(MetaplexError::NumericalOverflowError)?;
+    **account.lamports.borrow_mut() = 0;
+    account.data.borrow_mut().fill(0);
+
+    Ok(())
+}
+
 pub fn assert_derivation(
     program_id: &Pubkey,
     account: &AccountInfo,
@@ -905,6 +997,27 @@ pub fn end_auction<'a: 'b, 'b>(
     Ok(())
 }
 
+pub fn cancel_auction<'a: 'b, 'b>(
+    resource: Pubkey,
+    auction: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    auction_program: AccountInfo<'a>,
+    clock: AccountInfo<'a>,
+    authority_signer_seeds: &'b [&'b [u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &cancel_auction_instruction(
+            *auction_program.key,
+            *authority.key,
+            CancelAuctionArgs { resource },
+        ),
+        &[auction, authority, auction_program, clock],
+        &[authority_signer_seeds],
+    )?;
+
+    Ok(())
+}
+
 pub fn assert_is_ata(
     account: &AccountInfo,
     wallet: &Pubkey,
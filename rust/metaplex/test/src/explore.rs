@@ -0,0 +1,617 @@
+use {
+    super::make_account_with_data,
+    clap::ArgMatches,
+    serde_json::{json, Value},
+    solana_account_decoder::UiAccountEncoding,
+    solana_clap_utils::input_parsers::pubkey_of,
+    solana_client::{
+        rpc_client::RpcClient,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
+    },
+    solana_program::{borsh::try_from_slice_unchecked, pubkey::Pubkey},
+    solana_sdk::{account::Account, bs58, signature::Keypair},
+    spl_auction::processor::AuctionData,
+    spl_metaplex::state::{
+        get_auction_manager, AuctionManager, AuctionWinnerTokenTypeTracker, Key, PayoutTicket,
+        PrizeTrackingTicket, SafetyDepositConfig, WinningConfigType, PREFIX, TOTALS,
+    },
+    spl_token_metadata::state::Metadata,
+    spl_token_vault::state::SafetyDepositBox,
+    std::collections::BTreeMap,
+};
+
+/// Prints the JSON value when --json is given, the human readable lines otherwise.
+fn output(app_matches: &ArgMatches, value: Value, lines: Vec<String>) {
+    if app_matches.is_present("json") {
+        println!("{}", serde_json::to_string_pretty(&value).unwrap());
+    } else {
+        for line in lines {
+            println!("{}", line);
+        }
+    }
+}
+
+fn memcmp(offset: usize, bytes: &[u8]) -> RpcFilterType {
+    RpcFilterType::Memcmp(Memcmp {
+        offset,
+        bytes: MemcmpEncodedBytes::Binary(bs58::encode(bytes).into_string()),
+        encoding: None,
+    })
+}
+
+/// Accounts of the program with the given key byte whose first field is parent, filtered
+/// by the RPC node so we never scan the whole program.
+fn get_program_accounts_by_parent(
+    client: &RpcClient,
+    program: &Pubkey,
+    key: u8,
+    parent: &Pubkey,
+) -> Vec<(Pubkey, Account)> {
+    client
+        .get_program_accounts_with_config(
+            program,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![memcmp(0, &[key]), memcmp(1, parent.as_ref())]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )
+        .unwrap()
+}
+
+fn load_auction_manager(client: &RpcClient, key: &Pubkey) -> Box<dyn AuctionManager> {
+    let mut account = client.get_account(key).unwrap();
+    let mut lamports = 0;
+    get_auction_manager(&make_account_with_data(key, &mut account, &mut lamports)).unwrap()
+}
+
+fn load_auction(client: &RpcClient, auction_manager: &dyn AuctionManager) -> AuctionData {
+    let account = client.get_account(&auction_manager.auction()).unwrap();
+    try_from_slice_unchecked(&account.data).unwrap()
+}
+
+/// Every SafetyDepositConfig of an auction manager, sorted by safety deposit order.
+fn load_safety_deposit_configs(
+    client: &RpcClient,
+    auction_manager_key: &Pubkey,
+) -> Vec<(Pubkey, SafetyDepositConfig)> {
+    let mut configs = vec![];
+    for (key, mut acct) in get_program_accounts_by_parent(
+        client,
+        &spl_metaplex::id(),
+        Key::SafetyDepositConfigV1 as u8,
+        auction_manager_key,
+    ) {
+        let mut lamports = 0;
+        match SafetyDepositConfig::from_account_info(&make_account_with_data(
+            &key,
+            &mut acct,
+            &mut lamports,
+        )) {
+            Ok(config) => configs.push((key, config)),
+            Err(_) => eprintln!("Skipping {}", key),
+        }
+    }
+    configs.sort_by_key(|(_, config)| config.order);
+    configs
+}
+
+/// Every safety deposit box of a vault, keyed by order.
+fn load_safety_deposit_boxes(
+    client: &RpcClient,
+    vault: &Pubkey,
+) -> BTreeMap<u64, (Pubkey, SafetyDepositBox)> {
+    let mut boxes = BTreeMap::new();
+    for box_key in &[
+        spl_token_vault::state::Key::SafetyDepositBoxV1 as u8,
+        spl_token_vault::state::Key::SafetyDepositBoxV2 as u8,
+    ] {
+        for (key, acct) in
+            get_program_accounts_by_parent(client, &spl_token_vault::id(), *box_key, vault)
+        {
+            if let Ok(safety_deposit) = SafetyDepositBox::from_data(&acct.data) {
+                boxes.insert(safety_deposit.order as u64, (key, safety_deposit));
+            }
+        }
+    }
+    boxes
+}
+
+fn load_metadata(client: &RpcClient, mint: &Pubkey) -> Option<Metadata> {
+    let program = spl_token_metadata::id();
+    let (key, _) = Pubkey::find_program_address(
+        &[
+            spl_token_metadata::state::PREFIX.as_bytes(),
+            program.as_ref(),
+            mint.as_ref(),
+        ],
+        &program,
+    );
+    let account = client.get_account(&key).ok()?;
+    try_from_slice_unchecked(&account.data).ok()
+}
+
+/// How many tokens of this safety deposit the winner at winner_index receives.
+fn amount_for_winner(config: &SafetyDepositConfig, winner_index: u64) -> u64 {
+    let mut start = 0;
+    for range in &config.amount_ranges {
+        if winner_index >= start && winner_index < start + range.1 {
+            return range.0;
+        }
+        start += range.1;
+    }
+    0
+}
+
+/// Mirrors BidRedemptionTicket::get_index_and_mask for a ticket fetched over RPC.
+fn is_redeemed(ticket: &Account, order: u64) -> bool {
+    let data = &ticket.data;
+    if data.is_empty() || data[0] != Key::BidRedemptionTicketV2 as u8 {
+        return false;
+    }
+
+    let offset = if data[1] == 0 { 34 } else { 42 };
+    let position = (order / 8) as usize + offset;
+    let mask = 1u8 << (7 - order % 8);
+    position < data.len() && data[position] & mask != 0
+}
+
+pub fn send_list_auction_managers(app_matches: &ArgMatches, _payer: Keypair, client: RpcClient) {
+    let store_key = pubkey_of(app_matches, "store").unwrap();
+
+    let mut rows = vec![];
+    let mut lines = vec![];
+    let auction_managers = [Key::AuctionManagerV1, Key::AuctionManagerV2]
+        .iter()
+        .flat_map(|manager_key| {
+            get_program_accounts_by_parent(
+                &client,
+                &spl_metaplex::id(),
+                *manager_key as u8,
+                &store_key,
+            )
+        })
+        .collect::<Vec<_>>();
+    for (key, mut acct) in auction_managers {
+        let mut lamports = 0;
+        let auction_manager =
+            match get_auction_manager(&make_account_with_data(&key, &mut acct, &mut lamports)) {
+                Ok(auction_manager) => auction_manager,
+                Err(_) => {
+                    eprintln!("Skipping {}", key);
+                    continue;
+                }
+            };
+        let auction = load_auction(&client, auction_manager.as_ref());
+
+        lines.push(format!(
+            "{} {:?} {:?}, auction {} {:?}, {} of {} winners",
+            key,
+            auction_manager.key(),
+            auction_manager.status(),
+            auction_manager.auction(),
+            auction.state,
+            auction.num_winners(),
+            auction.num_possible_winners(),
+        ));
+        rows.push(json!({
+            "auction_manager": key.to_string(),
+            "version": format!("{:?}", auction_manager.key()),
+            "status": format!("{:?}", auction_manager.status()),
+            "authority": auction_manager.authority().to_string(),
+            "auction": auction_manager.auction().to_string(),
+            "auction_state": format!("{:?}", auction.state),
+            "winners": auction.num_winners(),
+            "possible_winners": auction.num_possible_winners(),
+        }));
+    }
+
+    lines.push(format!("Found {} auction managers", rows.len()));
+    output(app_matches, Value::Array(rows), lines);
+}
+
+pub fn send_show_items(app_matches: &ArgMatches, _payer: Keypair, client: RpcClient) {
+    let auction_manager_key = pubkey_of(app_matches, "auction_manager").unwrap();
+    let auction_manager = load_auction_manager(&client, &auction_manager_key);
+    let boxes = load_safety_deposit_boxes(&client, &auction_manager.vault());
+    let program = spl_metaplex::id();
+
+    let mut rows = vec![];
+    let mut lines = vec![];
+    for (config_key, config) in load_safety_deposit_configs(&client, &auction_manager_key) {
+        let safety_deposit = boxes.get(&config.order);
+
+        let prize_tracking_ticket = safety_deposit.and_then(|(_, safety_deposit)| {
+            let (key, _) = Pubkey::find_program_address(
+                &[
+                    PREFIX.as_bytes(),
+                    program.as_ref(),
+                    auction_manager_key.as_ref(),
+                    safety_deposit.token_mint.as_ref(),
+                ],
+                &program,
+            );
+            let mut account = client.get_account(&key).ok()?;
+            let mut lamports = 0;
+            PrizeTrackingTicket::from_account_info(&make_account_with_data(
+                &key,
+                &mut account,
+                &mut lamports,
+            ))
+            .ok()
+            .map(|ticket| (key, ticket))
+        });
+
+        lines.push(format!(
+            "Safety deposit config {}: {:#?}",
+            config_key, config
+        ));
+        match &prize_tracking_ticket {
+            Some((key, ticket)) => {
+                lines.push(format!("Prize tracking ticket {}: {:#?}", key, ticket))
+            }
+            None => lines.push("No prize tracking ticket found".to_owned()),
+        }
+
+        rows.push(json!({
+            "safety_deposit_config": config_key.to_string(),
+            "order": config.order,
+            "safety_deposit_box": safety_deposit.map(|(key, _)| key.to_string()),
+            "token_mint": safety_deposit.map(|(_, safety_deposit)| safety_deposit.token_mint.to_string()),
+            "winning_config_type": format!("{:?}", config.winning_config_type),
+            "amount_ranges": config
+                .amount_ranges
+                .iter()
+                .map(|range| json!({ "amount": range.0, "length": range.1 }))
+                .collect::<Vec<Value>>(),
+            "participation_fixed_price": config
+                .participation_config
+                .as_ref()
+                .and_then(|participation| participation.fixed_price),
+            "collected_to_accept_payment": config
+                .participation_state
+                .as_ref()
+                .map(|participation| participation.collected_to_accept_payment),
+            "prize_tracking_ticket": prize_tracking_ticket.map(|(key, ticket)| json!({
+                "address": key.to_string(),
+                "metadata": ticket.metadata.to_string(),
+                "supply_snapshot": ticket.supply_snapshot,
+                "expected_redemptions": ticket.expected_redemptions,
+                "redemptions": ticket.redemptions,
+            })),
+        }));
+    }
+
+    output(app_matches, Value::Array(rows), lines);
+}
+
+pub fn send_show_unredeemed(app_matches: &ArgMatches, _payer: Keypair, client: RpcClient) {
+    let auction_manager_key = pubkey_of(app_matches, "auction_manager").unwrap();
+    let auction_manager = load_auction_manager(&client, &auction_manager_key);
+    if auction_manager.key() != Key::AuctionManagerV2 {
+        let message = "Redemption tracking is only decoded for AuctionManagerV2s";
+        output(
+            app_matches,
+            json!({ "error": message }),
+            vec![message.to_owned()],
+        );
+        return;
+    }
+
+    let auction = load_auction(&client, auction_manager.as_ref());
+    let boxes = load_safety_deposit_boxes(&client, &auction_manager.vault());
+    let configs = load_safety_deposit_configs(&client, &auction_manager_key);
+    let auction_program = spl_auction::id();
+    let auction_key = auction_manager.auction();
+
+    let mut rows = vec![];
+    let mut lines = vec![];
+    for winner_index in 0..auction.num_winners() {
+        let bidder = match auction.winner_at(winner_index as usize) {
+            Some(bidder) => bidder,
+            None => continue,
+        };
+
+        let (bidder_metadata, _) = Pubkey::find_program_address(
+            &[
+                spl_auction::PREFIX.as_bytes(),
+                auction_program.as_ref(),
+                auction_key.as_ref(),
+                bidder.as_ref(),
+                "metadata".as_bytes(),
+            ],
+            &auction_program,
+        );
+        let (bid_redemption, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                auction_key.as_ref(),
+                bidder_metadata.as_ref(),
+            ],
+            &spl_metaplex::id(),
+        );
+        let ticket = client.get_account(&bid_redemption).ok();
+
+        for (config_key, config) in &configs {
+            let amount = amount_for_winner(config, winner_index);
+            if amount == 0
+                || config.winning_config_type == WinningConfigType::Participation
+                || ticket
+                    .as_ref()
+                    .map_or(false, |ticket| is_redeemed(ticket, config.order))
+            {
+                continue;
+            }
+
+            let safety_deposit = boxes.get(&config.order).map(|(key, _)| key.to_string());
+            lines.push(format!(
+                "Winner #{} {} has not redeemed {} x {:?} from safety deposit #{} {}",
+                winner_index + 1,
+                bidder,
+                amount,
+                config.winning_config_type,
+                config.order,
+                safety_deposit
+                    .clone()
+                    .unwrap_or_else(|| config_key.to_string()),
+            ));
+            rows.push(json!({
+                "winner_index": winner_index,
+                "bidder": bidder.to_string(),
+                "bid": auction.bid_state.amount(winner_index as usize),
+                "bid_redemption": bid_redemption.to_string(),
+                "safety_deposit_config": config_key.to_string(),
+                "safety_deposit_box": safety_deposit,
+                "order": config.order,
+                "winning_config_type": format!("{:?}", config.winning_config_type),
+                "amount": amount,
+            }));
+        }
+    }
+
+    lines.push(format!("{} unredeemed prizes", rows.len()));
+    output(app_matches, Value::Array(rows), lines);
+}
+
+/// Payee of a payout ticket, see process_empty_payment_account.
+enum Payee {
+    Creator(u8),
+    Auctioneer,
+    Platform,
+}
+
+/// Mirrors calculate_owed_amount in process_empty_payment_account without its logging.
+fn calculate_owed(
+    amount: u64,
+    metadata: &Metadata,
+    payee: &Payee,
+    platform_fee_basis_points: Option<u16>,
+    proportion_divisor: u64,
+) -> u64 {
+    let amount = amount as u128;
+    let platform_fee = amount * platform_fee_basis_points.unwrap_or(0) as u128 / 10000;
+    let seller_fee = metadata.data.seller_fee_basis_points as u128;
+    let (amount, numerator, artist_further_multiplier) = match payee {
+        Payee::Platform => (platform_fee, 10000, 10000),
+        Payee::Creator(index) => (
+            amount - platform_fee,
+            if metadata.primary_sale_happened {
+                seller_fee
+            } else {
+                10000
+            },
+            metadata.data.creators.as_ref().unwrap()[*index as usize].share as u128 * 100,
+        ),
+        Payee::Auctioneer => (
+            amount - platform_fee,
+            if metadata.primary_sale_happened {
+                10000 - seller_fee
+            } else {
+                0
+            },
+            10000,
+        ),
+    };
+
+    (amount * numerator * artist_further_multiplier / proportion_divisor as u128 / (10000 * 10000))
+        as u64
+}
+
+pub fn send_show_payouts(app_matches: &ArgMatches, _payer: Keypair, client: RpcClient) {
+    let auction_manager_key = pubkey_of(app_matches, "auction_manager").unwrap();
+    let auction_manager = load_auction_manager(&client, &auction_manager_key);
+    if auction_manager.key() != Key::AuctionManagerV2 {
+        let message = "Payouts are only calculated for AuctionManagerV2s";
+        output(
+            app_matches,
+            json!({ "error": message }),
+            vec![message.to_owned()],
+        );
+        return;
+    }
+
+    let program = spl_metaplex::id();
+    let auction = load_auction(&client, auction_manager.as_ref());
+    let boxes = load_safety_deposit_boxes(&client, &auction_manager.vault());
+    let platform_fee = auction_manager.platform_fee();
+
+    let (tracker_key, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program.as_ref(),
+            auction_manager_key.as_ref(),
+            TOTALS.as_bytes(),
+        ],
+        &program,
+    );
+    let tracker = client
+        .get_account(&tracker_key)
+        .ok()
+        .and_then(|mut account| {
+            let mut lamports = 0;
+            AuctionWinnerTokenTypeTracker::from_account_info(&make_account_with_data(
+                &tracker_key,
+                &mut account,
+                &mut lamports,
+            ))
+            .ok()
+        });
+    let unique_token_types = |winner_index: u64| -> u64 {
+        let mut start = 0;
+        for range in tracker
+            .iter()
+            .flat_map(|tracker| tracker.amount_ranges.iter())
+        {
+            if winner_index >= start && winner_index < start + range.1 {
+                return range.0;
+            }
+            start += range.1;
+        }
+        1
+    };
+
+    let mut rows = vec![];
+    let mut totals: BTreeMap<Pubkey, (u64, u64)> = BTreeMap::new();
+    for (_, config) in load_safety_deposit_configs(&client, &auction_manager_key) {
+        let (safety_deposit_key, safety_deposit) = match boxes.get(&config.order) {
+            Some(safety_deposit) => safety_deposit,
+            None => continue,
+        };
+        let metadata = match load_metadata(&client, &safety_deposit.token_mint) {
+            Some(metadata) => metadata,
+            None => {
+                eprintln!("No metadata found for {}", safety_deposit.token_mint);
+                continue;
+            }
+        };
+
+        // (winning config index key, amount to split, number of prizes sharing it)
+        let mut proceeds = vec![];
+        if config.winning_config_type == WinningConfigType::Participation {
+            if let Some(state) = &config.participation_state {
+                proceeds.push((
+                    "participation".to_owned(),
+                    state.collected_to_accept_payment,
+                    1,
+                ));
+            }
+        } else {
+            for winner_index in 0..auction.num_winners() {
+                if amount_for_winner(&config, winner_index) > 0 {
+                    proceeds.push((
+                        winner_index.to_string(),
+                        auction.bid_state.amount(winner_index as usize),
+                        unique_token_types(winner_index),
+                    ));
+                }
+            }
+        }
+
+        let mut payees = vec![];
+        if let Some(creators) = &metadata.data.creators {
+            for (index, creator) in creators.iter().enumerate() {
+                payees.push((
+                    Payee::Creator(index as u8),
+                    index.to_string(),
+                    creator.address,
+                ));
+            }
+        }
+        payees.push((
+            Payee::Auctioneer,
+            "auctioneer".to_owned(),
+            auction_manager.authority(),
+        ));
+        if let Some(platform_fee) = platform_fee {
+            payees.push((
+                Payee::Platform,
+                "platform".to_owned(),
+                platform_fee.destination,
+            ));
+        }
+
+        for (winning_config_index_key, amount, proportion_divisor) in &proceeds {
+            for (payee, creator_index_key, recipient) in &payees {
+                let owed = calculate_owed(
+                    *amount,
+                    &metadata,
+                    payee,
+                    platform_fee.map(|fee| fee.basis_points),
+                    *proportion_divisor,
+                );
+                let (payout_ticket_key, _) = Pubkey::find_program_address(
+                    &[
+                        PREFIX.as_bytes(),
+                        auction_manager_key.as_ref(),
+                        winning_config_index_key.as_bytes(),
+                        "0".as_bytes(),
+                        creator_index_key.as_bytes(),
+                        safety_deposit_key.as_ref(),
+                        recipient.as_ref(),
+                    ],
+                    &program,
+                );
+                let paid = client
+                    .get_account(&payout_ticket_key)
+                    .ok()
+                    .and_then(|mut account| {
+                        let mut lamports = 0;
+                        PayoutTicket::from_account_info(&make_account_with_data(
+                            &payout_ticket_key,
+                            &mut account,
+                            &mut lamports,
+                        ))
+                        .ok()
+                    })
+                    .map_or(0, |ticket| ticket.amount_paid);
+                let unpaid = owed.saturating_sub(paid);
+
+                let total = totals.entry(*recipient).or_insert((0, 0));
+                total.0 += paid;
+                total.1 += unpaid;
+                if unpaid == 0 {
+                    continue;
+                }
+
+                rows.push(json!({
+                    "recipient": recipient.to_string(),
+                    "payee": creator_index_key,
+                    "payout_ticket": payout_ticket_key.to_string(),
+                    "safety_deposit_box": safety_deposit_key.to_string(),
+                    "winning_config_index": winning_config_index_key,
+                    "owed": owed,
+                    "paid": paid,
+                    "unpaid": unpaid,
+                }));
+            }
+        }
+    }
+
+    let mut lines = vec![];
+    for (recipient, (paid, unpaid)) in &totals {
+        lines.push(format!(
+            "{}: {} unpaid, {} already paid",
+            recipient, unpaid, paid
+        ));
+    }
+    lines.push(format!("{} payout tickets with unpaid amounts", rows.len()));
+    output(
+        app_matches,
+        json!({
+            "recipients": totals
+                .iter()
+                .map(|(recipient, (paid, unpaid))| json!({
+                    "recipient": recipient.to_string(),
+                    "paid": paid,
+                    "unpaid": unpaid,
+                }))
+                .collect::<Vec<Value>>(),
+            "payout_tickets": rows,
+        }),
+        lines,
+    );
+}
@@ -1,7 +1,11 @@
+mod explore;
 mod show;
 
 use {
     clap::{crate_description, crate_name, crate_version, App, Arg, SubCommand},
+    explore::{
+        send_list_auction_managers, send_show_items, send_show_payouts, send_show_unredeemed,
+    },
     show::send_show,
     solana_clap_utils::input_validators::{is_url, is_valid_pubkey, is_valid_signer},
     solana_client::rpc_client::RpcClient,
@@ -69,6 +73,82 @@ fn main() {
                         .help("Pubkey of auction manager."),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("list_auction_managers")
+                .about("List every auction manager of a store with its status and winner counts.")
+                .arg(
+                    Arg::with_name("store")
+                        .long("store")
+                        .value_name("STORE")
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("Pubkey of store."),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .takes_value(false)
+                        .help("Print JSON instead of human readable output."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("show_items")
+                .about("Print every safety deposit config and prize tracking ticket of a manager.")
+                .arg(
+                    Arg::with_name("auction_manager")
+                        .long("auction_manager")
+                        .value_name("AUCTION_MANAGER")
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("Pubkey of auction manager."),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .takes_value(false)
+                        .help("Print JSON instead of human readable output."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("show_unredeemed")
+                .about("Print every prize a winner of a manager has not redeemed yet.")
+                .arg(
+                    Arg::with_name("auction_manager")
+                        .long("auction_manager")
+                        .value_name("AUCTION_MANAGER")
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("Pubkey of auction manager."),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .takes_value(false)
+                        .help("Print JSON instead of human readable output."),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("show_payouts")
+                .about("Print the amounts a manager still owes each creator, the auctioneer and the platform.")
+                .arg(
+                    Arg::with_name("auction_manager")
+                        .long("auction_manager")
+                        .value_name("AUCTION_MANAGER")
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("Pubkey of auction manager."),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .takes_value(false)
+                        .help("Print JSON instead of human readable output."),
+                ),
+        )
         .get_matches();
 
     let client = RpcClient::new(
@@ -86,6 +166,18 @@ fn main() {
         ("show", Some(arg_matches)) => {
             send_show(arg_matches, payer, client);
         }
+        ("list_auction_managers", Some(arg_matches)) => {
+            send_list_auction_managers(arg_matches, payer, client);
+        }
+        ("show_items", Some(arg_matches)) => {
+            send_show_items(arg_matches, payer, client);
+        }
+        ("show_unredeemed", Some(arg_matches)) => {
+            send_show_unredeemed(arg_matches, payer, client);
+        }
+        ("show_payouts", Some(arg_matches)) => {
+            send_show_payouts(arg_matches, payer, client);
+        }
 
         _ => unreachable!(),
     }
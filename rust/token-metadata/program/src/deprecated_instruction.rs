@@ -57,7 +57,7 @@ pub fn deprecated_create_master_edition(
         AccountMeta::new_readonly(update_authority, true),
         AccountMeta::new_readonly(printing_mint_authority, true),
         AccountMeta::new_readonly(mint_authority, true),
-        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new(metadata, false),
         AccountMeta::new_readonly(payer, false),
         AccountMeta::new_readonly(spl_token::id(), false),
         AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -171,7 +171,7 @@ pub fn deprecated_create_reservation_list(
         program_id,
         accounts: vec![
             AccountMeta::new(reservation_list, false),
-            AccountMeta::new_readonly(payer, true),
+            AccountMeta::new(payer, true),
             AccountMeta::new_readonly(update_authority, true),
             AccountMeta::new_readonly(master_edition, false),
             AccountMeta::new_readonly(resource, false),
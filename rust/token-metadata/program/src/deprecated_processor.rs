@@ -5,8 +5,8 @@ use {
         error::MetadataError,
         state::{
             get_reservation_list, Key, MasterEditionV1, Metadata, Reservation, ReservationListV2,
-            EDITION, MAX_MASTER_EDITION_LEN, MAX_RESERVATIONS, MAX_RESERVATION_LIST_SIZE, PREFIX,
-            RESERVATION,
+            TokenStandard, EDITION, MAX_MASTER_EDITION_LEN, MAX_RESERVATIONS,
+            MAX_RESERVATION_LIST_SIZE, PREFIX, RESERVATION,
         },
         utils::{
             assert_derivation, assert_initialized, assert_mint_authority_matches_mint,
@@ -48,7 +48,7 @@ pub fn process_deprecated_create_master_edition(
     let system_account_info = next_account_info(account_info_iter)?;
     let rent_info = next_account_info(account_info_iter)?;
 
-    let metadata = Metadata::from_account_info(metadata_account_info)?;
+    let mut metadata = Metadata::from_account_info(metadata_account_info)?;
     let mint: Mint = assert_initialized(mint_info)?;
     let printing_mint: Mint = assert_initialized(printing_mint_info)?;
     let one_time_printing_authorization_mint: Mint =
@@ -129,6 +129,12 @@ pub fn process_deprecated_create_master_edition(
     edition.one_time_printing_authorization_mint = *one_time_printing_authorization_mint_info.key;
     edition.serialize(&mut *edition_account_info.data.borrow_mut())?;
 
+    // Older clients pass the metadata read-only, SetTokenStandard can catch those up later.
+    if metadata_account_info.is_writable {
+        metadata.token_standard = Some(TokenStandard::NonFungible);
+        metadata.save(metadata_account_info)?;
+    }
+
     // While you can't mint any more of your master record, you can
     // mint as many limited editions as you like, and coins to permission others
     // to mint one of them in the future.
@@ -327,6 +333,7 @@ pub fn process_deprecated_create_reservation_list(
     reservation.master_edition = *master_edition_info.key;
     reservation.supply_snapshot = None;
     reservation.reservations = vec![];
+    reservation.funder = Some(*payer_info.key);
 
     reservation.serialize(&mut *reservation_list_info.data.borrow_mut())?;
 
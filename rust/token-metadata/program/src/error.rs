@@ -306,6 +306,106 @@ pub enum MetadataError {
     // In the legacy system the reservation needs to be of size one for cpu limit reasons
     #[error("In the legacy system the reservation needs to be of size one for cpu limit reasons")]
     ReservationArrayShouldBeSizeOne,
+
+    /// Collections must be backed by a Master Edition V2
+    #[error("Collections must be backed by a Master Edition V2")]
+    CollectionMustBeAMasterEditionV2,
+
+    /// This metadata does not belong to the given collection
+    #[error("This metadata does not belong to the given collection")]
+    CollectionNotFound,
+
+    /// Token close account failed
+    #[error("Token close account failed")]
+    TokenCloseFailed,
+
+    /// A master edition cannot be burned while prints of it still exist
+    #[error("A master edition cannot be burned while prints of it still exist")]
+    MasterEditionHasPrints,
+
+    /// No free edition in the given edition marker, reload the master edition supply and retry
+    #[error(
+        "No free edition in the given edition marker, reload the master edition supply and retry"
+    )]
+    NoFreeEditionInEditionMarker,
+
+    /// Uses must start with remaining equal to total, and single uses must have a total of one
+    #[error(
+        "Uses must start with remaining equal to total, and single uses must have a total of one"
+    )]
+    InvalidUseConfiguration,
+
+    /// This metadata has no uses
+    #[error("This metadata has no uses")]
+    Unusable,
+
+    /// Not enough uses remaining
+    #[error("Not enough uses remaining")]
+    NotEnoughUses,
+
+    /// This use authority has not been allowed that many uses
+    #[error("This use authority has not been allowed that many uses")]
+    UseAuthorityAllowanceExceeded,
+
+    /// A use authority record already exists for this use authority, revoke it first
+    #[error("A use authority record already exists for this use authority, revoke it first")]
+    UseAuthorityRecordAlreadyExists,
+
+    /// Token approve failed
+    #[error("Token approve failed")]
+    TokenApproveFailed,
+
+    /// Token revoke failed
+    #[error("Token revoke failed")]
+    TokenRevokeFailed,
+
+    /// The signer is not the pending update authority
+    #[error("The signer is not the pending update authority")]
+    PendingUpdateAuthorityMismatch,
+
+    /// The update authority has changed since this transfer was proposed
+    #[error("The update authority has changed since this transfer was proposed")]
+    StalePendingUpdateAuthority,
+
+    /// Changing royalties or a verified creator's share requires that creator's signature
+    #[error("Changing royalties or a verified creator's share requires that creator's signature")]
+    CreatorConsentRequired,
+
+    /// The signer is not the delegate of the token account
+    #[error("The signer is not the delegate of the token account")]
+    InvalidDelegate,
+
+    /// The mint's freeze authority is not its edition
+    #[error("The mint's freeze authority is not its edition")]
+    InvalidFreezeAuthority,
+
+    /// Token freeze or thaw failed
+    #[error("Token freeze or thaw failed")]
+    TokenFreezeThawFailed,
+
+    /// Too many attributes
+    #[error("Too many attributes")]
+    TooManyAttributes,
+
+    /// Attribute trait type or value too long
+    #[error("Attribute trait type or value too long")]
+    AttributeTooLong,
+
+    /// The use authority was approved by a previous owner of the token
+    #[error("The use authority was approved by a previous owner of the token")]
+    UseAuthorityApprovedByPreviousOwner,
+
+    /// Use authority records for this mint have to be revoked first
+    #[error("Use authority records for this mint have to be revoked first")]
+    UseAuthorityRecordsOutstanding,
+
+    /// Reservation lists still hold reservations that were not redeemed
+    #[error("Reservation lists still hold reservations that were not redeemed")]
+    ReservationsOutstanding,
+
+    /// The account is not the one that funded the reservation list
+    #[error("The account is not the one that funded the reservation list")]
+    ReservationListFunderMismatch,
 }
 
 impl PrintProgramError for MetadataError {
@@ -1,7 +1,10 @@
 use {
     crate::{
         deprecated_instruction::{MintPrintingTokensViaTokenArgs, SetReservationListArgs},
-        state::{Creator, Data, EDITION, EDITION_MARKER_BIT_SIZE, PREFIX},
+        state::{
+            Attribute, Creator, Data, Uses, ATTRIBUTES, BURN, EDITION, EDITION_MARKER_BIT_SIZE,
+            PENDING_UPDATE_AUTHORITY, PREFIX, USER,
+        },
     },
     borsh::{BorshDeserialize, BorshSerialize},
     solana_program::{
@@ -20,6 +23,17 @@ pub struct UpdateMetadataAccountArgs {
     pub primary_sale_happened: Option<bool>,
 }
 
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// Args for update call that can propose the new update authority instead of switching to it
+pub struct UpdateMetadataAccountArgsV2 {
+    pub data: Option<Data>,
+    pub update_authority: Option<Pubkey>,
+    pub primary_sale_happened: Option<bool>,
+    /// Record update_authority as pending, it then has to sign AcceptUpdateAuthority.
+    pub propose_update_authority: bool,
+}
+
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 /// Args for create call
@@ -30,6 +44,43 @@ pub struct CreateMetadataAccountArgs {
     pub is_mutable: bool,
 }
 
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// Args for create call that can also set uses
+pub struct CreateMetadataAccountArgsV2 {
+    /// Note that unique metadatas are disabled for now.
+    pub data: Data,
+    /// Whether you want your metadata to be updateable in the future.
+    pub is_mutable: bool,
+    /// Consumable uses, remaining must equal total. Cannot be changed afterwards.
+    pub uses: Option<Uses>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct UtilizeArgs {
+    pub number_of_uses: u64,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct ApproveUseAuthorityArgs {
+    pub number_of_uses: u64,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SetAttributesArgs {
+    /// Added, or replacing the value of an existing attribute with the same trait type
+    pub attributes: Vec<Attribute>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct RemoveAttributesArgs {
+    pub trait_types: Vec<String>,
+}
+
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct CreateMasterEditionArgs {
@@ -59,6 +110,7 @@ pub enum MetadataInstruction {
     /// Update a Metadata
     ///   0. `[writable]` Metadata account
     ///   1. `[signer]` Update authority key
+    ///   2..n `[signer]` Verified creators consenting to a royalty or share change, when the Metadata requires creator consent
     UpdateMetadataAccount(UpdateMetadataAccountArgs),
 
     /// Register a Metadata as a Master Edition V1, which means Editions can be minted.
@@ -73,7 +125,7 @@ pub enum MetadataInstruction {
     ///   4. `[signer]` Current Update authority key
     ///   5. `[signer]`   Printing mint authority - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY.
     ///   6. `[signer]` Mint authority on the metadata's mint - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY
-    ///   7. `[]` Metadata account
+    ///   7. `[writable]` Metadata account, marked NonFungible (read-only is accepted and leaves the token standard alone)
     ///   8. `[signer]` payer
     ///   9. `[]` Token program
     ///   10. `[]` System program
@@ -183,7 +235,7 @@ pub enum MetadataInstruction {
     ///   2. `[signer]` Update authority
     ///   3. `[signer]` Mint authority on the metadata's mint - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY
     ///   4. `[signer]` payer
-    ///   5. `[]` Metadata account
+    ///   5. `[writable]` Metadata account, marked NonFungible (read-only is accepted and leaves the token standard alone)
     ///   6. `[]` Token program
     ///   7. `[]` System program
     ///   8. `[]` Rent info
@@ -243,6 +295,229 @@ pub enum MetadataInstruction {
     /// so that it can be found using offset searches by the RPC to make client lookups cheaper.
     ///   0. `[writable]` Metadata account
     PuffMetadata,
+
+    /// Mark a Metadata as a verified member of a collection. The collection is identified by the mint
+    /// of an NFT with a Master Edition V2, and its update authority must sign, as must the Metadata's.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority of the Metadata
+    ///   2. `[signer]` Update authority of the collection NFT
+    ///   3. `[]` Mint of the collection NFT
+    ///   4. `[]` Metadata of the collection NFT (pda of ['metadata', program id, collection mint id])
+    ///   5. `[]` Master Edition V2 of the collection NFT (pda of ['metadata', program id, collection mint id, 'edition'])
+    VerifyCollection,
+
+    /// Withdraw the collection's verification of a Metadata, leaving the collection on it unverified.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority of the collection NFT
+    ///   2. `[]` Mint of the collection NFT
+    ///   3. `[]` Metadata of the collection NFT (pda of ['metadata', program id, collection mint id])
+    ///   4. `[]` Master Edition V2 of the collection NFT (pda of ['metadata', program id, collection mint id, 'edition'])
+    UnverifyCollection,
+
+    /// Burn an NFT, closing its token account, Metadata, Edition or Master Edition V2, attributes and pending update
+    /// authority and returning their rent to the owner. Every use authority record has to be revoked first.
+    /// A Master Edition V2 can only be burned once none of its prints exist. Burning a print of a Master Edition V2 clears
+    /// its bit in the edition marker and decrements the supply, so the edition number can be printed again. Burning a
+    /// print of a Master Edition V1 leaves the master edition as it is.
+    ///   0. `[writable]` Metadata (pda of ['metadata', program id, mint id])
+    ///   1. `[writable, signer]` Owner of the token account, receives the reclaimed rent
+    ///   2. `[writable]` Mint of the NFT
+    ///   3. `[writable]` Token account holding the NFT
+    ///   4. `[writable]` Master Edition V2 or Edition of the NFT (pda of ['metadata', program id, mint id, 'edition'])
+    ///   5. `[]` Token program
+    ///   6. `[writable]` Metadata attributes, closed if it exists (pda of ['metadata', program id, metadata key, 'attributes'])
+    ///   7. `[writable]` Pending update authority, closed if it exists (pda of ['metadata', program id, mint id, 'pending_update_authority'])
+    ///   Only when burning a print:
+    ///   8. `[writable]` Master Edition the print was made from (pda of ['metadata', program id, master mint id, 'edition'])
+    ///   9. `[]` Mint of the master edition
+    ///   10. `[writable]` Edition marker pda (pda of ['metadata', program id, master mint id, 'edition', edition_number])
+    ///   where edition_number is NOT the edition number of the print but floor(edition/EDITION_MARKER_BIT_SIZE).
+    ///   Unused for prints of a Master Edition V1.
+    BurnNft,
+
+    /// Same as MintNewEditionFromMasterEditionViaToken, except the program assigns the edition number: the lowest free one
+    /// above the Master Edition V2's current supply. Pass the edition marker covering supply + 1; if a concurrent print moved
+    /// the supply past it, or filled it, this fails with NoFreeEditionInEditionMarker and should be retried with a fresh supply.
+    ///   0. `[writable]` New Metadata key (pda of ['metadata', program id, mint id])
+    ///   1. `[writable]` New Edition (pda of ['metadata', program id, mint id, 'edition'])
+    ///   2. `[writable]` Master Record Edition V2 (pda of ['metadata', program id, master metadata mint id, 'edition'])
+    ///   3. `[writable]` Mint of new token - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY
+    ///   4. `[writable]` Edition pda to mark creation (pda of ['metadata', program id, master metadata mint id, 'edition', edition_number])
+    ///   where edition_number is floor((supply + 1)/EDITION_MARKER_BIT_SIZE).
+    ///   5. `[signer]` Mint authority of new mint
+    ///   6. `[signer]` payer
+    ///   7. `[signer]` owner of token account containing master token (#8)
+    ///   8. `[]` token account containing token from master metadata mint
+    ///   9. `[]` Update authority info for new metadata
+    ///   10. `[]` Master record metadata account
+    ///   11. `[]` Token program
+    ///   12. `[]` System program
+    ///   13. `[]` Rent info
+    MintNextEditionFromMasterEditionViaToken,
+
+    /// Same as CreateMetadataAccount, but can also give the Metadata consumable uses.
+    ///   0. `[writable]`  Metadata key (pda of ['metadata', program id, mint id])
+    ///   1. `[]` Mint of token asset
+    ///   2. `[signer]` Mint authority
+    ///   3. `[signer]` payer
+    ///   4. `[]` update authority info
+    ///   5. `[]` System program
+    ///   6. `[]` Rent info
+    CreateMetadataAccountV2(CreateMetadataAccountArgsV2),
+
+    /// Utilize some of a Metadata's remaining uses, signed by the token owner or an approved use authority.
+    /// A use authority can only utilize while the owner that approved it still holds the token.
+    /// When the use method is Burn and the last use is utilized, the token is burned.
+    ///   0. `[writable]` Metadata (pda of ['metadata', program id, mint id])
+    ///   1. `[writable]` Token account holding the NFT
+    ///   2. `[writable]` Mint of the NFT
+    ///   3. `[signer]` Token owner or use authority
+    ///   4. `[]` Owner of the token account
+    ///   5. `[]` Token program
+    ///   Only when a use authority signs:
+    ///   6. `[writable]` Use authority record (pda of ['metadata', program id, mint id, 'user', use authority])
+    ///   7. `[]` Program burner (pda of ['metadata', program id, mint id, 'user', use authority, 'burn']), the token account's delegate for Burn uses
+    Utilize(UtilizeArgs),
+
+    /// Approve a use authority to utilize up to number_of_uses of a Metadata's uses. When the use method is Burn,
+    /// the use authority's burner pda is also made the delegate of the owner's token account so it can burn the token.
+    /// A token account only has one delegate, so this replaces any earlier use authority's burn delegation.
+    /// A record left behind by a previous owner of the token is overwritten.
+    ///   0. `[writable]` Use authority record (pda of ['metadata', program id, mint id, 'user', use authority])
+    ///   1. `[signer]` Owner of the token account
+    ///   2. `[signer]` payer
+    ///   3. `[]` Use authority
+    ///   4. `[writable]` Owner's token account holding the NFT
+    ///   5. `[writable]` Metadata (pda of ['metadata', program id, mint id])
+    ///   6. `[]` Mint of the NFT
+    ///   7. `[]` Program burner (pda of ['metadata', program id, mint id, 'user', use authority, 'burn'])
+    ///   8. `[]` Token program
+    ///   9. `[]` System program
+    ///   10. `[]` Rent info
+    ApproveUseAuthority(ApproveUseAuthorityArgs),
+
+    /// Revoke a use authority, closing its record and returning the rent to the owner. For Burn uses the delegate
+    /// on the owner's token account is revoked as well, if it is still this use authority's burner.
+    ///   0. `[writable]` Use authority record (pda of ['metadata', program id, mint id, 'user', use authority])
+    ///   1. `[writable, signer]` Owner of the token account
+    ///   2. `[]` Use authority
+    ///   3. `[writable]` Owner's token account holding the NFT
+    ///   4. `[writable]` Metadata (pda of ['metadata', program id, mint id])
+    ///   5. `[]` Mint of the NFT
+    ///   6. `[]` Token program
+    ///   7. `[]` Program burner (pda of ['metadata', program id, mint id, 'user', use authority, 'burn'])
+    RevokeUseAuthority,
+
+    /// Propose a new update authority for a Metadata. Nothing changes until the new update authority signs
+    /// AcceptUpdateAuthority, and the current update authority can call CancelPendingUpdateAuthority until then.
+    /// Proposing again replaces the previous proposal. UpdateMetadataAccount still transfers immediately, while
+    /// UpdateMetadataAccountV2 can propose the same way.
+    ///   0. `[]` Metadata account
+    ///   1. `[writable]` Pending update authority (pda of ['metadata', program id, mint id, 'pending_update_authority'])
+    ///   2. `[signer]` Current update authority
+    ///   3. `[]` Proposed new update authority
+    ///   4. `[signer]` payer
+    ///   5. `[]` System program
+    ///   6. `[]` Rent info
+    SetPendingUpdateAuthority,
+
+    /// Accept a pending update authority transfer, closing the pending account.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[writable]` Pending update authority (pda of ['metadata', program id, mint id, 'pending_update_authority'])
+    ///   2. `[signer]` New update authority
+    ///   3. `[writable]` Update authority that proposed the transfer, receives the reclaimed rent
+    AcceptUpdateAuthority,
+
+    /// Cancel a pending update authority transfer, closing the pending account.
+    ///   0. `[]` Metadata account
+    ///   1. `[writable]` Pending update authority (pda of ['metadata', program id, mint id, 'pending_update_authority'])
+    ///   2. `[writable, signer]` Current update authority, receives the reclaimed rent
+    CancelPendingUpdateAuthority,
+
+    /// Opt a Metadata into requiring creator consent: from then on, UpdateMetadataAccount can only change
+    /// seller_fee_basis_points or a verified creator's share (including removing them) with the signatures of
+    /// the verified creators affected. This cannot be turned off.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority key
+    RequireCreatorConsent,
+
+    /// Remove the signing creator's own verification from a Metadata.
+    ///   0. `[writable]` Metadata (pda of ['metadata', program id, mint id])
+    ///   1. `[signer]` Creator
+    RemoveCreatorVerification,
+
+    /// Freeze the owner's token account holding an NFT, signed by the SPL Token delegate of that account.
+    /// The edition pda signs as the mint's freeze authority. The token account must hold exactly one token.
+    ///   0. `[signer]` Delegate of the token account
+    ///   1. `[writable]` Token account holding the NFT
+    ///   2. `[]` Master Edition V2 or Edition of the NFT (pda of ['metadata', program id, mint id, 'edition'])
+    ///   3. `[]` Mint of the NFT
+    ///   4. `[]` Token program
+    FreezeDelegatedAccount,
+
+    /// Thaw a token account frozen with FreezeDelegatedAccount, signed by the same delegate.
+    ///   0. `[signer]` Delegate of the token account
+    ///   1. `[writable]` Token account holding the NFT
+    ///   2. `[]` Master Edition V2 or Edition of the NFT (pda of ['metadata', program id, mint id, 'edition'])
+    ///   3. `[]` Mint of the NFT
+    ///   4. `[]` Token program
+    ThawDelegatedAccount,
+
+    /// Add or replace on-chain attributes of a mutable Metadata, creating its attributes account on first use.
+    /// The account is not resized as attributes come and go, it is always allocated at the full
+    /// MAX_METADATA_ATTRIBUTES_LEN (about 3.3 KB, enough for MAX_ATTRIBUTES pairs), and the payer funds its rent.
+    ///   0. `[writable]` Metadata attributes (pda of ['metadata', program id, metadata key, 'attributes'])
+    ///   1. `[]` Metadata account
+    ///   2. `[signer]` Update authority key
+    ///   3. `[writable, signer]` payer
+    ///   4. `[]` System program
+    ///   5. `[]` Rent info
+    SetAttributes(SetAttributesArgs),
+
+    /// Remove on-chain attributes of a mutable Metadata by trait type, closing its attributes account
+    /// once empty. Reclaimed rent goes to the payer, which has to sign.
+    ///   0. `[writable]` Metadata attributes (pda of ['metadata', program id, metadata key, 'attributes'])
+    ///   1. `[]` Metadata account
+    ///   2. `[signer]` Update authority key
+    ///   3. `[writable, signer]` payer
+    RemoveAttributes(RemoveAttributesArgs),
+
+    /// Converts a Master Edition V1 to a Master Edition V2 on behalf of the owner of the master edition token,
+    /// who can burn their own printing and one time authorization tokens and close the edition's reservation lists
+    /// on the way. Only succeeds once no printing or one time authorization tokens remain in circulation.
+    ///   0. `[writable]` Master Record Edition V1 (pda of ['metadata', program id, master metadata mint id, 'edition'])
+    ///   1. `[]` Master record metadata account
+    ///   2. `[writable, signer]` Owner of the master edition token, receives the rent of closed token accounts
+    ///        and of closed reservation lists that never recorded a funder
+    ///   3. `[]` Token account holding the master edition token
+    ///   4. `[writable]` One time authorization mint
+    ///   5. `[writable]` Printing mint
+    ///   6. `[]` Token program
+    ///   7..n `[writable]` The owner's printing or one time authorization token accounts, to be burned and closed,
+    ///        and Reservation Lists V1 or V2 of this master edition with no reservations left to redeem, each followed by
+    ///        the `[writable]` account that funded it when the list recorded one, which gets its rent back as it is closed.
+    MigrateMasterEditionV1ToV2,
+
+    /// Work out the token standard of a Metadata from its mint and edition, for metadata created before
+    /// token standards were recorded or whose master edition was created with a read-only metadata account.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority
+    ///   2. `[]` Mint of the metadata
+    ///   3. `[]` (Optional) Master Edition or Edition of the mint (pda of ['metadata', program id, mint id, 'edition'])
+    SetTokenStandard,
+
+    /// Same as UpdateMetadataAccount, but with propose_update_authority set a new update authority is recorded
+    /// as pending, same as SetPendingUpdateAuthority, and only takes over once it signs AcceptUpdateAuthority.
+    ///   0. `[writable]` Metadata account
+    ///   1. `[signer]` Update authority key
+    ///   Only when proposing a new update authority:
+    ///   2. `[writable]` Pending update authority (pda of ['metadata', program id, mint id, 'pending_update_authority'])
+    ///   3. `[signer]` payer
+    ///   4. `[]` System program
+    ///   5. `[]` Rent info
+    ///   Followed by:
+    ///   n..m `[signer]` Verified creators consenting to a royalty or share change, when the Metadata requires creator consent
+    UpdateMetadataAccountV2(UpdateMetadataAccountArgsV2),
 }
 
 /// Creates an CreateMetadataAccounts instruction
@@ -313,6 +588,41 @@ pub fn update_metadata_accounts(
     }
 }
 
+/// update metadata account v2 instruction that proposes new_update_authority, which then has to accept it
+#[allow(clippy::too_many_arguments)]
+pub fn update_metadata_accounts_with_pending_update_authority(
+    program_id: Pubkey,
+    metadata_account: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+    new_update_authority: Pubkey,
+    payer: Pubkey,
+    data: Option<Data>,
+    primary_sale_happened: Option<bool>,
+) -> Instruction {
+    let (pending_update_authority, _) = find_pending_update_authority_account(&program_id, &mint);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_account, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new(pending_update_authority, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::UpdateMetadataAccountV2(UpdateMetadataAccountArgsV2 {
+            data,
+            update_authority: Some(new_update_authority),
+            primary_sale_happened,
+            propose_update_authority: true,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
 /// puff metadata account instruction
 pub fn puff_metadata_account(program_id: Pubkey, metadata_account: Pubkey) -> Instruction {
     Instruction {
@@ -361,7 +671,7 @@ pub fn create_master_edition(
         AccountMeta::new_readonly(update_authority, true),
         AccountMeta::new_readonly(mint_authority, true),
         AccountMeta::new(payer, true),
-        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new(metadata, false),
         AccountMeta::new_readonly(spl_token::id(), false),
         AccountMeta::new_readonly(solana_program::system_program::id(), false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
@@ -525,3 +835,620 @@ pub fn mint_edition_from_master_edition_via_vault_proxy(
         .unwrap(),
     }
 }
+
+/// creates a verify_collection instruction
+#[allow(clippy::too_many_arguments)]
+pub fn verify_collection(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    collection_authority: Pubkey,
+    collection_mint: Pubkey,
+    collection_metadata: Pubkey,
+    collection_master_edition: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new_readonly(collection_authority, true),
+            AccountMeta::new_readonly(collection_mint, false),
+            AccountMeta::new_readonly(collection_metadata, false),
+            AccountMeta::new_readonly(collection_master_edition, false),
+        ],
+        data: MetadataInstruction::VerifyCollection.try_to_vec().unwrap(),
+    }
+}
+
+/// creates an unverify_collection instruction
+pub fn unverify_collection(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    collection_authority: Pubkey,
+    collection_mint: Pubkey,
+    collection_metadata: Pubkey,
+    collection_master_edition: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(collection_authority, true),
+            AccountMeta::new_readonly(collection_mint, false),
+            AccountMeta::new_readonly(collection_metadata, false),
+            AccountMeta::new_readonly(collection_master_edition, false),
+        ],
+        data: MetadataInstruction::UnverifyCollection
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a burn_nft instruction, pass the print's master edition, master mint and edition number when burning a print
+#[allow(clippy::too_many_arguments)]
+pub fn burn_nft(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    owner: Pubkey,
+    mint: Pubkey,
+    token_account: Pubkey,
+    edition: Pubkey,
+    print: Option<(Pubkey, Pubkey, u64)>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new(owner, true),
+        AccountMeta::new(mint, false),
+        AccountMeta::new(token_account, false),
+        AccountMeta::new(edition, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(find_metadata_attributes_account(&program_id, &metadata).0, false),
+        AccountMeta::new(find_pending_update_authority_account(&program_id, &mint).0, false),
+    ];
+
+    if let Some((master_edition, master_mint, edition_number)) = print {
+        let as_string = (edition_number / EDITION_MARKER_BIT_SIZE).to_string();
+        let (edition_mark_pda, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                master_mint.as_ref(),
+                EDITION.as_bytes(),
+                as_string.as_bytes(),
+            ],
+            &program_id,
+        );
+        accounts.push(AccountMeta::new(master_edition, false));
+        accounts.push(AccountMeta::new_readonly(master_mint, false));
+        accounts.push(AccountMeta::new(edition_mark_pda, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::BurnNft.try_to_vec().unwrap(),
+    }
+}
+
+/// creates a mint_next_edition_from_master_edition_via_token instruction, where master_edition_supply
+/// is the supply last read off the master edition and picks the edition marker to pass
+#[allow(clippy::too_many_arguments)]
+pub fn mint_next_edition_from_master_edition_via_token(
+    program_id: Pubkey,
+    new_metadata: Pubkey,
+    new_edition: Pubkey,
+    master_edition: Pubkey,
+    new_mint: Pubkey,
+    new_mint_authority: Pubkey,
+    payer: Pubkey,
+    token_account_owner: Pubkey,
+    token_account: Pubkey,
+    new_metadata_update_authority: Pubkey,
+    metadata: Pubkey,
+    metadata_mint: Pubkey,
+    master_edition_supply: u64,
+) -> Instruction {
+    let mut instruction = mint_new_edition_from_master_edition_via_token(
+        program_id,
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint,
+        new_mint_authority,
+        payer,
+        token_account_owner,
+        token_account,
+        new_metadata_update_authority,
+        metadata,
+        metadata_mint,
+        master_edition_supply.checked_add(1).unwrap(),
+    );
+    instruction.data = MetadataInstruction::MintNextEditionFromMasterEditionViaToken
+        .try_to_vec()
+        .unwrap();
+    instruction
+}
+
+/// Finds the use authority record pda for the given mint and use authority
+pub fn find_use_authority_account(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    use_authority: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint.as_ref(),
+            USER.as_bytes(),
+            use_authority.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Finds the pda the program burns Burn uses with when the given use authority utilizes the last one
+pub fn find_program_as_burner_account(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    use_authority: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint.as_ref(),
+            USER.as_bytes(),
+            use_authority.as_ref(),
+            BURN.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Creates an CreateMetadataAccountV2 instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_metadata_accounts_v2(
+    program_id: Pubkey,
+    metadata_account: Pubkey,
+    mint: Pubkey,
+    mint_authority: Pubkey,
+    payer: Pubkey,
+    update_authority: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    creators: Option<Vec<Creator>>,
+    seller_fee_basis_points: u16,
+    update_authority_is_signer: bool,
+    is_mutable: bool,
+    uses: Option<Uses>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_account, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(mint_authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(update_authority, update_authority_is_signer),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::CreateMetadataAccountV2(CreateMetadataAccountArgsV2 {
+            data: Data {
+                name,
+                symbol,
+                uri,
+                seller_fee_basis_points,
+                creators,
+            },
+            is_mutable,
+            uses,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// creates a utilize instruction, the use authority record and burner are passed when use_authority isn't the owner
+#[allow(clippy::too_many_arguments)]
+pub fn utilize(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    token_account: Pubkey,
+    mint: Pubkey,
+    use_authority: Pubkey,
+    owner: Pubkey,
+    number_of_uses: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new(token_account, false),
+        AccountMeta::new(mint, false),
+        AccountMeta::new_readonly(use_authority, true),
+        AccountMeta::new_readonly(owner, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    if use_authority != owner {
+        let (use_authority_record, _) =
+            find_use_authority_account(&program_id, &mint, &use_authority);
+        let (burner, _) = find_program_as_burner_account(&program_id, &mint, &use_authority);
+        accounts.push(AccountMeta::new(use_authority_record, false));
+        accounts.push(AccountMeta::new_readonly(burner, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::Utilize(UtilizeArgs { number_of_uses })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates an approve_use_authority instruction
+#[allow(clippy::too_many_arguments)]
+pub fn approve_use_authority(
+    program_id: Pubkey,
+    use_authority: Pubkey,
+    owner: Pubkey,
+    payer: Pubkey,
+    owner_token_account: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    number_of_uses: u64,
+) -> Instruction {
+    let (use_authority_record, _) = find_use_authority_account(&program_id, &mint, &use_authority);
+    let (burner, _) = find_program_as_burner_account(&program_id, &mint, &use_authority);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(use_authority_record, false),
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(use_authority, false),
+            AccountMeta::new(owner_token_account, false),
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(burner, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::ApproveUseAuthority(ApproveUseAuthorityArgs { number_of_uses })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a revoke_use_authority instruction
+pub fn revoke_use_authority(
+    program_id: Pubkey,
+    use_authority: Pubkey,
+    owner: Pubkey,
+    owner_token_account: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    let (use_authority_record, _) = find_use_authority_account(&program_id, &mint, &use_authority);
+    let (burner, _) = find_program_as_burner_account(&program_id, &mint, &use_authority);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(use_authority_record, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(use_authority, false),
+            AccountMeta::new(owner_token_account, false),
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(burner, false),
+        ],
+        data: MetadataInstruction::RevokeUseAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Finds the pending update authority pda for the given mint
+pub fn find_pending_update_authority_account(program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint.as_ref(),
+            PENDING_UPDATE_AUTHORITY.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// creates a set_pending_update_authority instruction
+pub fn set_pending_update_authority(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+    new_update_authority: Pubkey,
+    payer: Pubkey,
+) -> Instruction {
+    let (pending_update_authority, _) = find_pending_update_authority_account(&program_id, &mint);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new(pending_update_authority, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new_readonly(new_update_authority, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::SetPendingUpdateAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates an accept_update_authority instruction, proposed_by is the update authority that proposed the transfer
+pub fn accept_update_authority(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    new_update_authority: Pubkey,
+    proposed_by: Pubkey,
+) -> Instruction {
+    let (pending_update_authority, _) = find_pending_update_authority_account(&program_id, &mint);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new(pending_update_authority, false),
+            AccountMeta::new_readonly(new_update_authority, true),
+            AccountMeta::new(proposed_by, false),
+        ],
+        data: MetadataInstruction::AcceptUpdateAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a cancel_pending_update_authority instruction
+pub fn cancel_pending_update_authority(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    update_authority: Pubkey,
+) -> Instruction {
+    let (pending_update_authority, _) = find_pending_update_authority_account(&program_id, &mint);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new(pending_update_authority, false),
+            AccountMeta::new(update_authority, true),
+        ],
+        data: MetadataInstruction::CancelPendingUpdateAuthority
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a require_creator_consent instruction
+pub fn require_creator_consent(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+        ],
+        data: MetadataInstruction::RequireCreatorConsent
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a remove_creator_verification instruction
+pub fn remove_creator_verification(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    creator: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(creator, true),
+        ],
+        data: MetadataInstruction::RemoveCreatorVerification
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a freeze_delegated_account instruction
+pub fn freeze_delegated_account(
+    program_id: Pubkey,
+    delegate: Pubkey,
+    token_account: Pubkey,
+    edition: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(delegate, true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(edition, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: MetadataInstruction::FreezeDelegatedAccount
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a thaw_delegated_account instruction
+pub fn thaw_delegated_account(
+    program_id: Pubkey,
+    delegate: Pubkey,
+    token_account: Pubkey,
+    edition: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(delegate, true),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(edition, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: MetadataInstruction::ThawDelegatedAccount
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Finds the metadata attributes pda for the given metadata
+pub fn find_metadata_attributes_account(program_id: &Pubkey, metadata: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.as_ref(),
+            ATTRIBUTES.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// creates a set_attributes instruction
+pub fn set_attributes(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    attributes: Vec<Attribute>,
+) -> Instruction {
+    let (metadata_attributes, _) = find_metadata_attributes_account(&program_id, &metadata);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_attributes, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: MetadataInstruction::SetAttributes(SetAttributesArgs { attributes })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a remove_attributes instruction
+pub fn remove_attributes(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    payer: Pubkey,
+    trait_types: Vec<String>,
+) -> Instruction {
+    let (metadata_attributes, _) = find_metadata_attributes_account(&program_id, &metadata);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata_attributes, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new(payer, true),
+        ],
+        data: MetadataInstruction::RemoveAttributes(RemoveAttributesArgs { trait_types })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a migrate_master_edition_v1_to_v2 instruction, where owned_token_accounts are the owner's printing
+/// and one time authorization token accounts, and reservation_lists pairs each list with its recorded funder
+#[allow(clippy::too_many_arguments)]
+pub fn migrate_master_edition_v1_to_v2(
+    program_id: Pubkey,
+    master_edition: Pubkey,
+    metadata: Pubkey,
+    owner: Pubkey,
+    token_account: Pubkey,
+    one_time_printing_auth_mint: Pubkey,
+    printing_mint: Pubkey,
+    owned_token_accounts: Vec<Pubkey>,
+    reservation_lists: Vec<(Pubkey, Option<Pubkey>)>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(master_edition, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new(owner, true),
+        AccountMeta::new_readonly(token_account, false),
+        AccountMeta::new(one_time_printing_auth_mint, false),
+        AccountMeta::new(printing_mint, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    for account in owned_token_accounts {
+        accounts.push(AccountMeta::new(account, false));
+    }
+    for (reservation_list, funder) in reservation_lists {
+        accounts.push(AccountMeta::new(reservation_list, false));
+        if let Some(funder) = funder {
+            accounts.push(AccountMeta::new(funder, false));
+        }
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::MigrateMasterEditionV1ToV2
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a set_token_standard instruction, pass the edition if the mint has one
+pub fn set_token_standard(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    update_authority: Pubkey,
+    mint: Pubkey,
+    edition: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(metadata, false),
+        AccountMeta::new_readonly(update_authority, true),
+        AccountMeta::new_readonly(mint, false),
+    ];
+    if let Some(edition) = edition {
+        accounts.push(AccountMeta::new_readonly(edition, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::SetTokenStandard.try_to_vec().unwrap(),
+    }
+}
@@ -10,18 +10,26 @@ use {
         error::MetadataError,
         instruction::MetadataInstruction,
         state::{
-            Data, Key, MasterEditionV1, MasterEditionV2, Metadata, EDITION, MAX_MASTER_EDITION_LEN,
-            PREFIX,
+            get_reservation_list, Attribute, Collection, Data, Edition, EditionMarker, Key,
+            MasterEditionV1, MasterEditionV2, Metadata, MetadataAttributes, PendingUpdateAuthority,
+            TokenStandard, UseAuthorityRecord, UseMethod, Uses, ATTRIBUTES, BURN, EDITION,
+            EDITION_MARKER_BIT_SIZE, MAX_MASTER_EDITION_LEN, MAX_METADATA_ATTRIBUTES_LEN,
+            PENDING_UPDATE_AUTHORITY, PENDING_UPDATE_AUTHORITY_SIZE, PREFIX, USER,
+            USE_AUTHORITY_RECORD_SIZE,
         },
         utils::{
-            assert_data_valid, assert_derivation, assert_initialized,
+            assert_attributes_valid, assert_collection_authority_is_correct,
+            assert_creator_consent, assert_data_valid, assert_derivation, assert_initialized,
             assert_mint_authority_matches_mint, assert_owned_by, assert_signer,
             assert_token_program_matches_package, assert_update_authority_is_correct,
-            create_or_allocate_account_raw, get_owner_from_token_account,
+            close_program_account, create_or_allocate_account_raw, find_next_edition,
+            get_mint_freeze_authority, get_owner_from_token_account,
             process_create_metadata_accounts_logic,
             process_mint_new_edition_from_master_edition_via_token_logic, puff_out_data_fields,
+            spl_token_approve, spl_token_burn, spl_token_close_account, spl_token_revoke,
             transfer_mint_authority, CreateMetadataAccountsLogicArgs,
-            MintNewEditionFromMasterEditionViaTokenLogicArgs,
+            MintNewEditionFromMasterEditionViaTokenLogicArgs, TokenApproveParams, TokenBurnParams,
+            TokenCloseAccountParams, TokenRevokeParams,
         },
     },
     arrayref::array_ref,
@@ -30,7 +38,9 @@ use {
         account_info::{next_account_info, AccountInfo},
         entrypoint::ProgramResult,
         msg,
+        program::invoke_signed,
         program_error::ProgramError,
+        program_option::COption,
         pubkey::Pubkey,
     },
     spl_token::state::{Account, Mint},
@@ -52,6 +62,7 @@ pub fn process_instruction<'a>(
                 args.data,
                 false,
                 args.is_mutable,
+                None,
             )
         }
         MetadataInstruction::UpdateMetadataAccount(args) => {
@@ -62,6 +73,7 @@ pub fn process_instruction<'a>(
                 args.data,
                 args.update_authority,
                 args.primary_sale_happened,
+                false,
             )
         }
         MetadataInstruction::DeprecatedCreateMasterEdition(args) => {
@@ -114,7 +126,7 @@ pub fn process_instruction<'a>(
             process_mint_new_edition_from_master_edition_via_token(
                 program_id,
                 accounts,
-                args.edition,
+                Some(args.edition),
                 false,
             )
         }
@@ -134,6 +146,102 @@ pub fn process_instruction<'a>(
             msg!("Instruction: Puff Metadata");
             process_puff_metadata_account(program_id, accounts)
         }
+        MetadataInstruction::VerifyCollection => {
+            msg!("Instruction: Verify Collection");
+            process_verify_collection(program_id, accounts)
+        }
+        MetadataInstruction::UnverifyCollection => {
+            msg!("Instruction: Unverify Collection");
+            process_unverify_collection(program_id, accounts)
+        }
+        MetadataInstruction::BurnNft => {
+            msg!("Instruction: Burn NFT");
+            process_burn_nft(program_id, accounts)
+        }
+        MetadataInstruction::MintNextEditionFromMasterEditionViaToken => {
+            msg!("Instruction: Mint Next Edition from Master Edition Via Token");
+            process_mint_new_edition_from_master_edition_via_token(
+                program_id, accounts, None, false,
+            )
+        }
+        MetadataInstruction::CreateMetadataAccountV2(args) => {
+            msg!("Instruction: Create Metadata Accounts V2");
+            process_create_metadata_accounts(
+                program_id,
+                accounts,
+                args.data,
+                false,
+                args.is_mutable,
+                args.uses,
+            )
+        }
+        MetadataInstruction::Utilize(args) => {
+            msg!("Instruction: Utilize");
+            process_utilize(program_id, accounts, args.number_of_uses)
+        }
+        MetadataInstruction::ApproveUseAuthority(args) => {
+            msg!("Instruction: Approve Use Authority");
+            process_approve_use_authority(program_id, accounts, args.number_of_uses)
+        }
+        MetadataInstruction::RevokeUseAuthority => {
+            msg!("Instruction: Revoke Use Authority");
+            process_revoke_use_authority(program_id, accounts)
+        }
+        MetadataInstruction::SetPendingUpdateAuthority => {
+            msg!("Instruction: Set Pending Update Authority");
+            process_set_pending_update_authority(program_id, accounts)
+        }
+        MetadataInstruction::AcceptUpdateAuthority => {
+            msg!("Instruction: Accept Update Authority");
+            process_accept_update_authority(program_id, accounts)
+        }
+        MetadataInstruction::CancelPendingUpdateAuthority => {
+            msg!("Instruction: Cancel Pending Update Authority");
+            process_cancel_pending_update_authority(program_id, accounts)
+        }
+        MetadataInstruction::RequireCreatorConsent => {
+            msg!("Instruction: Require Creator Consent");
+            process_require_creator_consent(program_id, accounts)
+        }
+        MetadataInstruction::RemoveCreatorVerification => {
+            msg!("Instruction: Remove Creator Verification");
+            process_remove_creator_verification(program_id, accounts)
+        }
+        MetadataInstruction::FreezeDelegatedAccount => {
+            msg!("Instruction: Freeze Delegated Account");
+            process_freeze_delegated_account(program_id, accounts)
+        }
+        MetadataInstruction::ThawDelegatedAccount => {
+            msg!("Instruction: Thaw Delegated Account");
+            process_thaw_delegated_account(program_id, accounts)
+        }
+        MetadataInstruction::SetAttributes(args) => {
+            msg!("Instruction: Set Attributes");
+            process_set_attributes(program_id, accounts, args.attributes)
+        }
+        MetadataInstruction::RemoveAttributes(args) => {
+            msg!("Instruction: Remove Attributes");
+            process_remove_attributes(program_id, accounts, args.trait_types)
+        }
+        MetadataInstruction::MigrateMasterEditionV1ToV2 => {
+            msg!("Instruction: Migrate Master Edition V1 to V2");
+            process_migrate_master_edition_v1_to_v2(program_id, accounts)
+        }
+        MetadataInstruction::SetTokenStandard => {
+            msg!("Instruction: Set Token Standard");
+            process_set_token_standard(program_id, accounts)
+        }
+        MetadataInstruction::UpdateMetadataAccountV2(args) => {
+            msg!("Instruction: Update Metadata Accounts V2");
+            process_update_metadata_accounts(
+                program_id,
+                accounts,
+                args.data,
+                args.update_authority,
+                args.primary_sale_happened,
+                args.propose_update_authority,
+            )
+        }
     }
 }
 
@@ -143,6 +251,7 @@ pub fn process_create_metadata_accounts<'a>(
     data: Data,
     allow_direct_creator_writes: bool,
     is_mutable: bool,
+    uses: Option<Uses>,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let metadata_account_info = next_account_info(account_info_iter)?;
@@ -167,6 +276,7 @@ pub fn process_create_metadata_accounts<'a>(
         data,
         allow_direct_creator_writes,
         is_mutable,
+        uses,
     )
 }
 
@@ -177,6 +287,7 @@ pub fn process_update_metadata_accounts(
     optional_data: Option<Data>,
     update_authority: Option<Pubkey>,
     primary_sale_happened: Option<bool>,
+    propose_update_authority: bool,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
 
@@ -190,6 +301,9 @@ pub fn process_update_metadata_accounts(
     if let Some(data) = optional_data {
         if metadata.is_mutable {
             assert_data_valid(&data, update_authority_info.key, &metadata, false)?;
+            if metadata.creator_consent_required {
+                assert_creator_consent(&metadata, &data, accounts)?;
+            }
             metadata.data = data;
         } else {
             return Err(MetadataError::DataIsImmutable.into());
@@ -197,7 +311,27 @@ pub fn process_update_metadata_accounts(
     }
 
     if let Some(val) = update_authority {
-        metadata.update_authority = val;
+        if propose_update_authority && val != metadata.update_authority {
+            // The new update authority has to sign AcceptUpdateAuthority before it takes over,
+            // so a mistyped key can't strand the metadata.
+            let pending_info = next_account_info(account_info_iter)?;
+            let payer_info = next_account_info(account_info_iter)?;
+            let system_account_info = next_account_info(account_info_iter)?;
+            let rent_info = next_account_info(account_info_iter)?;
+
+            set_pending_update_authority(
+                program_id,
+                &metadata,
+                pending_info,
+                update_authority_info,
+                &val,
+                payer_info,
+                system_account_info,
+                rent_info,
+            )?;
+        } else {
+            metadata.update_authority = val;
+        }
     }
 
     if let Some(val) = primary_sale_happened {
@@ -210,7 +344,7 @@ pub fn process_update_metadata_accounts(
 
     puff_out_data_fields(&mut metadata);
 
-    metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
+    metadata.save(metadata_account_info)?;
     Ok(())
 }
 
@@ -247,7 +381,7 @@ pub fn process_update_primary_sale_happened_via_token(
     }
 
     metadata.primary_sale_happened = true;
-    metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
+    metadata.save(metadata_account_info)?;
 
     Ok(())
 }
@@ -278,7 +412,7 @@ pub fn process_sign_metadata(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
     } else {
         return Err(MetadataError::NoCreatorsPresentOnMetadata.into());
     }
-    metadata.serialize(&mut *metadata_info.data.borrow_mut())?;
+    metadata.save(metadata_info)?;
 
     Ok(())
 }
@@ -301,7 +435,7 @@ pub fn process_create_master_edition(
     let system_account_info = next_account_info(account_info_iter)?;
     let rent_info = next_account_info(account_info_iter)?;
 
-    let metadata = Metadata::from_account_info(metadata_account_info)?;
+    let mut metadata = Metadata::from_account_info(metadata_account_info)?;
     let mint: Mint = assert_initialized(mint_info)?;
 
     let bump_seed = assert_derivation(
@@ -359,6 +493,12 @@ pub fn process_create_master_edition(
     edition.max_supply = max_supply;
     edition.serialize(&mut *edition_account_info.data.borrow_mut())?;
 
+    // Older clients pass the metadata read-only, SetTokenStandard can catch those up later.
+    if metadata_account_info.is_writable {
+        metadata.token_standard = Some(TokenStandard::NonFungible);
+        metadata.save(metadata_account_info)?;
+    }
+
     // While you can't mint any more of your master record, you can
     // mint as many limited editions as you like within your max supply.
     transfer_mint_authority(
@@ -372,10 +512,11 @@ pub fn process_create_master_edition(
     Ok(())
 }
 
+/// Mint a new edition, letting the program pick the next free edition number when none is given.
 pub fn process_mint_new_edition_from_master_edition_via_token<'a>(
     program_id: &'a Pubkey,
     accounts: &'a [AccountInfo<'a>],
-    edition: u64,
+    edition: Option<u64>,
     ignore_owner_signer: bool,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
@@ -395,6 +536,21 @@ pub fn process_mint_new_edition_from_master_edition_via_token<'a>(
     let system_account_info = next_account_info(account_info_iter)?;
     let rent_info = next_account_info(account_info_iter)?;
 
+    let edition = match edition {
+        Some(edition) => edition,
+        None => {
+            assert_owned_by(master_edition_account_info, program_id)?;
+            assert_owned_by(master_metadata_account_info, program_id)?;
+            let master_metadata = Metadata::from_account_info(master_metadata_account_info)?;
+            find_next_edition(
+                program_id,
+                &master_metadata.mint,
+                master_edition_account_info,
+                edition_marker_info,
+            )?
+        }
+    };
+
     process_mint_new_edition_from_master_edition_via_token_logic(
         &program_id,
         MintNewEditionFromMasterEditionViaTokenLogicArgs {
@@ -495,11 +651,16 @@ pub fn process_mint_new_edition_from_master_edition_via_vault_proxy<'a>(
     let safety_deposit_data = safety_deposit_info.data.borrow();
 
     // Since we're crunching out borsh for CPU units, do type checks this way
-    if vault_data[0] != spl_token_vault::state::Key::VaultV1 as u8 {
+    // Both vault versions share the same offsets for every field read below.
+    if vault_data[0] != spl_token_vault::state::Key::VaultV1 as u8
+        && vault_data[0] != spl_token_vault::state::Key::VaultV2 as u8
+    {
         return Err(VaultError::DataTypeMismatch.into());
     }
 
-    if safety_deposit_data[0] != spl_token_vault::state::Key::SafetyDepositBoxV1 as u8 {
+    if safety_deposit_data[0] != spl_token_vault::state::Key::SafetyDepositBoxV1 as u8
+        && safety_deposit_data[0] != spl_token_vault::state::Key::SafetyDepositBoxV2 as u8
+    {
         return Err(VaultError::DataTypeMismatch.into());
     }
 
@@ -599,6 +760,1213 @@ pub fn process_puff_metadata_account(
     let (_, edition_bump_seed) = Pubkey::find_program_address(edition_seeds, program_id);
     metadata.edition_nonce = Some(edition_bump_seed);
 
-    metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
+    metadata.save(metadata_account_info)?;
+    Ok(())
+}
+
+/// Mark a metadata as a verified member of a collection, with the consent of both
+/// the metadata's update authority and the collection's update authority.
+pub fn process_verify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let collection_authority_info = next_account_info(account_info_iter)?;
+    let collection_mint_info = next_account_info(account_info_iter)?;
+    let collection_metadata_info = next_account_info(account_info_iter)?;
+    let collection_master_edition_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    assert_collection_authority_is_correct(
+        program_id,
+        collection_authority_info,
+        collection_mint_info,
+        collection_metadata_info,
+        collection_master_edition_info,
+    )?;
+
+    metadata.collection = Some(Collection {
+        key: *collection_mint_info.key,
+        verified: true,
+    });
+    metadata.save(metadata_info)?;
+
+    Ok(())
+}
+
+/// Let a collection's update authority withdraw its verification of a member,
+/// leaving the collection on the metadata as an unverified claim.
+pub fn process_unverify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let collection_authority_info = next_account_info(account_info_iter)?;
+    let collection_mint_info = next_account_info(account_info_iter)?;
+    let collection_metadata_info = next_account_info(account_info_iter)?;
+    let collection_master_edition_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_collection_authority_is_correct(
+        program_id,
+        collection_authority_info,
+        collection_mint_info,
+        collection_metadata_info,
+        collection_master_edition_info,
+    )?;
+
+    match &mut metadata.collection {
+        Some(collection) if collection.key == *collection_mint_info.key => {
+            collection.verified = false;
+        }
+        _ => return Err(MetadataError::CollectionNotFound.into()),
+    }
+    metadata.save(metadata_info)?;
+
+    Ok(())
+}
+
+/// Burn an NFT held by the signer, closing its token account, metadata and edition and returning
+/// their rent to the owner. Burning a print also frees its edition number on the parent.
+pub fn process_burn_nft<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let attributes_info = next_account_info(account_info_iter)?;
+    let pending_info = next_account_info(account_info_iter)?;
+
+    assert_signer(owner_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(edition_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_token_program_matches_package(token_program_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    // Use authority records can't be found from the metadata, so they have to be revoked first.
+    if metadata.use_authority_record_count > 0 {
+        return Err(MetadataError::UseAuthorityRecordsOutstanding.into());
+    }
+
+    let token_account: Account = assert_initialized(token_account_info)?;
+    if token_account.owner != *owner_info.key {
+        return Err(MetadataError::OwnerMismatch.into());
+    }
+    if token_account.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+    if token_account.amount != 1 {
+        return Err(MetadataError::EditionsMustHaveExactlyOneToken.into());
+    }
+
+    assert_derivation(
+        program_id,
+        attributes_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata_info.key.as_ref(),
+            ATTRIBUTES.as_bytes(),
+        ],
+    )?;
+    assert_derivation(
+        program_id,
+        pending_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            PENDING_UPDATE_AUTHORITY.as_bytes(),
+        ],
+    )?;
+
+    assert_derivation(
+        program_id,
+        edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+
+    let edition_key = edition_info.data.borrow()[0];
+    if edition_key == Key::MasterEditionV2 as u8 {
+        let master_edition = MasterEditionV2::from_account_info(edition_info)?;
+        if master_edition.supply > 0 {
+            return Err(MetadataError::MasterEditionHasPrints.into());
+        }
+    } else if edition_key == Key::EditionV1 as u8 {
+        let master_edition_info = next_account_info(account_info_iter)?;
+        let master_mint_info = next_account_info(account_info_iter)?;
+        let edition_marker_info = next_account_info(account_info_iter)?;
+
+        let edition = Edition::from_account_info(edition_info)?;
+        if edition.parent != *master_edition_info.key {
+            return Err(MetadataError::InvalidEditionKey.into());
+        }
+        assert_owned_by(master_edition_info, program_id)?;
+        assert_derivation(
+            program_id,
+            master_edition_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                master_mint_info.key.as_ref(),
+                EDITION.as_bytes(),
+            ],
+        )?;
+
+        let parent_key = master_edition_info.data.borrow()[0];
+        if parent_key == Key::MasterEditionV2 as u8 {
+            assert_owned_by(edition_marker_info, program_id)?;
+            let marker_number = edition
+                .edition
+                .checked_div(EDITION_MARKER_BIT_SIZE)
+                .ok_or(MetadataError::NumericalOverflowError)?
+                .to_string();
+            assert_derivation(
+                program_id,
+                edition_marker_info,
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    master_mint_info.key.as_ref(),
+                    EDITION.as_bytes(),
+                    marker_number.as_bytes(),
+                ],
+            )?;
+
+            let mut master_edition = MasterEditionV2::from_account_info(master_edition_info)?;
+            master_edition.supply = master_edition
+                .supply
+                .checked_sub(1)
+                .ok_or(MetadataError::NumericalOverflowError)?;
+            master_edition.serialize(&mut *master_edition_info.data.borrow_mut())?;
+
+            let mut edition_marker = EditionMarker::from_account_info(edition_marker_info)?;
+            edition_marker.remove_edition(edition.edition)?;
+            edition_marker.serialize(&mut *edition_marker_info.data.borrow_mut())?;
+        } else if parent_key != Key::MasterEditionV1 as u8 {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+        // Prints of a Master Edition V1 took their numbers from its supply or a reservation
+        // list, which never hand a number out twice, so its supply is left as it is.
+    } else {
+        // Master Edition V1s need converting to V2 first.
+        return Err(MetadataError::DataTypeMismatch.into());
+    }
+
+    spl_token_burn(TokenBurnParams {
+        mint: mint_info.clone(),
+        source: token_account_info.clone(),
+        amount: 1,
+        authority: owner_info.clone(),
+        authority_signer_seeds: None,
+        token_program: token_program_info.clone(),
+    })?;
+    spl_token_close_account(TokenCloseAccountParams {
+        account: token_account_info.clone(),
+        destination: owner_info.clone(),
+        authority: owner_info.clone(),
+        authority_signer_seeds: None,
+        token_program: token_program_info.clone(),
+    })?;
+
+    close_program_account(metadata_info, owner_info)?;
+    close_program_account(edition_info, owner_info)?;
+    for side_info in [attributes_info, pending_info] {
+        if !side_info.data_is_empty() {
+            assert_owned_by(side_info, program_id)?;
+            close_program_account(side_info, owner_info)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the token account holds the metadata's mint for the given owner and returns the metadata.
+fn assert_holds_usable_token(
+    program_id: &Pubkey,
+    metadata_info: &AccountInfo,
+    mint_info: &AccountInfo,
+    token_account_info: &AccountInfo,
+    owner_info: &AccountInfo,
+) -> Result<Metadata, ProgramError> {
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let token_account: Account = assert_initialized(token_account_info)?;
+    if token_account.owner != *owner_info.key {
+        return Err(MetadataError::OwnerMismatch.into());
+    }
+    if token_account.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+    if token_account.amount == 0 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    if metadata.uses.is_none() {
+        return Err(MetadataError::Unusable.into());
+    }
+
+    Ok(metadata)
+}
+
+pub fn process_utilize<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    number_of_uses: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let use_authority_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(use_authority_info)?;
+    assert_token_program_matches_package(token_program_info)?;
+    let mut metadata = assert_holds_usable_token(
+        program_id,
+        metadata_info,
+        mint_info,
+        token_account_info,
+        owner_info,
+    )?;
+
+    if number_of_uses == 0 {
+        return Err(MetadataError::InvalidOperation.into());
+    }
+
+    let is_owner = use_authority_info.key == owner_info.key;
+    let mut burner: Option<(&AccountInfo<'a>, u8)> = None;
+    if !is_owner {
+        let use_authority_record_info = next_account_info(account_info_iter)?;
+        let burner_info = next_account_info(account_info_iter)?;
+
+        assert_owned_by(use_authority_record_info, program_id)?;
+        assert_derivation(
+            program_id,
+            use_authority_record_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                mint_info.key.as_ref(),
+                USER.as_bytes(),
+                use_authority_info.key.as_ref(),
+            ],
+        )?;
+        let mut record = UseAuthorityRecord::from_account_info(use_authority_record_info)?;
+        if record.owner != *owner_info.key {
+            return Err(MetadataError::UseAuthorityApprovedByPreviousOwner.into());
+        }
+        record.allowed_uses = record
+            .allowed_uses
+            .checked_sub(number_of_uses)
+            .ok_or(MetadataError::UseAuthorityAllowanceExceeded)?;
+        record.serialize(&mut *use_authority_record_info.data.borrow_mut())?;
+
+        let bump = assert_derivation(
+            program_id,
+            burner_info,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                mint_info.key.as_ref(),
+                USER.as_bytes(),
+                use_authority_info.key.as_ref(),
+                BURN.as_bytes(),
+            ],
+        )?;
+        burner = Some((burner_info, bump));
+    }
+
+    let must_burn = match metadata.uses.as_mut() {
+        Some(uses) => {
+            uses.remaining = uses
+                .remaining
+                .checked_sub(number_of_uses)
+                .ok_or(MetadataError::NotEnoughUses)?;
+            uses.use_method == UseMethod::Burn && uses.remaining == 0
+        }
+        None => return Err(MetadataError::Unusable.into()),
+    };
+    metadata.save(metadata_info)?;
+
+    if must_burn {
+        match burner {
+            None => spl_token_burn(TokenBurnParams {
+                mint: mint_info.clone(),
+                source: token_account_info.clone(),
+                amount: 1,
+                authority: use_authority_info.clone(),
+                authority_signer_seeds: None,
+                token_program: token_program_info.clone(),
+            })?,
+            Some((burner_info, bump)) => {
+                let signer_seeds = &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    mint_info.key.as_ref(),
+                    USER.as_bytes(),
+                    use_authority_info.key.as_ref(),
+                    BURN.as_bytes(),
+                    &[bump],
+                ];
+                spl_token_burn(TokenBurnParams {
+                    mint: mint_info.clone(),
+                    source: token_account_info.clone(),
+                    amount: 1,
+                    authority: burner_info.clone(),
+                    authority_signer_seeds: Some(signer_seeds),
+                    token_program: token_program_info.clone(),
+                })?
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn process_approve_use_authority<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    number_of_uses: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let use_authority_record_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let use_authority_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let burner_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_signer(owner_info)?;
+    assert_signer(payer_info)?;
+    assert_token_program_matches_package(token_program_info)?;
+    let mut metadata = assert_holds_usable_token(
+        program_id,
+        metadata_info,
+        mint_info,
+        token_account_info,
+        owner_info,
+    )?;
+
+    if number_of_uses == 0 {
+        return Err(MetadataError::InvalidOperation.into());
+    }
+
+    let bump = assert_derivation(
+        program_id,
+        use_authority_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            USER.as_bytes(),
+            use_authority_info.key.as_ref(),
+        ],
+    )?;
+    if use_authority_record_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            use_authority_record_info,
+            rent_info,
+            system_account_info,
+            payer_info,
+            USE_AUTHORITY_RECORD_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                mint_info.key.as_ref(),
+                USER.as_bytes(),
+                use_authority_info.key.as_ref(),
+                &[bump],
+            ],
+        )?;
+
+        metadata.use_authority_record_count = metadata
+            .use_authority_record_count
+            .checked_add(1)
+            .ok_or(MetadataError::NumericalOverflowError)?;
+        metadata.save(metadata_info)?;
+    } else {
+        // A record approved by a previous owner of the token is void and can be replaced.
+        assert_owned_by(use_authority_record_info, program_id)?;
+        let record = UseAuthorityRecord::from_account_info(use_authority_record_info)?;
+        if record.owner == *owner_info.key {
+            return Err(MetadataError::UseAuthorityRecordAlreadyExists.into());
+        }
+    }
+
+    let record = UseAuthorityRecord {
+        key: Key::UseAuthorityRecord,
+        allowed_uses: number_of_uses,
+        owner: *owner_info.key,
+    };
+    record.serialize(&mut *use_authority_record_info.data.borrow_mut())?;
+
+    if let Some(uses) = metadata.uses {
+        if uses.use_method == UseMethod::Burn {
+            assert_derivation(
+                program_id,
+                burner_info,
+                &[
+                    PREFIX.as_bytes(),
+                    program_id.as_ref(),
+                    mint_info.key.as_ref(),
+                    USER.as_bytes(),
+                    use_authority_info.key.as_ref(),
+                    BURN.as_bytes(),
+                ],
+            )?;
+            spl_token_approve(TokenApproveParams {
+                source: token_account_info.clone(),
+                delegate: burner_info.clone(),
+                amount: 1,
+                authority: owner_info.clone(),
+                authority_signer_seeds: None,
+                token_program: token_program_info.clone(),
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn process_revoke_use_authority<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let use_authority_record_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let use_authority_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let burner_info = next_account_info(account_info_iter)?;
+
+    assert_signer(owner_info)?;
+    assert_token_program_matches_package(token_program_info)?;
+    let mut metadata = assert_holds_usable_token(
+        program_id,
+        metadata_info,
+        mint_info,
+        token_account_info,
+        owner_info,
+    )?;
+
+    assert_owned_by(use_authority_record_info, program_id)?;
+    assert_derivation(
+        program_id,
+        use_authority_record_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            USER.as_bytes(),
+            use_authority_info.key.as_ref(),
+        ],
+    )?;
+    UseAuthorityRecord::from_account_info(use_authority_record_info)?;
+
+    assert_derivation(
+        program_id,
+        burner_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            USER.as_bytes(),
+            use_authority_info.key.as_ref(),
+            BURN.as_bytes(),
+        ],
+    )?;
+    let token_account: Account = assert_initialized(token_account_info)?;
+
+    // Only one delegate fits on the token account, leave it alone if another use authority holds it.
+    if let Some(uses) = &metadata.uses {
+        if uses.use_method == UseMethod::Burn
+            && token_account.delegate == COption::Some(*burner_info.key)
+        {
+            spl_token_revoke(TokenRevokeParams {
+                source: token_account_info.clone(),
+                authority: owner_info.clone(),
+                authority_signer_seeds: None,
+                token_program: token_program_info.clone(),
+            })?;
+        }
+    }
+
+    close_program_account(use_authority_record_info, owner_info)?;
+
+    metadata.use_authority_record_count = metadata
+        .use_authority_record_count
+        .checked_sub(1)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+    metadata.save(metadata_info)?;
+
+    Ok(())
+}
+
+pub fn process_set_pending_update_authority<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let pending_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let new_update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    set_pending_update_authority(
+        program_id,
+        &metadata,
+        pending_info,
+        update_authority_info,
+        new_update_authority_info.key,
+        payer_info,
+        system_account_info,
+        rent_info,
+    )
+}
+
+/// Creates or overwrites the pending update authority account of the metadata.
+#[allow(clippy::too_many_arguments)]
+fn set_pending_update_authority<'a>(
+    program_id: &Pubkey,
+    metadata: &Metadata,
+    pending_info: &AccountInfo<'a>,
+    update_authority_info: &AccountInfo<'a>,
+    new_update_authority: &Pubkey,
+    payer_info: &AccountInfo<'a>,
+    system_account_info: &AccountInfo<'a>,
+    rent_info: &AccountInfo<'a>,
+) -> ProgramResult {
+    let bump = assert_derivation(
+        program_id,
+        pending_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            PENDING_UPDATE_AUTHORITY.as_bytes(),
+        ],
+    )?;
+
+    if pending_info.data_is_empty() {
+        create_or_allocate_account_raw(
+            *program_id,
+            pending_info,
+            rent_info,
+            system_account_info,
+            payer_info,
+            PENDING_UPDATE_AUTHORITY_SIZE,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                metadata.mint.as_ref(),
+                PENDING_UPDATE_AUTHORITY.as_bytes(),
+                &[bump],
+            ],
+        )?;
+    } else {
+        assert_owned_by(pending_info, program_id)?;
+    }
+
+    let pending = PendingUpdateAuthority {
+        key: Key::PendingUpdateAuthorityV1,
+        new_update_authority: *new_update_authority,
+        proposed_by: *update_authority_info.key,
+    };
+    pending.serialize(&mut *pending_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+pub fn process_accept_update_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let pending_info = next_account_info(account_info_iter)?;
+    let new_update_authority_info = next_account_info(account_info_iter)?;
+    let proposed_by_info = next_account_info(account_info_iter)?;
+
+    assert_signer(new_update_authority_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(pending_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_derivation(
+        program_id,
+        pending_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            PENDING_UPDATE_AUTHORITY.as_bytes(),
+        ],
+    )?;
+
+    let pending = PendingUpdateAuthority::from_account_info(pending_info)?;
+    if pending.new_update_authority != *new_update_authority_info.key {
+        return Err(MetadataError::PendingUpdateAuthorityMismatch.into());
+    }
+    // A transfer proposed by a previous update authority must not outlive its authority.
+    if pending.proposed_by != metadata.update_authority {
+        return Err(MetadataError::StalePendingUpdateAuthority.into());
+    }
+    if pending.proposed_by != *proposed_by_info.key {
+        return Err(MetadataError::UpdateAuthorityIncorrect.into());
+    }
+
+    metadata.update_authority = pending.new_update_authority;
+    metadata.save(metadata_info)?;
+
+    close_program_account(pending_info, proposed_by_info)?;
+
+    Ok(())
+}
+
+pub fn process_cancel_pending_update_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let pending_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(pending_info, program_id)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    assert_derivation(
+        program_id,
+        pending_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            PENDING_UPDATE_AUTHORITY.as_bytes(),
+        ],
+    )?;
+    PendingUpdateAuthority::from_account_info(pending_info)?;
+
+    close_program_account(pending_info, update_authority_info)?;
+
+    Ok(())
+}
+
+pub fn process_require_creator_consent(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(metadata_info, program_id)?;
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    metadata.creator_consent_required = true;
+    metadata.save(metadata_info)?;
+
+    Ok(())
+}
+
+pub fn process_remove_creator_verification(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let creator_info = next_account_info(account_info_iter)?;
+
+    assert_signer(creator_info)?;
+    assert_owned_by(metadata_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+
+    if let Some(creators) = &mut metadata.data.creators {
+        match creators.iter_mut().find(|c| c.address == *creator_info.key) {
+            Some(creator) => creator.verified = false,
+            None => return Err(MetadataError::CreatorNotFound.into()),
+        }
+    } else {
+        return Err(MetadataError::NoCreatorsPresentOnMetadata.into());
+    }
+    metadata.save(metadata_info)?;
+
+    Ok(())
+}
+
+pub fn process_freeze_delegated_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    freeze_or_thaw_delegated_account(program_id, accounts, true)
+}
+
+pub fn process_thaw_delegated_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    freeze_or_thaw_delegated_account(program_id, accounts, false)
+}
+
+/// Freezes or thaws a token account holding one NFT on behalf of its delegate, with the
+/// edition pda signing as the mint's freeze authority.
+fn freeze_or_thaw_delegated_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    freeze: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let delegate_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(delegate_info)?;
+    assert_owned_by(edition_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_token_program_matches_package(token_program_info)?;
+
+    let token_account: Account = assert_initialized(token_account_info)?;
+    if token_account.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+    if token_account.delegate != COption::Some(*delegate_info.key) {
+        return Err(MetadataError::InvalidDelegate.into());
+    }
+    if token_account.amount != 1 {
+        return Err(MetadataError::EditionsMustHaveExactlyOneToken.into());
+    }
+
+    let bump = assert_derivation(
+        program_id,
+        edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+    if get_mint_freeze_authority(mint_info)? != COption::Some(*edition_info.key) {
+        return Err(MetadataError::InvalidFreezeAuthority.into());
+    }
+
+    let instruction = if freeze {
+        spl_token::instruction::freeze_account(
+            token_program_info.key,
+            token_account_info.key,
+            mint_info.key,
+            edition_info.key,
+            &[],
+        )?
+    } else {
+        spl_token::instruction::thaw_account(
+            token_program_info.key,
+            token_account_info.key,
+            mint_info.key,
+            edition_info.key,
+            &[],
+        )?
+    };
+    invoke_signed(
+        &instruction,
+        &[
+            token_account_info.clone(),
+            mint_info.clone(),
+            edition_info.clone(),
+            token_program_info.clone(),
+        ],
+        &[&[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+            &[bump],
+        ]],
+    )
+    .map_err(|_| MetadataError::TokenFreezeThawFailed.into())
+}
+
+/// Checks the update authority may change the attributes of a mutable metadata,
+/// and that the attributes account is the metadata's.
+fn assert_can_update_attributes(
+    program_id: &Pubkey,
+    attributes_info: &AccountInfo,
+    metadata_info: &AccountInfo,
+    update_authority_info: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    assert_owned_by(metadata_info, program_id)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    if !metadata.is_mutable {
+        return Err(MetadataError::DataIsImmutable.into());
+    }
+
+    assert_derivation(
+        program_id,
+        attributes_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata_info.key.as_ref(),
+            ATTRIBUTES.as_bytes(),
+        ],
+    )
+}
+
+pub fn process_set_attributes<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    attributes: Vec<Attribute>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let attributes_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    let bump = assert_can_update_attributes(
+        program_id,
+        attributes_info,
+        metadata_info,
+        update_authority_info,
+    )?;
+
+    let is_new = attributes_info.data_is_empty();
+    let mut metadata_attributes = if is_new {
+        MetadataAttributes {
+            key: Key::MetadataAttributesV1,
+            metadata: *metadata_info.key,
+            attributes: vec![],
+        }
+    } else {
+        assert_owned_by(attributes_info, program_id)?;
+        MetadataAttributes::from_account_info(attributes_info)?
+    };
+
+    for attribute in attributes {
+        match metadata_attributes
+            .attributes
+            .iter_mut()
+            .find(|a| a.trait_type == attribute.trait_type)
+        {
+            Some(existing) => existing.value = attribute.value,
+            None => metadata_attributes.attributes.push(attribute),
+        }
+    }
+    assert_attributes_valid(&metadata_attributes.attributes)?;
+
+    if is_new {
+        create_or_allocate_account_raw(
+            *program_id,
+            attributes_info,
+            rent_info,
+            system_account_info,
+            payer_info,
+            MAX_METADATA_ATTRIBUTES_LEN,
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                metadata_info.key.as_ref(),
+                ATTRIBUTES.as_bytes(),
+                &[bump],
+            ],
+        )?;
+    }
+    metadata_attributes.save(attributes_info)?;
+
+    Ok(())
+}
+
+pub fn process_remove_attributes<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    trait_types: Vec<String>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let attributes_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+
+    assert_can_update_attributes(
+        program_id,
+        attributes_info,
+        metadata_info,
+        update_authority_info,
+    )?;
+    assert_owned_by(attributes_info, program_id)?;
+    // The payer receives the rent back if the account closes, so it can't be just any account
+    assert_signer(payer_info)?;
+
+    let mut metadata_attributes = MetadataAttributes::from_account_info(attributes_info)?;
+    metadata_attributes
+        .attributes
+        .retain(|a| !trait_types.contains(&a.trait_type));
+
+    if metadata_attributes.attributes.is_empty() {
+        close_program_account(attributes_info, payer_info)?;
+        return Ok(());
+    }
+
+    metadata_attributes.save(attributes_info)?;
+
+    Ok(())
+}
+
+pub fn process_migrate_master_edition_v1_to_v2<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let master_edition_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let one_time_printing_auth_mint_info = next_account_info(account_info_iter)?;
+    let printing_mint_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    assert_signer(owner_info)?;
+    assert_owned_by(master_edition_info, program_id)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+    assert_owned_by(one_time_printing_auth_mint_info, &spl_token::id())?;
+    assert_owned_by(printing_mint_info, &spl_token::id())?;
+    assert_token_program_matches_package(token_program_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_derivation(
+        program_id,
+        master_edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+
+    let token_account: Account = assert_initialized(token_account_info)?;
+    if token_account.owner != *owner_info.key {
+        return Err(MetadataError::OwnerMismatch.into());
+    }
+    if token_account.mint != metadata.mint {
+        return Err(MetadataError::TokenAccountMintMismatch.into());
+    }
+    if token_account.amount != 1 {
+        return Err(MetadataError::NotEnoughTokens.into());
+    }
+
+    let master_edition: MasterEditionV1 = MasterEditionV1::from_account_info(master_edition_info)?;
+    if master_edition.one_time_printing_authorization_mint != *one_time_printing_auth_mint_info.key
+    {
+        return Err(MetadataError::OneTimePrintingAuthMintMismatch.into());
+    }
+    if master_edition.printing_mint != *printing_mint_info.key {
+        return Err(MetadataError::PrintingMintMismatch.into());
+    }
+
+    // The rest are the owner's printing and one time authorization token accounts, which get
+    // burned and closed, and reservation lists of this master edition, which get closed to their funder
+    // or to the owner when they never recorded one.
+    while let Some(account_info) = account_info_iter.next() {
+        if *account_info.owner == spl_token::id() {
+            let printing_account: Account = assert_initialized(account_info)?;
+            if printing_account.owner != *owner_info.key {
+                return Err(MetadataError::OwnerMismatch.into());
+            }
+            let mint_info = if printing_account.mint == *printing_mint_info.key {
+                printing_mint_info
+            } else if printing_account.mint == *one_time_printing_auth_mint_info.key {
+                one_time_printing_auth_mint_info
+            } else {
+                return Err(MetadataError::TokenAccountMintMismatch.into());
+            };
+
+            if printing_account.amount > 0 {
+                spl_token_burn(TokenBurnParams {
+                    mint: mint_info.clone(),
+                    source: account_info.clone(),
+                    amount: printing_account.amount,
+                    authority: owner_info.clone(),
+                    authority_signer_seeds: None,
+                    token_program: token_program_info.clone(),
+                })?;
+            }
+            spl_token_close_account(TokenCloseAccountParams {
+                account: account_info.clone(),
+                destination: owner_info.clone(),
+                authority: owner_info.clone(),
+                authority_signer_seeds: None,
+                token_program: token_program_info.clone(),
+            })?;
+        } else if account_info.owner == program_id {
+            let reservation_list = get_reservation_list(account_info)?;
+            if reservation_list.master_edition() != *master_edition_info.key {
+                return Err(MetadataError::MasterRecordMismatch.into());
+            }
+            // Zeroed placeholders are system program keyed anchors, not real reservations
+            if reservation_list.reservations().iter().any(|reservation| {
+                reservation.address != solana_program::system_program::id()
+                    && reservation.spots_remaining > 0
+            }) {
+                return Err(MetadataError::ReservationsOutstanding.into());
+            }
+            // Lists that never recorded their funder refund the owner of the master edition
+            match reservation_list.funder() {
+                Some(funder) => {
+                    let funder_info = next_account_info(account_info_iter)?;
+                    if *funder_info.key != funder {
+                        return Err(MetadataError::ReservationListFunderMismatch.into());
+                    }
+                    close_program_account(account_info, funder_info)?;
+                }
+                None => close_program_account(account_info, owner_info)?,
+            }
+        } else {
+            return Err(MetadataError::IncorrectOwner.into());
+        }
+    }
+
+    let printing_mint: Mint = assert_initialized(printing_mint_info)?;
+    if printing_mint.supply != 0 {
+        return Err(MetadataError::PrintingMintSupplyMustBeZeroForConversion.into());
+    }
+    let auth_mint: Mint = assert_initialized(one_time_printing_auth_mint_info)?;
+    if auth_mint.supply != 0 {
+        return Err(MetadataError::OneTimeAuthMintSupplyMustBeZeroForConversion.into());
+    }
+
+    MasterEditionV2 {
+        key: Key::MasterEditionV2,
+        supply: master_edition.supply,
+        max_supply: master_edition.max_supply,
+    }
+    .serialize(&mut *master_edition_info.data.borrow_mut())?;
+
+    Ok(())
+}
+
+pub fn process_set_token_standard(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_info = next_account_info(account_info_iter).ok();
+
+    assert_owned_by(metadata_info, program_id)?;
+    assert_owned_by(mint_info, &spl_token::id())?;
+
+    let mut metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+    if metadata.mint != *mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+
+    let edition_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint_info.key.as_ref(),
+        EDITION.as_bytes(),
+    ];
+    let token_standard = match edition_info {
+        Some(edition_info) => {
+            assert_owned_by(edition_info, program_id)?;
+            assert_derivation(program_id, edition_info, edition_seeds)?;
+
+            let key = edition_info.data.borrow()[0];
+            if key == Key::EditionV1 as u8 {
+                TokenStandard::NonFungibleEdition
+            } else if key == Key::MasterEditionV1 as u8 || key == Key::MasterEditionV2 as u8 {
+                TokenStandard::NonFungible
+            } else {
+                return Err(MetadataError::DataTypeMismatch.into());
+            }
+        }
+        None => {
+            // Editions hold the mint authority, so a mint that has one can't skip it here.
+            let mint: Mint = assert_initialized(mint_info)?;
+            let (edition_key, _) = Pubkey::find_program_address(edition_seeds, program_id);
+            if mint.mint_authority == COption::Some(edition_key) {
+                return Err(MetadataError::InvalidEditionKey.into());
+            }
+            if mint.decimals == 0 {
+                TokenStandard::FungibleAsset
+            } else {
+                TokenStandard::Fungible
+            }
+        }
+    };
+
+    metadata.token_standard = Some(token_standard);
+    metadata.save(metadata_info)?;
+
     Ok(())
 }
@@ -2,8 +2,8 @@ use {
     crate::{error::MetadataError, utils::try_from_slice_checked},
     borsh::{BorshDeserialize, BorshSerialize},
     solana_program::{
-        account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-        pubkey::Pubkey,
+        account_info::AccountInfo, borsh::try_from_slice_unchecked, entrypoint::ProgramResult,
+        program_error::ProgramError, pubkey::Pubkey,
     },
 };
 /// prefix used for PDAs to avoid certain collision attacks (https://en.wikipedia.org/wiki/Collision_attack#Chosen-prefix_collision_attack)
@@ -14,13 +14,45 @@ pub const EDITION: &str = "edition";
 
 pub const RESERVATION: &str = "reservation";
 
+/// Used in seeds to make the use authority record pda address
+pub const USER: &str = "user";
+
+/// Used in seeds to make the pda that burns tokens on behalf of use authorities
+pub const BURN: &str = "burn";
+
+/// Used in seeds to make the pending update authority pda address
+pub const PENDING_UPDATE_AUTHORITY: &str = "pending_update_authority";
+
+/// Used in seeds to make the metadata attributes pda address
+pub const ATTRIBUTES: &str = "attributes";
+
 pub const MAX_NAME_LENGTH: usize = 32;
 
 pub const MAX_SYMBOL_LENGTH: usize = 10;
 
 pub const MAX_URI_LENGTH: usize = 200;
 
-pub const MAX_METADATA_LEN: usize = 1 + 32 + 32 + MAX_DATA_SIZE + 1 + 1 + 9 + 172;
+// The collection, uses, creator consent flag, token standard and use authority record count live
+// in what used to be padding, so the total length is unchanged.
+pub const MAX_METADATA_LEN: usize = 1
+    + 32
+    + 32
+    + MAX_DATA_SIZE
+    + 1
+    + 1
+    + 9
+    + MAX_COLLECTION_LEN
+    + MAX_USES_LEN
+    + 1
+    + MAX_TOKEN_STANDARD_LEN
+    + 4
+    + 113;
+
+/// Last byte of a metadata account. Nothing wrote past edition_nonce before the collection and the
+/// fields after it existed, so save sets this byte to mark accounts whose trailing fields can be read.
+pub const METADATA_FIELDS_MARKER_OFFSET: usize = MAX_METADATA_LEN - 1;
+
+pub const METADATA_FIELDS_MARKER: u8 = 1;
 
 pub const MAX_DATA_SIZE: usize = 4
     + MAX_NAME_LENGTH
@@ -44,6 +76,26 @@ pub const MAX_CREATOR_LIMIT: usize = 5;
 
 pub const MAX_CREATOR_LEN: usize = 32 + 1 + 1;
 
+pub const MAX_COLLECTION_LEN: usize = 1 + 32 + 1;
+
+pub const MAX_TOKEN_STANDARD_LEN: usize = 2;
+
+pub const MAX_USES_LEN: usize = 1 + 1 + 8 + 8;
+
+pub const USE_AUTHORITY_RECORD_SIZE: usize = 1 + 8 + 32 + 9;
+
+pub const PENDING_UPDATE_AUTHORITY_SIZE: usize = 1 + 32 + 32;
+
+pub const MAX_ATTRIBUTES: usize = 32;
+
+pub const MAX_TRAIT_TYPE_LENGTH: usize = 32;
+
+pub const MAX_TRAIT_VALUE_LENGTH: usize = 64;
+
+pub const MAX_ATTRIBUTE_LEN: usize = 4 + MAX_TRAIT_TYPE_LENGTH + 4 + MAX_TRAIT_VALUE_LENGTH;
+
+pub const MAX_METADATA_ATTRIBUTES_LEN: usize = 1 + 32 + 4 + MAX_ATTRIBUTES * MAX_ATTRIBUTE_LEN;
+
 pub const MAX_RESERVATIONS: usize = 200;
 
 // can hold up to 200 keys per reservation, note: the extra 8 is for number of elements in the vec
@@ -67,6 +119,9 @@ pub enum Key {
     ReservationListV2,
     MasterEditionV2,
     EditionMarker,
+    UseAuthorityRecord,
+    PendingUpdateAuthorityV1,
+    MetadataAttributesV1,
 }
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
@@ -84,7 +139,7 @@ pub struct Data {
 }
 
 #[repr(C)]
-#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+#[derive(Clone, BorshSerialize, Debug)]
 pub struct Metadata {
     pub key: Key,
     pub update_authority: Pubkey,
@@ -96,6 +151,67 @@ pub struct Metadata {
     pub is_mutable: bool,
     /// nonce for easy calculation of editions, if present
     pub edition_nonce: Option<u8>,
+    /// Collection this token belongs to, only trustworthy once verified by the collection's update authority
+    pub collection: Option<Collection>,
+    /// Consumable uses, only the remaining counter can change after creation
+    pub uses: Option<Uses>,
+    /// Once set, changing royalties or a verified creator's share needs that creator's signature
+    pub creator_consent_required: bool,
+    /// What kind of token the mint is, set by the program as the mint gains editions.
+    /// None for a zero decimal mint that has no edition yet, until SetTokenStandard records it.
+    pub token_standard: Option<TokenStandard>,
+    /// Use authority records approved for this mint and not yet revoked, BurnNft needs this to be zero
+    pub use_authority_record_count: u32,
+}
+
+impl BorshDeserialize for Metadata {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let account_data: &[u8] = buf;
+        let key = Key::deserialize(buf)?;
+        let update_authority = Pubkey::deserialize(buf)?;
+        let mint = Pubkey::deserialize(buf)?;
+        let data = Data::deserialize(buf)?;
+        let primary_sale_happened = bool::deserialize(buf)?;
+        let is_mutable = bool::deserialize(buf)?;
+        let edition_nonce = Option::<u8>::deserialize(buf)?;
+        // Metadata written before collections existed was never zeroed past its end, so a shrunken
+        // name, uri or creator list can leave bytes here that parse as anything, including a
+        // verified collection. Those fields are only read from accounts save has marked.
+        let has_trailing_fields = account_data.len() != MAX_METADATA_LEN
+            || account_data[METADATA_FIELDS_MARKER_OFFSET] == METADATA_FIELDS_MARKER;
+        let (
+            collection,
+            uses,
+            creator_consent_required,
+            token_standard,
+            use_authority_record_count,
+        ) = if has_trailing_fields {
+            (
+                Option::<Collection>::deserialize(buf)?,
+                Option::<Uses>::deserialize(buf)?,
+                bool::deserialize(buf)?,
+                Option::<TokenStandard>::deserialize(buf)?,
+                u32::deserialize(buf)?,
+            )
+        } else {
+            (None, None, false, None, 0)
+        };
+
+        Ok(Metadata {
+            key,
+            update_authority,
+            mint,
+            data,
+            primary_sale_happened,
+            is_mutable,
+            edition_nonce,
+            collection,
+            uses,
+            creator_consent_required,
+            token_standard,
+            use_authority_record_count,
+        })
+    }
 }
 
 impl Metadata {
@@ -105,6 +221,24 @@ impl Metadata {
 
         Ok(md)
     }
+
+    /// Writes the metadata and zeroes the rest of the account, so that bytes left over from a
+    /// longer previous version can never be read back as one of the trailing optional fields,
+    /// then marks the account as having those fields.
+    pub fn save(&self, a: &AccountInfo) -> ProgramResult {
+        let serialized = self.try_to_vec()?;
+        let mut data = a.data.borrow_mut();
+        if serialized.len() > data.len() || serialized.len() > METADATA_FIELDS_MARKER_OFFSET {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+        data[..serialized.len()].copy_from_slice(&serialized);
+        data[serialized.len()..].fill(0);
+        if data.len() == MAX_METADATA_LEN {
+            data[METADATA_FIELDS_MARKER_OFFSET] = METADATA_FIELDS_MARKER;
+        }
+
+        Ok(())
+    }
 }
 
 pub trait MasterEdition {
@@ -265,12 +399,152 @@ pub struct Creator {
     pub share: u8,
 }
 
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct Collection {
+    /// Mint of the collection NFT, which must have a MasterEditionV2
+    pub key: Pubkey,
+    /// Flipped by the collection NFT's update authority via VerifyCollection
+    pub verified: bool,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum TokenStandard {
+    /// A mint with a master edition
+    NonFungible,
+    /// A mint with zero decimals and no edition, such as an item that has several copies
+    FungibleAsset,
+    /// A mint with decimals and no edition
+    Fungible,
+    /// A print of a master edition
+    NonFungibleEdition,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy)]
+pub enum UseMethod {
+    /// The token is burned when the last use is utilized
+    Burn,
+    /// The token can be utilized until its uses run out
+    Multiple,
+    /// The token can be utilized exactly once
+    Single,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// Lets a key other than the token owner utilize up to allowed_uses of a metadata's uses.
+pub struct UseAuthorityRecord {
+    pub key: Key,
+    pub allowed_uses: u64,
+    /// Token owner that approved the use authority, the record is void once they no longer hold the token
+    pub owner: Pubkey,
+}
+
+impl UseAuthorityRecord {
+    pub fn from_account_info(a: &AccountInfo) -> Result<UseAuthorityRecord, ProgramError> {
+        let record: UseAuthorityRecord = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::UseAuthorityRecord,
+            USE_AUTHORITY_RECORD_SIZE,
+        )?;
+
+        Ok(record)
+    }
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// An update authority transfer that only takes effect once the new update authority accepts it.
+pub struct PendingUpdateAuthority {
+    pub key: Key,
+    pub new_update_authority: Pubkey,
+    /// Update authority at the time of the proposal, the transfer is void once it no longer is
+    pub proposed_by: Pubkey,
+}
+
+impl PendingUpdateAuthority {
+    pub fn from_account_info(a: &AccountInfo) -> Result<PendingUpdateAuthority, ProgramError> {
+        let pending: PendingUpdateAuthority = try_from_slice_checked(
+            &a.data.borrow_mut(),
+            Key::PendingUpdateAuthorityV1,
+            PENDING_UPDATE_AUTHORITY_SIZE,
+        )?;
+
+        Ok(pending)
+    }
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct Attribute {
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+/// On-chain traits of a Metadata, allocated at MAX_METADATA_ATTRIBUTES_LEN so it never needs resizing.
+pub struct MetadataAttributes {
+    pub key: Key,
+    /// Present for reverse lookups
+    pub metadata: Pubkey,
+    pub attributes: Vec<Attribute>,
+}
+
+impl MetadataAttributes {
+    pub fn from_account_info(a: &AccountInfo) -> Result<MetadataAttributes, ProgramError> {
+        MetadataAttributes::from_bytes(&a.data.borrow())
+    }
+
+    /// Parses the raw bytes of a MetadataAttributes account, for programs reading traits off it.
+    pub fn from_bytes(data: &[u8]) -> Result<MetadataAttributes, ProgramError> {
+        if data.is_empty() || data[0] != Key::MetadataAttributesV1 as u8 {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+        let attributes: MetadataAttributes = try_from_slice_unchecked(data)?;
+
+        Ok(attributes)
+    }
+
+    /// Writes the attributes, zeroing whatever a longer list left behind
+    pub fn save(&self, a: &AccountInfo) -> ProgramResult {
+        let serialized = self.try_to_vec()?;
+        let mut data = a.data.borrow_mut();
+        if serialized.len() > data.len() {
+            return Err(MetadataError::TooManyAttributes.into());
+        }
+        data[..serialized.len()].copy_from_slice(&serialized);
+        data[serialized.len()..].fill(0);
+
+        Ok(())
+    }
+
+    /// Value of the given trait, if present
+    pub fn get(&self, trait_type: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|a| a.trait_type == trait_type)
+            .map(|a| a.value.as_str())
+    }
+}
+
 pub trait ReservationList {
     fn master_edition(&self) -> Pubkey;
     fn supply_snapshot(&self) -> Option<u64>;
     fn reservations(&self) -> Vec<Reservation>;
     fn total_reservation_spots(&self) -> u64;
     fn current_reservation_spots(&self) -> u64;
+    fn funder(&self) -> Option<Pubkey>;
     fn set_master_edition(&mut self, key: Pubkey);
     fn set_supply_snapshot(&mut self, supply: Option<u64>);
     fn set_reservations(&mut self, reservations: Vec<Reservation>) -> ProgramResult;
@@ -312,6 +586,9 @@ pub struct ReservationListV2 {
     pub total_reservation_spots: u64,
     /// Cached count of reservation spots in the reservation vec to save on CPU.
     pub current_reservation_spots: u64,
+    /// Who paid for the list and gets its rent back when it is closed,
+    /// None for lists created before it was recorded.
+    pub funder: Option<Pubkey>,
 }
 
 impl ReservationList for ReservationListV2 {
@@ -405,6 +682,10 @@ impl ReservationList for ReservationListV2 {
     fn set_current_reservation_spots(&mut self, current_reservation_spots: u64) {
         self.current_reservation_spots = current_reservation_spots;
     }
+
+    fn funder(&self) -> Option<Pubkey> {
+        self.funder
+    }
 }
 
 impl ReservationListV2 {
@@ -506,6 +787,11 @@ impl ReservationList for ReservationListV1 {
     }
 
     fn set_current_reservation_spots(&mut self, _: u64) {}
+
+    /// V1 lists never recorded who paid for them
+    fn funder(&self) -> Option<Pubkey> {
+        None
+    }
 }
 
 impl ReservationListV1 {
@@ -608,4 +894,11 @@ impl EditionMarker {
         self.ledger[index] = self.ledger[index] | mask;
         Ok(())
     }
+
+    pub fn remove_edition(&mut self, edition: u64) -> ProgramResult {
+        let (index, mask) = EditionMarker::get_index_and_mask(edition)?;
+        // bitwise and with everything but our position to clear it
+        self.ledger[index] &= !mask;
+        Ok(())
+    }
 }
@@ -2,10 +2,11 @@ use {
     crate::{
         error::MetadataError,
         state::{
-            get_reservation_list, Data, EditionMarker, Key, MasterEditionV1, Metadata, EDITION,
-            EDITION_MARKER_BIT_SIZE, MAX_CREATOR_LIMIT, MAX_EDITION_LEN, MAX_EDITION_MARKER_SIZE,
-            MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH,
-            MAX_URI_LENGTH, PREFIX,
+            get_reservation_list, Attribute, Data, EditionMarker, Key, MasterEditionV1, Metadata,
+            TokenStandard, UseMethod, Uses, EDITION, EDITION_MARKER_BIT_SIZE, MAX_ATTRIBUTES,
+            MAX_CREATOR_LIMIT, MAX_EDITION_LEN, MAX_EDITION_MARKER_SIZE, MAX_MASTER_EDITION_LEN,
+            MAX_METADATA_LEN, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_TRAIT_TYPE_LENGTH,
+            MAX_TRAIT_VALUE_LENGTH, MAX_URI_LENGTH, PREFIX,
         },
     },
     arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs},
@@ -129,6 +130,72 @@ pub fn assert_data_valid(
     Ok(())
 }
 
+/// For metadata requiring creator consent, checks that every verified creator whose share changes,
+/// or every verified creator if the royalties change, is among the signers.
+pub fn assert_creator_consent(
+    existing_metadata: &Metadata,
+    data: &Data,
+    signers: &[AccountInfo],
+) -> ProgramResult {
+    let existing_creators = match &existing_metadata.data.creators {
+        Some(creators) => creators,
+        None => return Ok(()),
+    };
+    let royalties_changed =
+        data.seller_fee_basis_points != existing_metadata.data.seller_fee_basis_points;
+
+    for existing_creator in existing_creators.iter().filter(|c| c.verified) {
+        // A creator dropped from the list has had their share changed to nothing.
+        let new_share = data
+            .creators
+            .as_ref()
+            .and_then(|creators| {
+                creators
+                    .iter()
+                    .find(|c| c.address == existing_creator.address)
+            })
+            .map(|c| c.share);
+
+        if (royalties_changed || new_share != Some(existing_creator.share))
+            && !signers
+                .iter()
+                .any(|s| s.is_signer && *s.key == existing_creator.address)
+        {
+            return Err(MetadataError::CreatorConsentRequired.into());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn assert_attributes_valid(attributes: &[Attribute]) -> ProgramResult {
+    if attributes.len() > MAX_ATTRIBUTES {
+        return Err(MetadataError::TooManyAttributes.into());
+    }
+
+    for attribute in attributes {
+        if attribute.trait_type.len() > MAX_TRAIT_TYPE_LENGTH
+            || attribute.value.len() > MAX_TRAIT_VALUE_LENGTH
+        {
+            return Err(MetadataError::AttributeTooLong.into());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn assert_uses_valid(uses: &Uses) -> ProgramResult {
+    if uses.total == 0 || uses.remaining != uses.total {
+        return Err(MetadataError::InvalidUseConfiguration.into());
+    }
+
+    if uses.use_method == UseMethod::Single && uses.total != 1 {
+        return Err(MetadataError::InvalidUseConfiguration.into());
+    }
+
+    Ok(())
+}
+
 /// assert initialized account
 pub fn assert_initialized<T: Pack + IsInitialized>(
     account_info: &AccountInfo,
@@ -205,6 +272,43 @@ pub fn assert_update_authority_is_correct(
     Ok(())
 }
 
+/// Checks that the collection authority signed and is the update authority of a collection
+/// NFT whose mint has a Master Edition V2.
+pub fn assert_collection_authority_is_correct(
+    program_id: &Pubkey,
+    collection_authority_info: &AccountInfo,
+    collection_mint_info: &AccountInfo,
+    collection_metadata_info: &AccountInfo,
+    collection_master_edition_info: &AccountInfo,
+) -> ProgramResult {
+    assert_owned_by(collection_metadata_info, program_id)?;
+
+    let collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
+    if collection_metadata.mint != *collection_mint_info.key {
+        return Err(MetadataError::MintMismatch.into());
+    }
+    assert_update_authority_is_correct(&collection_metadata, collection_authority_info)?;
+
+    assert_derivation(
+        program_id,
+        collection_master_edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            collection_mint_info.key.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )?;
+    if collection_master_edition_info.data_is_empty()
+        || collection_master_edition_info.data.borrow()[0] != Key::MasterEditionV2 as u8
+    {
+        return Err(MetadataError::CollectionMustBeAMasterEditionV2.into());
+    }
+    assert_owned_by(collection_master_edition_info, program_id)?;
+
+    Ok(())
+}
+
 /// Unpacks COption from a slice, taken from token program
 fn unpack_coption_key(src: &[u8; 36]) -> Result<COption<Pubkey>, ProgramError> {
     let (tag, body) = array_refs![src, 4, 32];
@@ -455,6 +559,55 @@ pub fn calculate_edition_number(
     Ok(edition)
 }
 
+/// Picks the lowest edition above the master edition's supply that is free in the given edition
+/// marker, which must be the marker covering supply + 1. When a concurrent print has moved the
+/// supply into the next marker, or taken every remaining edition in this one, the caller has to
+/// retry with a fresh marker.
+pub fn find_next_edition(
+    program_id: &Pubkey,
+    master_mint: &Pubkey,
+    master_edition_account_info: &AccountInfo,
+    edition_marker_info: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    let first_edition = get_supply_off_master_edition(master_edition_account_info)?
+        .checked_add(1)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+    let marker_number = first_edition
+        .checked_div(EDITION_MARKER_BIT_SIZE)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+    let as_string = marker_number.to_string();
+    let (edition_marker_key, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            master_mint.as_ref(),
+            EDITION.as_bytes(),
+            as_string.as_bytes(),
+        ],
+        program_id,
+    );
+    if edition_marker_key != *edition_marker_info.key {
+        return Err(MetadataError::NoFreeEditionInEditionMarker.into());
+    }
+
+    let marker_end = marker_number
+        .checked_add(1)
+        .and_then(|next| next.checked_mul(EDITION_MARKER_BIT_SIZE))
+        .ok_or(MetadataError::NumericalOverflowError)?;
+    if edition_marker_info.data_is_empty() {
+        return Ok(first_edition);
+    }
+
+    let edition_marker = EditionMarker::from_account_info(edition_marker_info)?;
+    for edition in first_edition..marker_end {
+        if !edition_marker.edition_taken(edition)? {
+            return Ok(edition);
+        }
+    }
+
+    Err(MetadataError::NoFreeEditionInEditionMarker.into())
+}
+
 fn get_max_supply_off_master_edition(
     master_edition_account_info: &AccountInfo,
 ) -> Result<Option<u64>, ProgramError> {
@@ -588,7 +741,16 @@ pub fn mint_limited_edition<'a>(
         master_metadata.data,
         true,
         false,
+        // Prints get a fresh set of the master's uses.
+        master_metadata.uses.map(|uses| Uses {
+            remaining: uses.total,
+            ..uses
+        }),
     )?;
+    let mut new_metadata = Metadata::from_account_info(new_metadata_account_info)?;
+    new_metadata.token_standard = Some(TokenStandard::NonFungibleEdition);
+    new_metadata.save(new_metadata_account_info)?;
+
     let edition_authority_seeds = &[
         PREFIX.as_bytes(),
         program_id.as_ref(),
@@ -708,6 +870,134 @@ pub fn spl_token_mint_to(params: TokenMintToParams<'_, '_>) -> ProgramResult {
     result.map_err(|_| MetadataError::TokenMintToFailed.into())
 }
 
+pub fn spl_token_close_account(params: TokenCloseAccountParams<'_, '_>) -> ProgramResult {
+    let TokenCloseAccountParams {
+        account,
+        destination,
+        authority,
+        token_program,
+        authority_signer_seeds,
+    } = params;
+    let mut seeds: Vec<&[&[u8]]> = vec![];
+    if let Some(seed) = authority_signer_seeds {
+        seeds.push(seed);
+    }
+    let result = invoke_signed(
+        &spl_token::instruction::close_account(
+            token_program.key,
+            account.key,
+            destination.key,
+            authority.key,
+            &[],
+        )?,
+        &[account, destination, authority, token_program],
+        seeds.as_slice(),
+    );
+    result.map_err(|_| MetadataError::TokenCloseFailed.into())
+}
+
+/// TokenCloseAccountParams
+pub struct TokenCloseAccountParams<'a: 'b, 'b> {
+    /// account
+    pub account: AccountInfo<'a>,
+    /// destination
+    pub destination: AccountInfo<'a>,
+    /// authority
+    pub authority: AccountInfo<'a>,
+    /// authority_signer_seeds
+    pub authority_signer_seeds: Option<&'b [&'b [u8]]>,
+    /// token_program
+    pub token_program: AccountInfo<'a>,
+}
+
+pub fn spl_token_approve(params: TokenApproveParams<'_, '_>) -> ProgramResult {
+    let TokenApproveParams {
+        source,
+        delegate,
+        authority,
+        token_program,
+        amount,
+        authority_signer_seeds,
+    } = params;
+    let mut seeds: Vec<&[&[u8]]> = vec![];
+    if let Some(seed) = authority_signer_seeds {
+        seeds.push(seed);
+    }
+    let result = invoke_signed(
+        &spl_token::instruction::approve(
+            token_program.key,
+            source.key,
+            delegate.key,
+            authority.key,
+            &[],
+            amount,
+        )?,
+        &[source, delegate, authority, token_program],
+        seeds.as_slice(),
+    );
+    result.map_err(|_| MetadataError::TokenApproveFailed.into())
+}
+
+/// TokenApproveParams
+pub struct TokenApproveParams<'a: 'b, 'b> {
+    /// source
+    pub source: AccountInfo<'a>,
+    /// delegate
+    pub delegate: AccountInfo<'a>,
+    /// amount
+    pub amount: u64,
+    /// authority
+    pub authority: AccountInfo<'a>,
+    /// authority_signer_seeds
+    pub authority_signer_seeds: Option<&'b [&'b [u8]]>,
+    /// token_program
+    pub token_program: AccountInfo<'a>,
+}
+
+pub fn spl_token_revoke(params: TokenRevokeParams<'_, '_>) -> ProgramResult {
+    let TokenRevokeParams {
+        source,
+        authority,
+        token_program,
+        authority_signer_seeds,
+    } = params;
+    let mut seeds: Vec<&[&[u8]]> = vec![];
+    if let Some(seed) = authority_signer_seeds {
+        seeds.push(seed);
+    }
+    let result = invoke_signed(
+        &spl_token::instruction::revoke(token_program.key, source.key, authority.key, &[])?,
+        &[source, authority, token_program],
+        seeds.as_slice(),
+    );
+    result.map_err(|_| MetadataError::TokenRevokeFailed.into())
+}
+
+/// TokenRevokeParams
+pub struct TokenRevokeParams<'a: 'b, 'b> {
+    /// source
+    pub source: AccountInfo<'a>,
+    /// authority
+    pub authority: AccountInfo<'a>,
+    /// authority_signer_seeds
+    pub authority_signer_seeds: Option<&'b [&'b [u8]]>,
+    /// token_program
+    pub token_program: AccountInfo<'a>,
+}
+
+/// Zero out a program-owned account and move all of its lamports to the receiver.
+pub fn close_program_account(account: &AccountInfo, receiver: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **receiver.lamports.borrow_mut() = receiver
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+    **account.lamports.borrow_mut() = 0;
+    account.data.borrow_mut().fill(0);
+
+    Ok(())
+}
+
 /// TokenMintToParams
 pub struct TokenMintToParams<'a: 'b, 'b> {
     /// mint
@@ -793,6 +1083,7 @@ pub fn process_create_metadata_accounts_logic(
     data: Data,
     allow_direct_creator_writes: bool,
     is_mutable: bool,
+    uses: Option<Uses>,
 ) -> ProgramResult {
     let CreateMetadataAccountsLogicArgs {
         metadata_account_info,
@@ -843,12 +1134,22 @@ pub fn process_create_metadata_accounts_logic(
         &metadata,
         allow_direct_creator_writes,
     )?;
+    if let Some(uses) = &uses {
+        assert_uses_valid(uses)?;
+    }
 
     metadata.mint = *mint_info.key;
     metadata.key = Key::MetadataV1;
     metadata.data = data;
     metadata.is_mutable = is_mutable;
     metadata.update_authority = *update_authority_info.key;
+    metadata.uses = uses;
+
+    // A mint with zero decimals may still get a master edition, so only one with decimals can be told apart yet.
+    let mint: Mint = assert_initialized(mint_info)?;
+    if mint.decimals > 0 {
+        metadata.token_standard = Some(TokenStandard::Fungible);
+    }
 
     puff_out_data_fields(&mut metadata);
 
@@ -861,7 +1162,7 @@ pub fn process_create_metadata_accounts_logic(
     let (_, edition_bump_seed) = Pubkey::find_program_address(edition_seeds, program_id);
     metadata.edition_nonce = Some(edition_bump_seed);
 
-    metadata.serialize(&mut *metadata_account_info.data.borrow_mut())?;
+    metadata.save(metadata_account_info)?;
 
     Ok(())
 }
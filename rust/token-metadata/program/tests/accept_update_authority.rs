@@ -0,0 +1,162 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use spl_token_metadata::{
+    error::MetadataError,
+    id,
+    instruction::{self, find_pending_update_authority_account},
+};
+use utils::*;
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+    let new_update_authority = Keypair::new();
+
+    test_metadata
+        .set_pending_update_authority(&mut context, &new_update_authority.pubkey())
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, context.payer.pubkey());
+
+    test_metadata
+        .accept_update_authority(&mut context, &new_update_authority)
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, new_update_authority.pubkey());
+
+    let (pending, _) = find_pending_update_authority_account(&id(), &test_metadata.mint.pubkey());
+    assert!(context
+        .banks_client
+        .get_account(pending)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn fail_not_pending_update_authority() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+    let new_update_authority = Keypair::new();
+
+    test_metadata
+        .set_pending_update_authority(&mut context, &new_update_authority.pubkey())
+        .await
+        .unwrap();
+    let result = test_metadata
+        .accept_update_authority(&mut context, &Keypair::new())
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::PendingUpdateAuthorityMismatch);
+}
+
+#[tokio::test]
+async fn fail_cancelled() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+    let new_update_authority = Keypair::new();
+
+    test_metadata
+        .set_pending_update_authority(&mut context, &new_update_authority.pubkey())
+        .await
+        .unwrap();
+    test_metadata
+        .cancel_pending_update_authority(&mut context)
+        .await
+        .unwrap();
+    let result = test_metadata
+        .accept_update_authority(&mut context, &new_update_authority)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::IncorrectOwner);
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, context.payer.pubkey());
+}
+
+#[tokio::test]
+async fn fail_stale_after_direct_transfer() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+    let new_update_authority = Keypair::new();
+    let other_update_authority = Keypair::new();
+
+    test_metadata
+        .set_pending_update_authority(&mut context, &new_update_authority.pubkey())
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_metadata_accounts(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            Some(other_update_authority.pubkey()),
+            None,
+            None,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let result = test_metadata
+        .accept_update_authority(&mut context, &new_update_authority)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::StalePendingUpdateAuthority);
+}
+
+#[tokio::test]
+async fn success_proposed_by_update_metadata_accounts() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+    let new_update_authority = Keypair::new();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::update_metadata_accounts_with_pending_update_authority(
+                id(),
+                test_metadata.pubkey,
+                test_metadata.mint.pubkey(),
+                context.payer.pubkey(),
+                new_update_authority.pubkey(),
+                context.payer.pubkey(),
+                None,
+                None,
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, context.payer.pubkey());
+
+    test_metadata
+        .accept_update_authority(&mut context, &new_update_authority)
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.update_authority, new_update_authority.pubkey());
+}
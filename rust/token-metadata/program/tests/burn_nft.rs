@@ -0,0 +1,190 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use spl_token_metadata::{
+    error::MetadataError,
+    id,
+    instruction::{
+        find_metadata_attributes_account, find_pending_update_authority_account, set_attributes,
+    },
+    state::{Attribute, UseMethod, Uses},
+};
+use utils::*;
+
+async fn account_is_closed(context: &mut ProgramTestContext, pubkey: &Pubkey) -> bool {
+    context
+        .banks_client
+        .get_account(*pubkey)
+        .await
+        .unwrap()
+        .is_none()
+}
+
+async fn create_master_edition_with_uses(
+    context: &mut ProgramTestContext,
+) -> (Metadata, MasterEditionV2) {
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    test_metadata
+        .create_v2(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            true,
+            Some(Uses {
+                use_method: UseMethod::Multiple,
+                remaining: 3,
+                total: 3,
+            }),
+        )
+        .await
+        .unwrap();
+
+    test_master_edition.create(context, Some(10)).await.unwrap();
+
+    (test_metadata, test_master_edition)
+}
+
+#[tokio::test]
+async fn success_master_edition() {
+    let mut context = program_test().start_with_context().await;
+    let (test_metadata, test_master_edition) = create_test_master_edition(&mut context).await;
+
+    test_metadata
+        .burn(&mut context, &test_master_edition)
+        .await
+        .unwrap();
+
+    assert!(account_is_closed(&mut context, &test_metadata.pubkey).await);
+    assert!(account_is_closed(&mut context, &test_master_edition.pubkey).await);
+    assert!(account_is_closed(&mut context, &test_metadata.token.pubkey()).await);
+
+    let mint = get_mint(&mut context, &test_metadata.mint.pubkey()).await;
+    assert_eq!(mint.supply, 0);
+}
+
+#[tokio::test]
+async fn success_print() {
+    let mut context = program_test().start_with_context().await;
+    let (test_metadata, test_master_edition) = create_test_master_edition(&mut context).await;
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+
+    test_edition_marker.create(&mut context).await.unwrap();
+    test_edition_marker.burn(&mut context).await.unwrap();
+
+    assert!(account_is_closed(&mut context, &test_edition_marker.new_metadata_pubkey).await);
+    assert!(account_is_closed(&mut context, &test_edition_marker.new_edition_pubkey).await);
+
+    let edition_marker = test_edition_marker.get_data(&mut context).await;
+    let master_edition = test_master_edition.get_data(&mut context).await;
+
+    assert_eq!(edition_marker.ledger[0], 0);
+    assert_eq!(master_edition.supply, 0);
+}
+
+#[tokio::test]
+async fn fail_master_edition_with_prints() {
+    let mut context = program_test().start_with_context().await;
+    let (test_metadata, test_master_edition) = create_test_master_edition(&mut context).await;
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+
+    test_edition_marker.create(&mut context).await.unwrap();
+
+    let result = test_metadata
+        .burn(&mut context, &test_master_edition)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::MasterEditionHasPrints);
+}
+
+#[tokio::test]
+async fn success_closes_attributes_and_pending_update_authority() {
+    let mut context = program_test().start_with_context().await;
+    let (test_metadata, test_master_edition) = create_master_edition_with_uses(&mut context).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[set_attributes(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            vec![Attribute {
+                trait_type: "Background".to_string(),
+                value: "Blue".to_string(),
+            }],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+    test_metadata
+        .set_pending_update_authority(&mut context, &Keypair::new().pubkey())
+        .await
+        .unwrap();
+
+    test_metadata
+        .burn(&mut context, &test_master_edition)
+        .await
+        .unwrap();
+
+    let (attributes, _) = find_metadata_attributes_account(&id(), &test_metadata.pubkey);
+    let (pending, _) = find_pending_update_authority_account(&id(), &test_metadata.mint.pubkey());
+    assert!(account_is_closed(&mut context, &test_metadata.pubkey).await);
+    assert!(account_is_closed(&mut context, &attributes).await);
+    assert!(account_is_closed(&mut context, &pending).await);
+}
+
+#[tokio::test]
+async fn success_after_revoking_use_authority() {
+    let mut context = program_test().start_with_context().await;
+    let (test_metadata, test_master_edition) = create_master_edition_with_uses(&mut context).await;
+    let use_authority = Keypair::new();
+
+    test_metadata
+        .approve_use_authority(&mut context, &use_authority.pubkey(), 1)
+        .await
+        .unwrap();
+    test_metadata
+        .revoke_use_authority(&mut context, &use_authority.pubkey())
+        .await
+        .unwrap();
+    test_metadata
+        .burn(&mut context, &test_master_edition)
+        .await
+        .unwrap();
+
+    assert!(account_is_closed(&mut context, &test_metadata.pubkey).await);
+}
+
+#[tokio::test]
+async fn fail_use_authority_records_outstanding() {
+    let mut context = program_test().start_with_context().await;
+    let (test_metadata, test_master_edition) = create_master_edition_with_uses(&mut context).await;
+    let use_authority = Keypair::new();
+
+    test_metadata
+        .approve_use_authority(&mut context, &use_authority.pubkey(), 1)
+        .await
+        .unwrap();
+
+    let result = test_metadata
+        .burn(&mut context, &test_master_edition)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::UseAuthorityRecordsOutstanding);
+}
@@ -10,7 +10,7 @@ use solana_sdk::{
     transport::TransportError,
 };
 use spl_token_metadata::error::MetadataError;
-use spl_token_metadata::state::Key;
+use spl_token_metadata::state::{Key, TokenStandard};
 use spl_token_metadata::{id, instruction};
 use utils::*;
 
@@ -51,6 +51,9 @@ async fn success() {
     assert_eq!(master_edition.supply, 0);
     assert_eq!(master_edition.max_supply.unwrap(), 10);
     assert_eq!(master_edition.key, Key::MasterEditionV2);
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.token_standard, Some(TokenStandard::NonFungible));
 }
 
 #[tokio::test]
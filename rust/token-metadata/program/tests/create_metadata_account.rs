@@ -45,6 +45,7 @@ async fn success() {
     assert_eq!(metadata.mint, test_metadata.mint.pubkey());
     assert_eq!(metadata.update_authority, context.payer.pubkey());
     assert_eq!(metadata.key, Key::MetadataV1);
+    assert_eq!(metadata.token_standard, None);
 }
 
 #[tokio::test]
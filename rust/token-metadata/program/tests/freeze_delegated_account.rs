@@ -0,0 +1,179 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use spl_token::state::{Account, AccountState};
+use spl_token_metadata::{error::MetadataError, id, instruction};
+use utils::*;
+
+/// Creates an NFT whose mint has a freeze authority, which the master edition takes over,
+/// and approves the delegate on the payer's token account
+async fn create_delegated_nft(
+    context: &mut ProgramTestContext,
+    delegate: &Keypair,
+) -> (Metadata, MasterEditionV2) {
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+    let payer_pubkey = context.payer.pubkey();
+
+    create_mint(
+        context,
+        &test_metadata.mint,
+        &payer_pubkey,
+        Some(&payer_pubkey),
+    )
+    .await
+    .unwrap();
+    create_token_account(
+        context,
+        &test_metadata.token,
+        &test_metadata.mint.pubkey(),
+        &payer_pubkey,
+    )
+    .await
+    .unwrap();
+    mint_tokens(
+        context,
+        &test_metadata.mint.pubkey(),
+        &test_metadata.token.pubkey(),
+        1,
+        &payer_pubkey,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::create_metadata_accounts(
+                id(),
+                test_metadata.pubkey,
+                test_metadata.mint.pubkey(),
+                payer_pubkey,
+                payer_pubkey,
+                payer_pubkey,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                false,
+            ),
+            spl_token::instruction::approve(
+                &spl_token::id(),
+                &test_metadata.token.pubkey(),
+                &delegate.pubkey(),
+                &payer_pubkey,
+                &[],
+                1,
+            )
+            .unwrap(),
+        ],
+        Some(&payer_pubkey),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    test_master_edition.create(context, Some(10)).await.unwrap();
+
+    (test_metadata, test_master_edition)
+}
+
+async fn freeze_or_thaw(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    test_master_edition: &MasterEditionV2,
+    delegate: &Keypair,
+    freeze: bool,
+) -> transport::Result<()> {
+    let builder = if freeze {
+        instruction::freeze_delegated_account
+    } else {
+        instruction::thaw_delegated_account
+    };
+    let tx = Transaction::new_signed_with_payer(
+        &[builder(
+            id(),
+            delegate.pubkey(),
+            test_metadata.token.pubkey(),
+            test_master_edition.pubkey,
+            test_metadata.mint.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, delegate],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn token_account_state(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+) -> AccountState {
+    let account = get_account(context, &test_metadata.token.pubkey()).await;
+    Account::unpack(&account.data).unwrap().state
+}
+
+#[tokio::test]
+async fn success_freeze_and_thaw() {
+    let mut context = program_test().start_with_context().await;
+    let delegate = Keypair::new();
+    let (test_metadata, test_master_edition) = create_delegated_nft(&mut context, &delegate).await;
+
+    freeze_or_thaw(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        &delegate,
+        true,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_account_state(&mut context, &test_metadata).await,
+        AccountState::Frozen
+    );
+
+    freeze_or_thaw(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        &delegate,
+        false,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        token_account_state(&mut context, &test_metadata).await,
+        AccountState::Initialized
+    );
+}
+
+#[tokio::test]
+async fn fail_not_delegate() {
+    let mut context = program_test().start_with_context().await;
+    let delegate = Keypair::new();
+    let (test_metadata, test_master_edition) = create_delegated_nft(&mut context, &delegate).await;
+
+    let result = freeze_or_thaw(
+        &mut context,
+        &test_metadata,
+        &test_master_edition,
+        &Keypair::new(),
+        true,
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::InvalidDelegate);
+}
@@ -0,0 +1,244 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use spl_token_metadata::{
+    error::MetadataError,
+    state::{Key, MasterEditionV2 as ProgramMasterEdition, ReservationV1},
+};
+use utils::*;
+
+async fn create_master_edition_v1(
+    context: &mut ProgramTestContext,
+    printing_token_account: &Keypair,
+    printing_token_owner: &Pubkey,
+) -> (Metadata, MasterEditionV1) {
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV1::new(&test_metadata);
+
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+    test_master_edition
+        .create(context, printing_token_account, printing_token_owner, 5)
+        .await
+        .unwrap();
+
+    (test_metadata, test_master_edition)
+}
+
+async fn fund(context: &mut ProgramTestContext, funder: &Keypair) {
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &funder.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let printing_token_account = Keypair::new();
+    let payer_pubkey = context.payer.pubkey();
+    let (_, test_master_edition) =
+        create_master_edition_v1(&mut context, &printing_token_account, &payer_pubkey).await;
+
+    test_master_edition
+        .migrate(&mut context, vec![printing_token_account.pubkey()], vec![])
+        .await
+        .unwrap();
+
+    let account = get_account(&mut context, &test_master_edition.pubkey).await;
+    let master_edition: ProgramMasterEdition = try_from_slice_unchecked(&account.data).unwrap();
+    assert_eq!(master_edition.key, Key::MasterEditionV2);
+    assert_eq!(master_edition.supply, 0);
+    assert_eq!(master_edition.max_supply, None);
+
+    let printing_mint = get_mint(&mut context, &test_master_edition.printing_mint.pubkey()).await;
+    assert_eq!(printing_mint.supply, 0);
+    assert!(context
+        .banks_client
+        .get_account(printing_token_account.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn fail_printing_tokens_held_by_others() {
+    let mut context = program_test().start_with_context().await;
+    let printing_token_account = Keypair::new();
+    let (_, test_master_edition) = create_master_edition_v1(
+        &mut context,
+        &printing_token_account,
+        &Keypair::new().pubkey(),
+    )
+    .await;
+
+    let result = test_master_edition
+        .migrate(&mut context, vec![], vec![])
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(
+        result,
+        MetadataError::PrintingMintSupplyMustBeZeroForConversion
+    );
+
+    let master_edition = test_master_edition.get_data(&mut context).await;
+    assert_eq!(master_edition.key, Key::MasterEditionV1);
+}
+
+#[tokio::test]
+async fn success_refunds_reservation_list_funder() {
+    let mut context = program_test().start_with_context().await;
+    let printing_token_account = Keypair::new();
+    let payer_pubkey = context.payer.pubkey();
+    let (_, test_master_edition) =
+        create_master_edition_v1(&mut context, &printing_token_account, &payer_pubkey).await;
+
+    let funder = Keypair::new();
+    fund(&mut context, &funder).await;
+    let reservation_list = test_master_edition
+        .create_reservation_list(&mut context, &funder, &Keypair::new().pubkey())
+        .await
+        .unwrap();
+    let list_lamports = get_account(&mut context, &reservation_list).await.lamports;
+    let funder_lamports = get_account(&mut context, &funder.pubkey()).await.lamports;
+
+    test_master_edition
+        .migrate(
+            &mut context,
+            vec![printing_token_account.pubkey()],
+            vec![(reservation_list, Some(funder.pubkey()))],
+        )
+        .await
+        .unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(reservation_list)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        get_account(&mut context, &funder.pubkey()).await.lamports,
+        funder_lamports + list_lamports
+    );
+
+    let master_edition = test_master_edition.get_data(&mut context).await;
+    assert_eq!(master_edition.key, Key::MasterEditionV2);
+}
+
+#[tokio::test]
+async fn success_closes_reservation_list_v1_to_owner() {
+    let mut context = program_test().start_with_context().await;
+    let printing_token_account = Keypair::new();
+    let payer_pubkey = context.payer.pubkey();
+    let (_, test_master_edition) =
+        create_master_edition_v1(&mut context, &printing_token_account, &payer_pubkey).await;
+
+    let reservation_list = test_master_edition.create_reservation_list_v1(
+        &mut context,
+        &Keypair::new().pubkey(),
+        vec![ReservationV1 {
+            address: Keypair::new().pubkey(),
+            spots_remaining: 0,
+            total_spots: 1,
+        }],
+    );
+    let list_lamports = get_account(&mut context, &reservation_list).await.lamports;
+    let token_account_lamports = get_account(&mut context, &printing_token_account.pubkey())
+        .await
+        .lamports;
+    let owner_lamports = get_account(&mut context, &payer_pubkey).await.lamports;
+
+    test_master_edition
+        .migrate(
+            &mut context,
+            vec![printing_token_account.pubkey()],
+            vec![(reservation_list, None)],
+        )
+        .await
+        .unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(reservation_list)
+        .await
+        .unwrap()
+        .is_none());
+    // The owner also pays the transaction fee
+    assert!(
+        get_account(&mut context, &payer_pubkey).await.lamports
+            > owner_lamports + token_account_lamports + list_lamports - 10_000
+    );
+
+    let master_edition = test_master_edition.get_data(&mut context).await;
+    assert_eq!(master_edition.key, Key::MasterEditionV2);
+}
+
+#[tokio::test]
+async fn fail_reservations_outstanding() {
+    let mut context = program_test().start_with_context().await;
+    let printing_token_account = Keypair::new();
+    let payer_pubkey = context.payer.pubkey();
+    let (_, test_master_edition) =
+        create_master_edition_v1(&mut context, &printing_token_account, &payer_pubkey).await;
+
+    let funder = Keypair::new();
+    fund(&mut context, &funder).await;
+    let resource = Keypair::new();
+    let reservation_list = test_master_edition
+        .create_reservation_list(&mut context, &funder, &resource.pubkey())
+        .await
+        .unwrap();
+    test_master_edition
+        .set_reservation(
+            &mut context,
+            &reservation_list,
+            &resource,
+            &Keypair::new().pubkey(),
+            1,
+        )
+        .await
+        .unwrap();
+
+    let result = test_master_edition
+        .migrate(
+            &mut context,
+            vec![printing_token_account.pubkey()],
+            vec![(reservation_list, Some(funder.pubkey()))],
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::ReservationsOutstanding);
+
+    let master_edition = test_master_edition.get_data(&mut context).await;
+    assert_eq!(master_edition.key, Key::MasterEditionV1);
+}
@@ -1,6 +1,7 @@
 mod utils;
 
 use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
 use solana_program_test::*;
 use solana_sdk::{
     instruction::InstructionError,
@@ -9,7 +10,7 @@ use solana_sdk::{
     transport::TransportError,
 };
 use spl_token_metadata::error::MetadataError;
-use spl_token_metadata::state::Key;
+use spl_token_metadata::state::{Key, TokenStandard};
 use spl_token_metadata::{id, instruction};
 use utils::*;
 
@@ -44,6 +45,14 @@ async fn success() {
 
     assert_eq!(edition_marker.ledger[0], 64);
     assert_eq!(edition_marker.key, Key::EditionMarker);
+
+    let account = get_account(&mut context, &test_edition_marker.new_metadata_pubkey).await;
+    let new_metadata: spl_token_metadata::state::Metadata =
+        try_from_slice_unchecked(&account.data).unwrap();
+    assert_eq!(
+        new_metadata.token_standard,
+        Some(TokenStandard::NonFungibleEdition)
+    );
 }
 
 #[tokio::test]
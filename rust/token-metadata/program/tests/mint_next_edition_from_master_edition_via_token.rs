@@ -0,0 +1,54 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, transaction::TransactionError, transport::TransportError,
+};
+use spl_token_metadata::error::MetadataError;
+use spl_token_metadata::state::Edition;
+use utils::*;
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let (test_metadata, test_master_edition) = create_test_master_edition(&mut context).await;
+    let first_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+    let second_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 2);
+    let third_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 3);
+
+    first_edition_marker
+        .create_next(&mut context, 0)
+        .await
+        .unwrap();
+    second_edition_marker.create(&mut context).await.unwrap();
+    // A stale supply still lands on the next free edition within the same marker.
+    third_edition_marker
+        .create_next(&mut context, 0)
+        .await
+        .unwrap();
+
+    let account = get_account(&mut context, &third_edition_marker.new_edition_pubkey).await;
+    let edition: Edition = try_from_slice_unchecked(&account.data).unwrap();
+    let edition_marker = third_edition_marker.get_data(&mut context).await;
+    let master_edition = test_master_edition.get_data(&mut context).await;
+
+    assert_eq!(edition.edition, 3);
+    assert_eq!(edition_marker.ledger[0], 64 + 32 + 16);
+    assert_eq!(master_edition.supply, 3);
+}
+
+#[tokio::test]
+async fn fail_edition_marker_out_of_range() {
+    let mut context = program_test().start_with_context().await;
+    let (test_metadata, test_master_edition) = create_test_master_edition(&mut context).await;
+    let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 248);
+
+    let result = test_edition_marker
+        .create_next(&mut context, 247)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::NoFreeEditionInEditionMarker);
+}
@@ -0,0 +1,185 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, InstructionError},
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use spl_token_metadata::{
+    error::MetadataError,
+    id, instruction,
+    state::{Creator, Data},
+};
+use utils::*;
+
+fn creators(update_authority: &Keypair, creator: &Keypair, share: u8) -> Vec<Creator> {
+    vec![
+        Creator {
+            address: update_authority.pubkey(),
+            verified: true,
+            share: 100 - share,
+        },
+        Creator {
+            address: creator.pubkey(),
+            verified: true,
+            share,
+        },
+    ]
+}
+
+/// Creates a metadata with the payer and creator verified at 50% each, requiring creator consent
+async fn create_metadata_requiring_consent(
+    context: &mut ProgramTestContext,
+    creator: &Keypair,
+) -> Metadata {
+    let mut initial_creators = creators(&context.payer, creator, 50);
+    initial_creators[1].verified = false;
+    let test_metadata = create_test_metadata(context, Some(initial_creators), true).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::sign_metadata(id(), test_metadata.pubkey, creator.pubkey()),
+            instruction::require_creator_consent(
+                id(),
+                test_metadata.pubkey,
+                context.payer.pubkey(),
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, creator],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    test_metadata
+}
+
+async fn update(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    creators: Vec<Creator>,
+    seller_fee_basis_points: u16,
+    consenting_creator: Option<&Keypair>,
+) -> transport::Result<()> {
+    let mut update_instruction = instruction::update_metadata_accounts(
+        id(),
+        test_metadata.pubkey,
+        context.payer.pubkey(),
+        None,
+        Some(Data {
+            name: "Test".to_string(),
+            symbol: "TST".to_string(),
+            uri: "uri".to_string(),
+            creators: Some(creators),
+            seller_fee_basis_points,
+        }),
+        None,
+    );
+    let mut signers = vec![&context.payer];
+    if let Some(creator) = consenting_creator {
+        update_instruction
+            .accounts
+            .push(AccountMeta::new_readonly(creator.pubkey(), true));
+        signers.push(creator);
+    }
+
+    let tx = Transaction::new_signed_with_payer(
+        &[update_instruction],
+        Some(&context.payer.pubkey()),
+        &signers,
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success_with_consent() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let test_metadata = create_metadata_requiring_consent(&mut context, &creator).await;
+
+    let new_creators = creators(&context.payer, &creator, 40);
+    update(
+        &mut context,
+        &test_metadata,
+        new_creators,
+        500,
+        Some(&creator),
+    )
+    .await
+    .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert!(metadata.creator_consent_required);
+    assert_eq!(metadata.data.seller_fee_basis_points, 500);
+    assert_eq!(metadata.data.creators.unwrap()[1].share, 40);
+}
+
+#[tokio::test]
+async fn success_unrelated_change_without_consent() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let test_metadata = create_metadata_requiring_consent(&mut context, &creator).await;
+
+    let same_creators = creators(&context.payer, &creator, 50);
+    update(&mut context, &test_metadata, same_creators, 10, None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn fail_royalty_change_without_consent() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let test_metadata = create_metadata_requiring_consent(&mut context, &creator).await;
+
+    let same_creators = creators(&context.payer, &creator, 50);
+    let result = update(&mut context, &test_metadata, same_creators, 500, None)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::CreatorConsentRequired);
+}
+
+#[tokio::test]
+async fn fail_removing_verified_creator_without_consent() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let test_metadata = create_metadata_requiring_consent(&mut context, &creator).await;
+
+    let mut remaining_creators = creators(&context.payer, &creator, 0);
+    remaining_creators.pop();
+    let result = update(&mut context, &test_metadata, remaining_creators, 10, None)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::CreatorConsentRequired);
+}
+
+#[tokio::test]
+async fn success_remove_creator_verification() {
+    let mut context = program_test().start_with_context().await;
+    let creator = Keypair::new();
+    let test_metadata = create_metadata_requiring_consent(&mut context, &creator).await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::remove_creator_verification(
+            id(),
+            test_metadata.pubkey,
+            creator.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &creator],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    let metadata_creators = metadata.data.creators.unwrap();
+    assert!(metadata_creators[0].verified);
+    assert!(!metadata_creators[1].verified);
+}
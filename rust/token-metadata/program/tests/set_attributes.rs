@@ -0,0 +1,243 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use spl_token_metadata::{
+    error::MetadataError,
+    id,
+    instruction::{self, find_metadata_attributes_account},
+    state::{
+        Attribute, MetadataAttributes, MAX_ATTRIBUTES, MAX_METADATA_ATTRIBUTES_LEN,
+        MAX_TRAIT_TYPE_LENGTH, MAX_TRAIT_VALUE_LENGTH,
+    },
+};
+use utils::*;
+
+fn attribute(trait_type: &str, value: &str) -> Attribute {
+    Attribute {
+        trait_type: trait_type.to_string(),
+        value: value.to_string(),
+    }
+}
+
+async fn set_attributes(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    attributes: Vec<Attribute>,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::set_attributes(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            attributes,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn remove_attributes(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    trait_types: Vec<String>,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::remove_attributes(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            context.payer.pubkey(),
+            trait_types,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn get_attributes(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+) -> Option<MetadataAttributes> {
+    let (pubkey, _) = find_metadata_attributes_account(&id(), &test_metadata.pubkey);
+    context
+        .banks_client
+        .get_account(pubkey)
+        .await
+        .unwrap()
+        .map(|account| MetadataAttributes::from_bytes(&account.data).unwrap())
+}
+
+#[tokio::test]
+async fn success_set_and_replace() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+
+    set_attributes(
+        &mut context,
+        &test_metadata,
+        vec![attribute("Background", "Blue"), attribute("Eyes", "Laser")],
+    )
+    .await
+    .unwrap();
+    set_attributes(
+        &mut context,
+        &test_metadata,
+        vec![attribute("Eyes", "Sleepy and a little bit bored")],
+    )
+    .await
+    .unwrap();
+
+    let attributes = get_attributes(&mut context, &test_metadata).await.unwrap();
+    assert_eq!(attributes.metadata, test_metadata.pubkey);
+    assert_eq!(attributes.attributes.len(), 2);
+    assert_eq!(attributes.get("Background"), Some("Blue"));
+    assert_eq!(
+        attributes.get("Eyes"),
+        Some("Sleepy and a little bit bored")
+    );
+    assert_eq!(attributes.get("Hat"), None);
+}
+
+#[tokio::test]
+async fn success_max_attributes() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+    let attributes: Vec<Attribute> = (0..MAX_ATTRIBUTES)
+        .map(|i| Attribute {
+            trait_type: format!("{:0>width$}", i, width = MAX_TRAIT_TYPE_LENGTH),
+            value: "v".repeat(MAX_TRAIT_VALUE_LENGTH),
+        })
+        .collect();
+
+    // A few at a time to stay within the transaction size limit
+    for chunk in attributes.chunks(4) {
+        set_attributes(&mut context, &test_metadata, chunk.to_vec())
+            .await
+            .unwrap();
+    }
+
+    let (pubkey, _) = find_metadata_attributes_account(&id(), &test_metadata.pubkey);
+    let account = get_account(&mut context, &pubkey).await;
+    assert_eq!(account.data.len(), MAX_METADATA_ATTRIBUTES_LEN);
+    assert_eq!(
+        MetadataAttributes::from_bytes(&account.data)
+            .unwrap()
+            .attributes,
+        attributes
+    );
+}
+
+#[tokio::test]
+async fn success_remove() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+
+    set_attributes(
+        &mut context,
+        &test_metadata,
+        vec![attribute("Background", "Blue"), attribute("Eyes", "Laser")],
+    )
+    .await
+    .unwrap();
+    remove_attributes(&mut context, &test_metadata, vec!["Background".to_string()])
+        .await
+        .unwrap();
+
+    let attributes = get_attributes(&mut context, &test_metadata).await.unwrap();
+    assert_eq!(attributes.attributes, vec![attribute("Eyes", "Laser")]);
+
+    remove_attributes(&mut context, &test_metadata, vec!["Eyes".to_string()])
+        .await
+        .unwrap();
+
+    assert!(get_attributes(&mut context, &test_metadata).await.is_none());
+}
+
+#[tokio::test]
+async fn fail_remove_to_non_signing_payer() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+
+    set_attributes(
+        &mut context,
+        &test_metadata,
+        vec![attribute("Background", "Blue")],
+    )
+    .await
+    .unwrap();
+
+    let mut remove_instruction = instruction::remove_attributes(
+        id(),
+        test_metadata.pubkey,
+        context.payer.pubkey(),
+        Keypair::new().pubkey(),
+        vec!["Background".to_string()],
+    );
+    remove_instruction.accounts[3].is_signer = false;
+    let tx = Transaction::new_signed_with_payer(
+        &[remove_instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let result: transport::Result<()> = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .map_err(Into::into);
+
+    assert!(matches!(
+        result.unwrap_err(),
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::MissingRequiredSignature
+        ))
+    ));
+    assert!(get_attributes(&mut context, &test_metadata).await.is_some());
+}
+
+#[tokio::test]
+async fn fail_immutable() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_test_metadata(&mut context, None, false).await;
+
+    let result = set_attributes(
+        &mut context,
+        &test_metadata,
+        vec![attribute("Background", "Blue")],
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::DataIsImmutable);
+}
+
+#[tokio::test]
+async fn fail_attribute_too_long() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+
+    let result = set_attributes(
+        &mut context,
+        &test_metadata,
+        vec![attribute("Background", &"Blue".repeat(20))],
+    )
+    .await
+    .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::AttributeTooLong);
+}
@@ -0,0 +1,104 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, InstructionError},
+    signature::Signer,
+    transaction::{Transaction, TransactionError},
+    transport::{self, TransportError},
+};
+use spl_token_metadata::{error::MetadataError, id, instruction, state::TokenStandard};
+use utils::*;
+
+/// Creates a master edition the way older clients do, passing the metadata read-only
+async fn create_with_read_only_metadata(
+    context: &mut ProgramTestContext,
+) -> (Metadata, MasterEditionV2) {
+    let test_metadata = Metadata::new();
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+
+    let mut create_instruction = instruction::create_master_edition(
+        id(),
+        test_master_edition.pubkey,
+        test_master_edition.mint_pubkey,
+        context.payer.pubkey(),
+        context.payer.pubkey(),
+        test_metadata.pubkey,
+        context.payer.pubkey(),
+        Some(10),
+    );
+    create_instruction.accounts[5] = AccountMeta::new_readonly(test_metadata.pubkey, false);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    (test_metadata, test_master_edition)
+}
+
+async fn set_token_standard(
+    context: &mut ProgramTestContext,
+    test_metadata: &Metadata,
+    edition: Option<&MasterEditionV2>,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::set_token_standard(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            test_metadata.mint.pubkey(),
+            edition.map(|edition| edition.pubkey),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let (test_metadata, test_master_edition) = create_with_read_only_metadata(&mut context).await;
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.token_standard, None);
+
+    set_token_standard(&mut context, &test_metadata, Some(&test_master_edition))
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.token_standard, Some(TokenStandard::NonFungible));
+}
+
+#[tokio::test]
+async fn fail_missing_edition() {
+    let mut context = program_test().start_with_context().await;
+    let (test_metadata, _) = create_with_read_only_metadata(&mut context).await;
+
+    let result = set_token_standard(&mut context, &test_metadata, None)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::InvalidEditionKey);
+}
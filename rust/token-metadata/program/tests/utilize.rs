@@ -0,0 +1,287 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use spl_token::instruction::{set_authority, AuthorityType};
+use spl_token_metadata::{
+    error::MetadataError,
+    id,
+    instruction::{self, find_use_authority_account},
+    state::{UseAuthorityRecord, UseMethod, Uses},
+};
+use utils::*;
+
+async fn create_with_uses(
+    context: &mut ProgramTestContext,
+    use_method: UseMethod,
+    total: u64,
+) -> Metadata {
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create_v2(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+            Some(Uses {
+                use_method,
+                remaining: total,
+                total,
+            }),
+        )
+        .await
+        .unwrap();
+
+    test_metadata
+}
+
+#[tokio::test]
+async fn success_owner_multiple() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_with_uses(&mut context, UseMethod::Multiple, 3).await;
+
+    test_metadata.utilize(&mut context, None, 2).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    let uses = metadata.uses.unwrap();
+    assert_eq!(uses.use_method, UseMethod::Multiple);
+    assert_eq!(uses.remaining, 1);
+    assert_eq!(uses.total, 3);
+
+    let mint = get_mint(&mut context, &test_metadata.mint.pubkey()).await;
+    assert_eq!(mint.supply, 1);
+}
+
+#[tokio::test]
+async fn success_owner_burn() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_with_uses(&mut context, UseMethod::Burn, 1).await;
+
+    test_metadata.utilize(&mut context, None, 1).await.unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(metadata.uses.unwrap().remaining, 0);
+
+    let mint = get_mint(&mut context, &test_metadata.mint.pubkey()).await;
+    assert_eq!(mint.supply, 0);
+}
+
+#[tokio::test]
+async fn success_use_authority_burn() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_with_uses(&mut context, UseMethod::Burn, 2).await;
+    let use_authority = Keypair::new();
+
+    test_metadata
+        .approve_use_authority(&mut context, &use_authority.pubkey(), 2)
+        .await
+        .unwrap();
+    test_metadata
+        .utilize(&mut context, Some(&use_authority), 2)
+        .await
+        .unwrap();
+
+    let (record_pubkey, _) =
+        find_use_authority_account(&id(), &test_metadata.mint.pubkey(), &use_authority.pubkey());
+    let record_account = get_account(&mut context, &record_pubkey).await;
+    let record: UseAuthorityRecord = try_from_slice_unchecked(&record_account.data).unwrap();
+    assert_eq!(record.allowed_uses, 0);
+
+    let mint = get_mint(&mut context, &test_metadata.mint.pubkey()).await;
+    assert_eq!(mint.supply, 0);
+}
+
+#[tokio::test]
+async fn fail_not_enough_uses() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_with_uses(&mut context, UseMethod::Multiple, 3).await;
+
+    test_metadata.utilize(&mut context, None, 1).await.unwrap();
+    let result = test_metadata
+        .utilize(&mut context, None, 3)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::NotEnoughUses);
+}
+
+#[tokio::test]
+async fn fail_use_authority_allowance_exceeded() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_with_uses(&mut context, UseMethod::Multiple, 3).await;
+    let use_authority = Keypair::new();
+
+    test_metadata
+        .approve_use_authority(&mut context, &use_authority.pubkey(), 1)
+        .await
+        .unwrap();
+    let result = test_metadata
+        .utilize(&mut context, Some(&use_authority), 2)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::UseAuthorityAllowanceExceeded);
+}
+
+#[tokio::test]
+async fn fail_revoked_use_authority() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_with_uses(&mut context, UseMethod::Burn, 1).await;
+    let use_authority = Keypair::new();
+
+    test_metadata
+        .approve_use_authority(&mut context, &use_authority.pubkey(), 1)
+        .await
+        .unwrap();
+    test_metadata
+        .revoke_use_authority(&mut context, &use_authority.pubkey())
+        .await
+        .unwrap();
+    let result = test_metadata
+        .utilize(&mut context, Some(&use_authority), 1)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::IncorrectOwner);
+}
+
+#[tokio::test]
+async fn success_revoke_keeps_other_burn_delegate() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_with_uses(&mut context, UseMethod::Burn, 1).await;
+    let first_use_authority = Keypair::new();
+    let second_use_authority = Keypair::new();
+
+    test_metadata
+        .approve_use_authority(&mut context, &first_use_authority.pubkey(), 1)
+        .await
+        .unwrap();
+    test_metadata
+        .approve_use_authority(&mut context, &second_use_authority.pubkey(), 1)
+        .await
+        .unwrap();
+    test_metadata
+        .revoke_use_authority(&mut context, &first_use_authority.pubkey())
+        .await
+        .unwrap();
+    test_metadata
+        .utilize(&mut context, Some(&second_use_authority), 1)
+        .await
+        .unwrap();
+
+    let mint = get_mint(&mut context, &test_metadata.mint.pubkey()).await;
+    assert_eq!(mint.supply, 0);
+}
+
+#[tokio::test]
+async fn fail_use_authority_approved_by_previous_owner() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = create_with_uses(&mut context, UseMethod::Multiple, 3).await;
+    let use_authority = Keypair::new();
+    let new_owner = Keypair::new();
+
+    test_metadata
+        .approve_use_authority(&mut context, &use_authority.pubkey(), 3)
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[set_authority(
+            &spl_token::id(),
+            &test_metadata.token.pubkey(),
+            Some(&new_owner.pubkey()),
+            AuthorityType::AccountOwner,
+            &context.payer.pubkey(),
+            &[],
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::utilize(
+            id(),
+            test_metadata.pubkey,
+            test_metadata.token.pubkey(),
+            test_metadata.mint.pubkey(),
+            use_authority.pubkey(),
+            new_owner.pubkey(),
+            1,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &use_authority],
+        context.last_blockhash,
+    );
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::UseAuthorityApprovedByPreviousOwner);
+}
+
+#[tokio::test]
+async fn fail_without_uses() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+        )
+        .await
+        .unwrap();
+    let result = test_metadata
+        .utilize(&mut context, None, 1)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::Unusable);
+}
+
+#[tokio::test]
+async fn fail_invalid_single_use() {
+    let mut context = program_test().start_with_context().await;
+    let test_metadata = Metadata::new();
+
+    let result = test_metadata
+        .create_v2(
+            &mut context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            None,
+            10,
+            false,
+            Some(Uses {
+                use_method: UseMethod::Single,
+                remaining: 2,
+                total: 2,
+            }),
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::InvalidUseConfiguration);
+}
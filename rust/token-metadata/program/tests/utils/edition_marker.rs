@@ -191,6 +191,55 @@ impl EditionMarker {
         Ok(context.banks_client.process_transaction(tx).await?)
     }
 
+    pub async fn create_next(
+        &self,
+        context: &mut ProgramTestContext,
+        master_edition_supply: u64,
+    ) -> transport::Result<()> {
+        create_mint(context, &self.mint, &context.payer.pubkey(), None).await?;
+        create_token_account(
+            context,
+            &self.token,
+            &self.mint.pubkey(),
+            &context.payer.pubkey(),
+        )
+        .await?;
+        mint_tokens(
+            context,
+            &self.mint.pubkey(),
+            &self.token.pubkey(),
+            1,
+            &context.payer.pubkey(),
+            None,
+        )
+        .await?;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                instruction::mint_next_edition_from_master_edition_via_token(
+                    id(),
+                    self.new_metadata_pubkey,
+                    self.new_edition_pubkey,
+                    self.master_edition_pubkey,
+                    self.mint.pubkey(),
+                    context.payer.pubkey(),
+                    context.payer.pubkey(),
+                    context.payer.pubkey(),
+                    self.metadata_token_pubkey,
+                    context.payer.pubkey(),
+                    self.metadata_pubkey,
+                    self.metadata_mint_pubkey,
+                    master_edition_supply,
+                ),
+            ],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
     pub async fn create_with_invalid_token_program(
         &self,
         context: &mut ProgramTestContext,
@@ -249,4 +298,27 @@ impl EditionMarker {
 
         Ok(context.banks_client.process_transaction(tx).await?)
     }
+
+    pub async fn burn(&self, context: &mut ProgramTestContext) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::burn_nft(
+                id(),
+                self.new_metadata_pubkey,
+                context.payer.pubkey(),
+                self.mint.pubkey(),
+                self.token.pubkey(),
+                self.new_edition_pubkey,
+                Some((
+                    self.master_edition_pubkey,
+                    self.metadata_mint_pubkey,
+                    self.edition,
+                )),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
 }
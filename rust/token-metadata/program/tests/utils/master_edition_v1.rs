@@ -0,0 +1,260 @@
+use crate::*;
+use borsh::BorshSerialize;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program_test::*;
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Signer,
+    signer::keypair::Keypair,
+    transaction::Transaction,
+    transport,
+};
+use spl_token_metadata::{
+    deprecated_instruction, id, instruction,
+    state::{
+        Key, Reservation, ReservationListV1, ReservationV1, EDITION, MAX_RESERVATION_LIST_V1_SIZE,
+        PREFIX, RESERVATION,
+    },
+};
+
+#[derive(Debug)]
+pub struct MasterEditionV1 {
+    pub pubkey: Pubkey,
+    pub metadata_pubkey: Pubkey,
+    pub mint_pubkey: Pubkey,
+    pub token_pubkey: Pubkey,
+    pub printing_mint: Keypair,
+    pub one_time_printing_authorization_mint: Keypair,
+}
+
+impl MasterEditionV1 {
+    pub fn new(metadata: &Metadata) -> Self {
+        let program_id = id();
+        let mint_pubkey = metadata.mint.pubkey();
+
+        let master_edition_seeds = &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            mint_pubkey.as_ref(),
+            EDITION.as_bytes(),
+        ];
+        let (pubkey, _) = Pubkey::find_program_address(master_edition_seeds, &id());
+
+        MasterEditionV1 {
+            pubkey,
+            metadata_pubkey: metadata.pubkey,
+            mint_pubkey,
+            token_pubkey: metadata.token.pubkey(),
+            printing_mint: Keypair::new(),
+            one_time_printing_authorization_mint: Keypair::new(),
+        }
+    }
+
+    pub async fn get_data(
+        &self,
+        context: &mut ProgramTestContext,
+    ) -> spl_token_metadata::state::MasterEditionV1 {
+        let account = get_account(context, &self.pubkey).await;
+        try_from_slice_unchecked(&account.data).unwrap()
+    }
+
+    /// Creates the printing mints and the master edition, minting printing_tokens
+    /// printing tokens into printing_token_account first, which must be owned by printing_token_owner
+    pub async fn create(
+        &self,
+        context: &mut ProgramTestContext,
+        printing_token_account: &Keypair,
+        printing_token_owner: &Pubkey,
+        printing_tokens: u64,
+    ) -> transport::Result<()> {
+        let payer_pubkey = context.payer.pubkey();
+        create_mint(context, &self.printing_mint, &payer_pubkey, None).await?;
+        create_mint(
+            context,
+            &self.one_time_printing_authorization_mint,
+            &payer_pubkey,
+            None,
+        )
+        .await?;
+        create_token_account(
+            context,
+            printing_token_account,
+            &self.printing_mint.pubkey(),
+            printing_token_owner,
+        )
+        .await?;
+        if printing_tokens > 0 {
+            mint_tokens(
+                context,
+                &self.printing_mint.pubkey(),
+                &printing_token_account.pubkey(),
+                printing_tokens,
+                &payer_pubkey,
+                None,
+            )
+            .await?;
+        }
+
+        let tx = Transaction::new_signed_with_payer(
+            &[deprecated_instruction::deprecated_create_master_edition(
+                id(),
+                self.pubkey,
+                self.mint_pubkey,
+                self.printing_mint.pubkey(),
+                self.one_time_printing_authorization_mint.pubkey(),
+                payer_pubkey,
+                payer_pubkey,
+                payer_pubkey,
+                self.metadata_pubkey,
+                payer_pubkey,
+                None,
+                None,
+            )],
+            Some(&payer_pubkey),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
+    /// Creates a reservation list for resource, paid for by funder
+    pub async fn create_reservation_list(
+        &self,
+        context: &mut ProgramTestContext,
+        funder: &Keypair,
+        resource: &Pubkey,
+    ) -> transport::Result<Pubkey> {
+        let program_id = id();
+        let (reservation_list, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                self.pubkey.as_ref(),
+                RESERVATION.as_bytes(),
+                resource.as_ref(),
+            ],
+            &program_id,
+        );
+
+        let tx = Transaction::new_signed_with_payer(
+            &[deprecated_instruction::deprecated_create_reservation_list(
+                program_id,
+                reservation_list,
+                funder.pubkey(),
+                context.payer.pubkey(),
+                self.pubkey,
+                *resource,
+                self.metadata_pubkey,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, funder],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await?;
+
+        Ok(reservation_list)
+    }
+
+    /// Writes a Reservation List V1 for resource straight into the bank, since only the
+    /// long removed create instruction made V1 lists and none of them recorded a funder
+    pub fn create_reservation_list_v1(
+        &self,
+        context: &mut ProgramTestContext,
+        resource: &Pubkey,
+        reservations: Vec<ReservationV1>,
+    ) -> Pubkey {
+        let program_id = id();
+        let (reservation_list, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                program_id.as_ref(),
+                self.pubkey.as_ref(),
+                RESERVATION.as_bytes(),
+                resource.as_ref(),
+            ],
+            &program_id,
+        );
+
+        let mut data = ReservationListV1 {
+            key: Key::ReservationListV1,
+            master_edition: self.pubkey,
+            supply_snapshot: Some(0),
+            reservations,
+        }
+        .try_to_vec()
+        .unwrap();
+        data.resize(MAX_RESERVATION_LIST_V1_SIZE, 0);
+
+        let account = Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        };
+        context.set_account(&reservation_list, &AccountSharedData::from(account));
+
+        reservation_list
+    }
+
+    /// Reserves spots editions for address on the reservation list of resource
+    pub async fn set_reservation(
+        &self,
+        context: &mut ProgramTestContext,
+        reservation_list: &Pubkey,
+        resource: &Keypair,
+        address: &Pubkey,
+        spots: u64,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[deprecated_instruction::deprecated_set_reservation_list(
+                id(),
+                self.pubkey,
+                *reservation_list,
+                resource.pubkey(),
+                vec![Reservation {
+                    address: *address,
+                    spots_remaining: spots,
+                    total_spots: spots,
+                }],
+                Some(spots),
+                0,
+                0,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, resource],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
+    pub async fn migrate(
+        &self,
+        context: &mut ProgramTestContext,
+        owned_token_accounts: Vec<Pubkey>,
+        reservation_lists: Vec<(Pubkey, Option<Pubkey>)>,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::migrate_master_edition_v1_to_v2(
+                id(),
+                self.pubkey,
+                self.metadata_pubkey,
+                context.payer.pubkey(),
+                self.token_pubkey,
+                self.one_time_printing_authorization_mint.pubkey(),
+                self.printing_mint.pubkey(),
+                owned_token_accounts,
+                reservation_lists,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+}
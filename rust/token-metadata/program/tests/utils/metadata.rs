@@ -7,7 +7,7 @@ use solana_sdk::{
 };
 use spl_token_metadata::{
     id, instruction,
-    state::{Creator, Data, PREFIX},
+    state::{Creator, Data, Uses, PREFIX},
 };
 
 #[derive(Debug)]
@@ -143,4 +143,270 @@ impl Metadata {
 
         Ok(context.banks_client.process_transaction(tx).await?)
     }
+
+    pub async fn verify_collection(
+        &self,
+        context: &mut ProgramTestContext,
+        collection: &Metadata,
+        collection_master_edition: &MasterEditionV2,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::verify_collection(
+                id(),
+                self.pubkey,
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                collection.mint.pubkey(),
+                collection.pubkey,
+                collection_master_edition.pubkey,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
+    pub async fn unverify_collection(
+        &self,
+        context: &mut ProgramTestContext,
+        collection: &Metadata,
+        collection_master_edition: &MasterEditionV2,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::unverify_collection(
+                id(),
+                self.pubkey,
+                context.payer.pubkey(),
+                collection.mint.pubkey(),
+                collection.pubkey,
+                collection_master_edition.pubkey,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
+    pub async fn burn(
+        &self,
+        context: &mut ProgramTestContext,
+        master_edition: &MasterEditionV2,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::burn_nft(
+                id(),
+                self.pubkey,
+                context.payer.pubkey(),
+                self.mint.pubkey(),
+                self.token.pubkey(),
+                master_edition.pubkey,
+                None,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
+    pub async fn create_v2(
+        &self,
+        context: &mut ProgramTestContext,
+        name: String,
+        symbol: String,
+        uri: String,
+        creators: Option<Vec<Creator>>,
+        seller_fee_basis_points: u16,
+        is_mutable: bool,
+        uses: Option<Uses>,
+    ) -> transport::Result<()> {
+        create_mint(context, &self.mint, &context.payer.pubkey(), None).await?;
+        create_token_account(
+            context,
+            &self.token,
+            &self.mint.pubkey(),
+            &context.payer.pubkey(),
+        )
+        .await?;
+        mint_tokens(
+            context,
+            &self.mint.pubkey(),
+            &self.token.pubkey(),
+            1,
+            &context.payer.pubkey(),
+            None,
+        )
+        .await?;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::create_metadata_accounts_v2(
+                id(),
+                self.pubkey.clone(),
+                self.mint.pubkey(),
+                context.payer.pubkey().clone(),
+                context.payer.pubkey().clone(),
+                context.payer.pubkey().clone(),
+                name,
+                symbol,
+                uri,
+                creators,
+                seller_fee_basis_points,
+                false,
+                is_mutable,
+                uses,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
+    pub async fn utilize(
+        &self,
+        context: &mut ProgramTestContext,
+        use_authority: Option<&Keypair>,
+        number_of_uses: u64,
+    ) -> transport::Result<()> {
+        let mut signers = vec![&context.payer];
+        let use_authority_pubkey = match use_authority {
+            Some(use_authority) => {
+                signers.push(use_authority);
+                use_authority.pubkey()
+            }
+            None => context.payer.pubkey(),
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::utilize(
+                id(),
+                self.pubkey,
+                self.token.pubkey(),
+                self.mint.pubkey(),
+                use_authority_pubkey,
+                context.payer.pubkey(),
+                number_of_uses,
+            )],
+            Some(&context.payer.pubkey()),
+            &signers,
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
+    pub async fn approve_use_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        use_authority: &Pubkey,
+        number_of_uses: u64,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::approve_use_authority(
+                id(),
+                *use_authority,
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                self.token.pubkey(),
+                self.pubkey,
+                self.mint.pubkey(),
+                number_of_uses,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
+    pub async fn revoke_use_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        use_authority: &Pubkey,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::revoke_use_authority(
+                id(),
+                *use_authority,
+                context.payer.pubkey(),
+                self.token.pubkey(),
+                self.pubkey,
+                self.mint.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
+    pub async fn set_pending_update_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        new_update_authority: &Pubkey,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::set_pending_update_authority(
+                id(),
+                self.pubkey,
+                self.mint.pubkey(),
+                context.payer.pubkey(),
+                *new_update_authority,
+                context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
+    pub async fn accept_update_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        new_update_authority: &Keypair,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::accept_update_authority(
+                id(),
+                self.pubkey,
+                self.mint.pubkey(),
+                new_update_authority.pubkey(),
+                context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, new_update_authority],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
+
+    pub async fn cancel_pending_update_authority(
+        &self,
+        context: &mut ProgramTestContext,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::cancel_pending_update_authority(
+                id(),
+                self.pubkey,
+                self.mint.pubkey(),
+                context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        Ok(context.banks_client.process_transaction(tx).await?)
+    }
 }
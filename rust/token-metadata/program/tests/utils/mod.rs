@@ -1,6 +1,7 @@
 mod assert;
 mod edition_marker;
 mod external_price;
+mod master_edition_v1;
 mod master_edition_v2;
 mod metadata;
 mod vault;
@@ -8,6 +9,7 @@ mod vault;
 pub use assert::*;
 pub use edition_marker::EditionMarker;
 pub use external_price::ExternalPrice;
+pub use master_edition_v1::MasterEditionV1;
 pub use master_edition_v2::MasterEditionV2;
 pub use metadata::Metadata;
 use solana_program_test::*;
@@ -16,12 +18,47 @@ use solana_sdk::{
     signer::keypair::Keypair, system_instruction, transaction::Transaction, transport,
 };
 use spl_token::state::Mint;
+use spl_token_metadata::state::Creator;
 pub use vault::Vault;
 
 pub fn program_test<'a>() -> ProgramTest {
     ProgramTest::new("spl_token_metadata", spl_token_metadata::id(), None)
 }
 
+pub async fn create_test_metadata(
+    context: &mut ProgramTestContext,
+    creators: Option<Vec<Creator>>,
+    is_mutable: bool,
+) -> Metadata {
+    let test_metadata = Metadata::new();
+
+    test_metadata
+        .create(
+            context,
+            "Test".to_string(),
+            "TST".to_string(),
+            "uri".to_string(),
+            creators,
+            10,
+            is_mutable,
+        )
+        .await
+        .unwrap();
+
+    test_metadata
+}
+
+pub async fn create_test_master_edition(
+    context: &mut ProgramTestContext,
+) -> (Metadata, MasterEditionV2) {
+    let test_metadata = create_test_metadata(context, None, false).await;
+    let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+    test_master_edition.create(context, Some(10)).await.unwrap();
+
+    (test_metadata, test_master_edition)
+}
+
 pub async fn get_account(context: &mut ProgramTestContext, pubkey: &Pubkey) -> Account {
     context
         .banks_client
@@ -0,0 +1,141 @@
+mod utils;
+
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+    transport::TransportError,
+};
+use spl_token_metadata::error::MetadataError;
+use spl_token_metadata::state::Collection;
+use spl_token_metadata::{id, instruction};
+use utils::*;
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let test_collection = create_test_metadata(&mut context, None, true).await;
+    let test_collection_master_edition = MasterEditionV2::new(&test_collection);
+    test_collection_master_edition
+        .create(&mut context, Some(0))
+        .await
+        .unwrap();
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+
+    test_metadata
+        .verify_collection(
+            &mut context,
+            &test_collection,
+            &test_collection_master_edition,
+        )
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(
+        metadata.collection,
+        Some(Collection {
+            key: test_collection.mint.pubkey(),
+            verified: true,
+        })
+    );
+    assert_eq!(metadata.data.name, "Test");
+
+    test_metadata
+        .unverify_collection(
+            &mut context,
+            &test_collection,
+            &test_collection_master_edition,
+        )
+        .await
+        .unwrap();
+
+    let metadata = test_metadata.get_data(&mut context).await;
+    assert_eq!(
+        metadata.collection,
+        Some(Collection {
+            key: test_collection.mint.pubkey(),
+            verified: false,
+        })
+    );
+}
+
+#[tokio::test]
+async fn fail_collection_without_master_edition() {
+    let mut context = program_test().start_with_context().await;
+    let test_collection = create_test_metadata(&mut context, None, true).await;
+    let test_collection_master_edition = MasterEditionV2::new(&test_collection);
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+
+    let result = test_metadata
+        .verify_collection(
+            &mut context,
+            &test_collection,
+            &test_collection_master_edition,
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::CollectionMustBeAMasterEditionV2);
+}
+
+#[tokio::test]
+async fn fail_invalid_collection_authority() {
+    let mut context = program_test().start_with_context().await;
+    let test_collection = create_test_metadata(&mut context, None, true).await;
+    let test_collection_master_edition = MasterEditionV2::new(&test_collection);
+    test_collection_master_edition
+        .create(&mut context, Some(0))
+        .await
+        .unwrap();
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+    let fake_collection_authority = Keypair::new();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::verify_collection(
+            id(),
+            test_metadata.pubkey,
+            context.payer.pubkey(),
+            fake_collection_authority.pubkey(),
+            test_collection.mint.pubkey(),
+            test_collection.pubkey,
+            test_collection_master_edition.pubkey,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_collection_authority],
+        context.last_blockhash,
+    );
+
+    let result = context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::UpdateAuthorityIncorrect);
+}
+
+#[tokio::test]
+async fn fail_unverify_other_collection() {
+    let mut context = program_test().start_with_context().await;
+    let test_collection = create_test_metadata(&mut context, None, true).await;
+    let test_collection_master_edition = MasterEditionV2::new(&test_collection);
+    test_collection_master_edition
+        .create(&mut context, Some(0))
+        .await
+        .unwrap();
+    let test_metadata = create_test_metadata(&mut context, None, true).await;
+
+    let result = test_metadata
+        .unverify_collection(
+            &mut context,
+            &test_collection,
+            &test_collection_master_edition,
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error!(result, MetadataError::CollectionNotFound);
+}
@@ -22,16 +22,19 @@ use {
     },
     spl_token_metadata::{
         instruction::{
-            create_master_edition, create_metadata_accounts,
+            create_master_edition, create_metadata_accounts, migrate_master_edition_v1_to_v2,
             mint_new_edition_from_master_edition_via_token, puff_metadata_account,
-            update_metadata_accounts,
+            update_metadata_accounts, update_metadata_accounts_with_pending_update_authority,
         },
         state::{
             get_reservation_list, Data, Edition, Key, MasterEditionV1, MasterEditionV2, Metadata,
             EDITION, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH, PREFIX,
         },
     },
-    std::str::FromStr,
+    std::{
+        collections::{HashMap, HashSet},
+        str::FromStr,
+    },
 };
 
 const TOKEN_PROGRAM_PUBKEY: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
@@ -95,6 +98,173 @@ fn puff_unpuffed_metadata(_app_matches: &ArgMatches, payer: Keypair, client: Rpc
     }
 }
 
+fn migrate_master_editions(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
+    let program_key = spl_token_metadata::id();
+    let batch_size = match app_matches.value_of("batch_size") {
+        Some(val) => val.parse::<usize>().unwrap(),
+        None => 3,
+    };
+    let dry_run = app_matches.is_present("dry_run");
+
+    let mut master_editions: HashMap<Pubkey, MasterEditionV1> = HashMap::new();
+    let mut reservation_lists: HashMap<Pubkey, Vec<(Pubkey, Option<Pubkey>)>> = HashMap::new();
+    let mut outstanding_reservations: HashSet<Pubkey> = HashSet::new();
+    for (key, mut acct) in client.get_program_accounts(&program_key).unwrap() {
+        if acct.data[0] == Key::MasterEditionV1 as u8 {
+            match try_from_slice_unchecked(&acct.data) {
+                Ok(master_edition) => {
+                    master_editions.insert(key, master_edition);
+                }
+                Err(_) => println!("Skipping {}", key),
+            }
+        } else if acct.data[0] == Key::ReservationListV1 as u8
+            || acct.data[0] == Key::ReservationListV2 as u8
+        {
+            let mut lamports = 0;
+            let account_info = AccountInfo::new(
+                &key,
+                false,
+                false,
+                &mut lamports,
+                &mut acct.data,
+                &acct.owner,
+                false,
+                0,
+            );
+            match get_reservation_list(&account_info) {
+                Ok(res_list) => {
+                    if res_list.reservations().iter().any(|reservation| {
+                        reservation.address != solana_program::system_program::id()
+                            && reservation.spots_remaining > 0
+                    }) {
+                        outstanding_reservations.insert(res_list.master_edition());
+                    }
+                    reservation_lists
+                        .entry(res_list.master_edition())
+                        .or_insert_with(Vec::new)
+                        .push((key, res_list.funder()))
+                }
+                Err(_) => println!("Skipping {}", key),
+            }
+        }
+    }
+
+    let mut payer_token_accounts: Vec<(Pubkey, Account)> = vec![];
+    for keyed_account in client
+        .get_token_accounts_by_owner(
+            &payer.pubkey(),
+            TokenAccountsFilter::ProgramId(spl_token::id()),
+        )
+        .unwrap()
+    {
+        let key = Pubkey::from_str(&keyed_account.pubkey).unwrap();
+        let acct = client.get_account(&key).unwrap();
+        payer_token_accounts.push((key, Account::unpack(&acct.data).unwrap()));
+    }
+
+    let mut instructions = vec![];
+    for (token_account_key, token_account) in &payer_token_accounts {
+        if token_account.amount != 1 {
+            continue;
+        }
+        let (master_edition_key, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                &program_key.as_ref(),
+                &token_account.mint.as_ref(),
+                EDITION.as_bytes(),
+            ],
+            &program_key,
+        );
+        let master_edition = match master_editions.get(&master_edition_key) {
+            Some(val) => val,
+            None => continue,
+        };
+        if outstanding_reservations.contains(&master_edition_key) {
+            println!(
+                "Skipping master edition {}, its reservation lists still hold reservations",
+                master_edition_key
+            );
+            continue;
+        }
+        let (metadata_key, _) = Pubkey::find_program_address(
+            &[
+                PREFIX.as_bytes(),
+                &program_key.as_ref(),
+                &token_account.mint.as_ref(),
+            ],
+            &program_key,
+        );
+        let owned_token_accounts = payer_token_accounts
+            .iter()
+            .filter(|(_, acct)| {
+                acct.mint == master_edition.printing_mint
+                    || acct.mint == master_edition.one_time_printing_authorization_mint
+            })
+            .map(|(key, _)| *key)
+            .collect::<Vec<Pubkey>>();
+        let lists = reservation_lists
+            .get(&master_edition_key)
+            .cloned()
+            .unwrap_or_default();
+
+        println!(
+            "Migrating master edition {} of mint {}, burning {} token accounts and passing {} reservation lists",
+            master_edition_key,
+            token_account.mint,
+            owned_token_accounts.len(),
+            lists.len()
+        );
+        instructions.push(migrate_master_edition_v1_to_v2(
+            program_key,
+            master_edition_key,
+            metadata_key,
+            payer.pubkey(),
+            *token_account_key,
+            master_edition.one_time_printing_authorization_mint,
+            master_edition.printing_mint,
+            owned_token_accounts,
+            lists,
+        ));
+    }
+    println!("Found {} master editions to migrate", instructions.len());
+
+    if dry_run {
+        return;
+    }
+
+    let mut migrated = 0;
+    for batch in instructions.chunks(batch_size) {
+        let mut attempts = 0;
+        loop {
+            let mut transaction = Transaction::new_with_payer(batch, Some(&payer.pubkey()));
+            let recent_blockhash = client.get_recent_blockhash().unwrap().0;
+            transaction.sign(&[&payer], recent_blockhash);
+            match client.send_and_confirm_transaction(&transaction) {
+                Ok(_) => {
+                    migrated += batch.len();
+                    println!("At {} / {}", migrated, instructions.len());
+                    break;
+                }
+                Err(err) => {
+                    attempts += 1;
+                    if attempts >= 3 {
+                        println!("Txn failed, skipping batch: {:?}", err);
+                        break;
+                    }
+                    println!("Txn failed. Retry.");
+                    std::thread::sleep(std::time::Duration::from_millis(1000));
+                }
+            }
+        }
+    }
+    println!(
+        "Migrated {} / {} master editions",
+        migrated,
+        instructions.len()
+    );
+}
+
 fn mint_coins(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) {
     let token_key = Pubkey::from_str(TOKEN_PROGRAM_PUBKEY).unwrap();
     let amount = match app_matches.value_of("amount") {
@@ -506,18 +676,34 @@ fn update_metadata_account_call(
         creators: metadata.data.creators,
     };
 
-    let instructions = [update_metadata_accounts(
-        program_key,
-        metadata_key,
-        update_authority.pubkey(),
-        new_update_authority,
-        Some(new_data),
-        None,
-    )];
+    // The new update authority can't sign here, so it is proposed and has to accept it
+    let instructions = [match new_update_authority {
+        Some(new_update_authority) => update_metadata_accounts_with_pending_update_authority(
+            program_key,
+            metadata_key,
+            mint_key,
+            update_authority.pubkey(),
+            new_update_authority,
+            payer.pubkey(),
+            Some(new_data),
+            None,
+        ),
+        None => update_metadata_accounts(
+            program_key,
+            metadata_key,
+            update_authority.pubkey(),
+            None,
+            Some(new_data),
+            None,
+        ),
+    }];
 
     let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
     let recent_blockhash = client.get_recent_blockhash().unwrap().0;
-    let signers = vec![&update_authority];
+    let mut signers = vec![&update_authority];
+    if payer.pubkey() != update_authority.pubkey() {
+        signers.push(&payer);
+    }
 
     transaction.sign(&signers, recent_blockhash);
     client.send_and_confirm_transaction(&transaction).unwrap();
@@ -840,7 +1026,26 @@ fn main() {
 
         ).subcommand(
                 SubCommand::with_name("puff_unpuffed_metadata")
-                        .about("Take metadata that still have variable length name, symbol, and uri fields and stretch them out with null symbols so they can be searched more easily by RPC.")).get_matches();
+                        .about("Take metadata that still have variable length name, symbol, and uri fields and stretch them out with null symbols so they can be searched more easily by RPC."))
+        .subcommand(
+            SubCommand::with_name("migrate_master_editions")
+                .about("Migrate every MasterEditionV1 you hold the token for to V2, burning and closing your printing and authorization token accounts and any reservation lists.")
+                .arg(
+                    Arg::with_name("batch_size")
+                        .long("batch_size")
+                        .value_name("BATCH_SIZE")
+                        .required(false)
+                        .takes_value(true)
+                        .help("Number of master editions to migrate per transaction, defaults to 3"),
+                ).arg(
+                    Arg::with_name("dry_run")
+                        .long("dry_run")
+                        .value_name("DRY_RUN")
+                        .required(false)
+                        .takes_value(false)
+                        .help("Only list the master editions that would be migrated"),
+                )
+        ).get_matches();
 
     let client = RpcClient::new(
         app_matches
@@ -895,6 +1100,9 @@ fn main() {
         ("puff_unpuffed_metadata", Some(arg_matches)) => {
             puff_unpuffed_metadata(arg_matches, payer, client);
         }
+        ("migrate_master_editions", Some(arg_matches)) => {
+            migrate_master_editions(arg_matches, payer, client);
+        }
         _ => unreachable!(),
     }
 }
@@ -232,6 +232,54 @@ pub enum VaultError {
     /// Derived key invalid
     #[error("Derived key invalid")]
     DerivedKeyInvalid,
+
+    /// Invalid token metadata program
+    #[error("Invalid token metadata program")]
+    InvalidTokenMetadataProgram,
+
+    /// Token metadata creation failed
+    #[error("Token metadata creation failed")]
+    TokenMetadataCreationFailed,
+
+    /// Vault should be deactivated
+    #[error("Vault should be deactivated")]
+    VaultShouldBeDeactivated,
+
+    /// Store must be empty before it can be closed
+    #[error("Store must be empty before it can be closed")]
+    StoreNotEmpty,
+
+    /// Token close account failed
+    #[error("Token close account failed")]
+    TokenCloseAccountFailed,
+
+    /// External price account needs to match vault
+    #[error("External price account needs to match vault")]
+    ExternalPriceAccountNeedsToMatchVault,
+
+    /// Receiver cannot be one of the accounts being closed
+    #[error("Receiver cannot be one of the accounts being closed")]
+    InvalidReceiver,
+
+    /// Price change exceeds the maximum allowed by the external price account's timelock
+    #[error("Price change exceeds the maximum allowed by the external price account's timelock")]
+    PriceChangeExceedsMaximum,
+
+    /// A price timelock can only be made stricter
+    #[error("A price timelock can only be made stricter")]
+    TimelockCanOnlyBeTightened,
+
+    /// Every safety deposit box has to be closed before the vault can be
+    #[error("Every safety deposit box has to be closed before the vault can be")]
+    SafetyDepositBoxesStillOpen,
+
+    /// No longer returned, VaultV1s are closed with whichever safety deposit boxes are passed in
+    #[error("VaultV1 accounts can be closed now, this error is no longer returned")]
+    VaultV1CannotBeClosed,
+
+    /// Allowing combination or changing the price mint of a timelocked price account needs a delay
+    #[error("Allowing combination or changing the price mint of a timelocked price account needs a delay")]
+    CombineTermsChangeNeedsDelay,
 }
 
 impl PrintProgramError for VaultError {
@@ -26,6 +26,35 @@ pub struct NumberOfShareArgs {
     pub number_of_shares: u64,
 }
 
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct CreateFractionMetadataArgs {
+    /// The name of the fraction token
+    pub name: String,
+    /// The symbol of the fraction token
+    pub symbol: String,
+    /// URI pointing to JSON describing the fraction token and the vault it represents
+    pub uri: String,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct SetExternalPriceAccountTimelockArgs {
+    /// Seconds an update must wait before it takes effect
+    pub delay_seconds: i64,
+    /// If set, the most price_per_share may change in one update, in basis points of the current price
+    pub max_change_basis_points: Option<u16>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
+pub struct CloseVaultArgs {
+    /// If false, only the safety deposit boxes and stores passed in are closed, so that vaults
+    /// with more boxes than fit in one transaction can be drained over several calls before
+    /// a final call closes the vault itself.
+    pub close_vault: bool,
+}
+
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone)]
 pub struct MintEditionProxyArgs {
@@ -39,7 +68,8 @@ pub enum VaultInstruction {
     ///   0. `[writable]` Initialized fractional share mint with 0 tokens in supply, authority on mint must be pda of program with seed [prefix, programid]
     ///   1. `[writable]` Initialized redeem treasury token account with 0 tokens in supply, owner of account must be pda of program like above
     ///   2. `[writable]` Initialized fraction treasury token account with 0 tokens in supply, owner of account must be pda of program like above
-    ///   3. `[writable]` Uninitialized vault account
+    ///   3. `[writable]` Uninitialized vault account, allocated with MAX_VAULT_V2_SIZE for a VaultV2 (more than 255 safety deposit boxes)
+    ///                   or MAX_VAULT_SIZE for a legacy VaultV1
     ///   4. `[]` Authority on the vault
     ///   5. `[]` Pricing Lookup Address
     ///   6. `[]` Token program
@@ -85,6 +115,7 @@ pub enum VaultInstruction {
     ///   9. `[]` PDA-based Burn authority for the fraction treasury account containing the uncirculated shares seed [PREFIX, program_id]
     ///   10. `[]` External pricing lookup address
     ///   11. `[]` Token program
+    ///   12. `[]` Clock sysvar - required if the external price account is timelocked
     CombineVault,
 
     /// If in the combine state, shareholders can hit this endpoint to burn shares in exchange for monies from the treasury.
@@ -144,7 +175,10 @@ pub enum VaultInstruction {
     /// Helpful method that isn't necessary to use for main users of the app, but allows one to create/update
     /// existing external price account fields if they are signers of this account.
     /// Useful for testing purposes, and the CLI makes use of it as well so that you can verify logic.
+    /// If the account is timelocked, the update is queued and only takes effect once the delay passes.
+    /// A timelock without a delay rejects updates that allow combination or change the price mint.
     ///   0. `[writable]` External price account
+    ///   1. `[]` Clock sysvar - required if the external price account is timelocked
     UpdateExternalPriceAccount(ExternalPriceAccount),
 
     /// Sets the authority of the vault to a new authority.
@@ -153,6 +187,44 @@ pub enum VaultInstruction {
     ///   1. `[signer]` Vault authority
     ///   2. `[]` New authority
     SetAuthority,
+
+    /// Creates a fungible token metadata account for the vault's fraction mint via CPI into the
+    /// token metadata program, so wallets can display the shares. The vault's PDA signs as mint authority
+    /// and the vault is recorded as the metadata's update authority. The metadata is immutable.
+    ///
+    ///   0. `[writable]` Uninitialized metadata account (pda of ['metadata', token metadata program id, fraction mint])
+    ///   1. `[]` Fraction mint
+    ///   2. `[]` Initialized vault
+    ///   3. `[signer]` Authority on the vault
+    ///   4. `[]` PDA-based mint authority on the fraction mint, seed [PREFIX, program_id, vault]
+    ///   5. `[signer]` Payer
+    ///   6. `[]` Token metadata program
+    ///   7. `[]` System program
+    ///   8. `[]` Rent sysvar
+    CreateFractionMetadata(CreateFractionMetadataArgs),
+
+    /// Closes a deactivated vault, reclaiming rent. Every store and treasury must be empty.
+    /// Token accounts are closed through the vault PDA and program-owned accounts are zeroed,
+    /// with all lamports going to the receiver. The external price account is only closed if it signs,
+    /// since it may be shared with other vaults. A VaultV2 is only closed once every one of its safety deposit
+    /// boxes has been. VaultV1s don't track their boxes, so the final call has to pass every box not closed yet,
+    /// as any left out can no longer be closed once the vault is gone.
+    ///   0. `[writable]` Deactivated vault
+    ///   1. `[signer]` Authority on the vault
+    ///   2. `[]` PDA-based authority on the stores and treasuries, seed [PREFIX, program_id, vault]
+    ///   3. `[writable]` Fraction treasury
+    ///   4. `[writable]` Redeem treasury
+    ///   5. `[writable]` Receiver of the reclaimed lamports
+    ///   6. `[writable]` External price account - signer if you wish it closed too
+    ///   7. `[]` Token program
+    ///   8..8+2N `[writable]` Pairs of safety deposit box and its store, for each box to close
+    CloseVault(CloseVaultArgs),
+
+    /// Sets the timelock on an external price account allocated with MAX_EXTERNAL_ACCOUNT_V2_SIZE.
+    /// Once set, the delay can only grow and the max change can only shrink. A max change is relative
+    /// to the current price, so a price account still priced at zero can't be moved once bounded.
+    ///   0. `[writable, signer]` External price account
+    SetExternalPriceAccountTimelock(SetExternalPriceAccountTimelockArgs),
 }
 
 /// Creates an InitVault instruction
@@ -198,7 +270,10 @@ pub fn create_update_external_price_account_instruction(
 ) -> Instruction {
     Instruction {
         program_id,
-        accounts: vec![AccountMeta::new(external_price_account, true)],
+        accounts: vec![
+            AccountMeta::new(external_price_account, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
         data: VaultInstruction::UpdateExternalPriceAccount(ExternalPriceAccount {
             key: Key::ExternalAccountKeyV1,
             price_per_share,
@@ -210,6 +285,27 @@ pub fn create_update_external_price_account_instruction(
     }
 }
 
+/// Creates a SetExternalPriceAccountTimelock instruction
+pub fn create_set_external_price_account_timelock_instruction(
+    program_id: Pubkey,
+    external_price_account: Pubkey,
+    delay_seconds: i64,
+    max_change_basis_points: Option<u16>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![AccountMeta::new(external_price_account, true)],
+        data: VaultInstruction::SetExternalPriceAccountTimelock(
+            SetExternalPriceAccountTimelockArgs {
+                delay_seconds,
+                max_change_basis_points,
+            },
+        )
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
 /// Creates an AddTokenToInactiveVault instruction
 #[allow(clippy::too_many_arguments)]
 pub fn create_add_token_to_inactive_vault_instruction(
@@ -301,6 +397,7 @@ pub fn create_combine_vault_instruction(
             AccountMeta::new_readonly(uncirculated_burn_authority, false),
             AccountMeta::new_readonly(external_pricing_account, false),
             AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
         ],
         data: VaultInstruction::CombineVault.try_to_vec().unwrap(),
     }
@@ -462,3 +559,81 @@ pub fn create_set_authority_instruction(
         data: VaultInstruction::SetAuthority.try_to_vec().unwrap(),
     }
 }
+
+/// Creates a CreateFractionMetadata instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_fraction_metadata_instruction(
+    program_id: Pubkey,
+    metadata: Pubkey,
+    fraction_mint: Pubkey,
+    vault: Pubkey,
+    vault_authority: Pubkey,
+    fraction_mint_authority: Pubkey,
+    payer: Pubkey,
+    token_metadata_program: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(metadata, false),
+            AccountMeta::new_readonly(fraction_mint, false),
+            AccountMeta::new_readonly(vault, false),
+            AccountMeta::new_readonly(vault_authority, true),
+            AccountMeta::new_readonly(fraction_mint_authority, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(token_metadata_program, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: VaultInstruction::CreateFractionMetadata(CreateFractionMetadataArgs {
+            name,
+            symbol,
+            uri,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// Creates a CloseVault instruction
+#[allow(clippy::too_many_arguments)]
+pub fn create_close_vault_instruction(
+    program_id: Pubkey,
+    vault: Pubkey,
+    vault_authority: Pubkey,
+    pda_authority: Pubkey,
+    fraction_treasury: Pubkey,
+    redeem_treasury: Pubkey,
+    receiver: Pubkey,
+    external_price_account: Pubkey,
+    close_external_price_account: bool,
+    safety_deposit_boxes_and_stores: Vec<(Pubkey, Pubkey)>,
+    close_vault: bool,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(vault, false),
+        AccountMeta::new_readonly(vault_authority, true),
+        AccountMeta::new_readonly(pda_authority, false),
+        AccountMeta::new(fraction_treasury, false),
+        AccountMeta::new(redeem_treasury, false),
+        AccountMeta::new(receiver, false),
+        AccountMeta::new(external_price_account, close_external_price_account),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    for (safety_deposit_box, store) in safety_deposit_boxes_and_stores {
+        accounts.push(AccountMeta::new(safety_deposit_box, false));
+        accounts.push(AccountMeta::new(store, false));
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data: VaultInstruction::CloseVault(CloseVaultArgs { close_vault })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
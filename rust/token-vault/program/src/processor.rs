@@ -3,19 +3,23 @@ use {
         error::VaultError,
         instruction::VaultInstruction,
         state::{
-            ExternalPriceAccount, Key, SafetyDepositBox, Vault, VaultState,
-            MAX_SAFETY_DEPOSIT_SIZE, PREFIX,
+            ExternalPriceAccountV2, Key, PendingPriceUpdate, SafetyDepositBox, Vault, VaultState,
+            MAX_EXTERNAL_ACCOUNT_V2_SIZE, MAX_SAFETY_DEPOSIT_SIZE, MAX_SAFETY_DEPOSIT_V2_SIZE,
+            MAX_VAULT_V2_SIZE, PREFIX,
         },
         utils::{
             assert_initialized, assert_owned_by, assert_rent_exempt, assert_token_matching,
-            assert_token_program_matches_package, assert_vault_authority_correct,
-            create_or_allocate_account_raw, spl_token_burn, spl_token_mint_to, spl_token_transfer,
-            TokenBurnParams, TokenMintToParams, TokenTransferParams,
+            assert_token_metadata_program_matches_package, assert_token_program_matches_package,
+            assert_vault_authority_correct, close_program_account, create_fraction_metadata,
+            create_or_allocate_account_raw, spl_token_burn, spl_token_close_account,
+            spl_token_mint_to, spl_token_transfer, CreateFractionMetadataParams, TokenBurnParams,
+            TokenCloseAccountParams, TokenMintToParams, TokenTransferParams,
         },
     },
-    borsh::{BorshDeserialize, BorshSerialize},
+    borsh::BorshDeserialize,
     solana_program::{
         account_info::{next_account_info, AccountInfo},
+        clock::{Clock, UnixTimestamp},
         entrypoint::ProgramResult,
         msg,
         program_option::COption,
@@ -88,7 +92,239 @@ pub fn process_instruction(
             msg!("Instruction: Set Authority");
             process_set_authority(program_id, accounts)
         }
+        VaultInstruction::CreateFractionMetadata(args) => {
+            msg!("Instruction: Create Fraction Metadata");
+            process_create_fraction_metadata(program_id, accounts, args.name, args.symbol, args.uri)
+        }
+        VaultInstruction::CloseVault(args) => {
+            msg!("Instruction: Close Vault");
+            process_close_vault(program_id, accounts, args.close_vault)
+        }
+        VaultInstruction::SetExternalPriceAccountTimelock(args) => {
+            msg!("Instruction: Set External Price Account Timelock");
+            process_set_external_price_account_timelock(
+                program_id,
+                accounts,
+                args.delay_seconds,
+                args.max_change_basis_points,
+            )
+        }
+    }
+}
+
+pub fn process_close_vault(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    close_vault: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let vault_info = next_account_info(account_info_iter)?;
+    let vault_authority_info = next_account_info(account_info_iter)?;
+    let pda_authority_info = next_account_info(account_info_iter)?;
+    let fraction_treasury_info = next_account_info(account_info_iter)?;
+    let redeem_treasury_info = next_account_info(account_info_iter)?;
+    let receiver_info = next_account_info(account_info_iter)?;
+    let external_price_account_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    let mut vault = Vault::from_account_info(vault_info)?;
+
+    assert_token_program_matches_package(token_program_info)?;
+    assert_token_matching(&vault, token_program_info)?;
+    assert_owned_by(vault_info, program_id)?;
+    assert_vault_authority_correct(&vault, vault_authority_info)?;
+
+    if vault.state != VaultState::Deactivated {
+        return Err(VaultError::VaultShouldBeDeactivated.into());
+    }
+
+    if receiver_info.key == vault_info.key
+        || receiver_info.key == external_price_account_info.key
+        || receiver_info.key == fraction_treasury_info.key
+        || receiver_info.key == redeem_treasury_info.key
+    {
+        return Err(VaultError::InvalidReceiver.into());
+    }
+
+    let (authority, bump_seed) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            vault_info.key.as_ref(),
+        ],
+        program_id,
+    );
+    let authority_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        vault_info.key.as_ref(),
+        &[bump_seed],
+    ];
+
+    if authority != *pda_authority_info.key {
+        return Err(VaultError::InvalidAuthority.into());
+    }
+
+    while let Ok(safety_deposit_info) = next_account_info(account_info_iter) {
+        let store_info = next_account_info(account_info_iter)?;
+
+        assert_owned_by(safety_deposit_info, program_id)?;
+        assert_owned_by(store_info, token_program_info.key)?;
+        let safety_deposit = SafetyDepositBox::from_account_info(safety_deposit_info)?;
+        let store: Account = assert_initialized(store_info)?;
+
+        if safety_deposit.vault != *vault_info.key {
+            return Err(VaultError::SafetyDepositBoxVaultMismatch.into());
+        }
+
+        if *store_info.key != safety_deposit.store {
+            return Err(VaultError::StoreDoesNotMatchSafetyDepositBox.into());
+        }
+
+        if receiver_info.key == safety_deposit_info.key || receiver_info.key == store_info.key {
+            return Err(VaultError::InvalidReceiver.into());
+        }
+
+        if store.amount != 0 {
+            return Err(VaultError::StoreNotEmpty.into());
+        }
+
+        spl_token_close_account(TokenCloseAccountParams {
+            account: store_info.clone(),
+            destination: receiver_info.clone(),
+            authority: pda_authority_info.clone(),
+            authority_signer_seeds,
+            token_program: token_program_info.clone(),
+        })?;
+
+        close_program_account(safety_deposit_info, receiver_info)?;
+
+        if vault.key == Key::VaultV2 {
+            vault.open_safety_deposit_boxes = vault
+                .open_safety_deposit_boxes
+                .checked_sub(1)
+                .ok_or(VaultError::NumericalOverflowError)?;
+        }
+    }
+
+    if !close_vault {
+        vault.save(vault_info)?;
+        return Ok(());
+    }
+
+    // VaultV1s have no room to count their boxes and token_type_count is already zero once
+    // deactivated, so any box not passed in by now is simply left behind.
+    if vault.key == Key::VaultV2 && vault.open_safety_deposit_boxes != 0 {
+        return Err(VaultError::SafetyDepositBoxesStillOpen.into());
+    }
+
+    if *fraction_treasury_info.key != vault.fraction_treasury {
+        return Err(VaultError::FractionTreasuryNeedsToMatchVault.into());
+    }
+
+    if *redeem_treasury_info.key != vault.redeem_treasury {
+        return Err(VaultError::RedeemTreasuryNeedsToMatchVault.into());
+    }
+
+    if *external_price_account_info.key != vault.pricing_lookup_address {
+        return Err(VaultError::ExternalPriceAccountNeedsToMatchVault.into());
+    }
+
+    for treasury_info in &[fraction_treasury_info, redeem_treasury_info] {
+        assert_owned_by(treasury_info, token_program_info.key)?;
+        let treasury: Account = assert_initialized(treasury_info)?;
+        if treasury.amount != 0 {
+            return Err(VaultError::TreasuryNotEmpty.into());
+        }
+
+        spl_token_close_account(TokenCloseAccountParams {
+            account: (*treasury_info).clone(),
+            destination: receiver_info.clone(),
+            authority: pda_authority_info.clone(),
+            authority_signer_seeds,
+            token_program: token_program_info.clone(),
+        })?;
+    }
+
+    // The price account may be shared between vaults, so only its owner can choose to close it.
+    if external_price_account_info.is_signer {
+        assert_owned_by(external_price_account_info, program_id)?;
+        close_program_account(external_price_account_info, receiver_info)?;
+    }
+
+    close_program_account(vault_info, receiver_info)?;
+
+    Ok(())
+}
+
+pub fn process_create_fraction_metadata(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+    symbol: String,
+    uri: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let metadata_info = next_account_info(account_info_iter)?;
+    let fraction_mint_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let vault_authority_info = next_account_info(account_info_iter)?;
+    let fraction_mint_authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let token_metadata_program_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let rent_info = next_account_info(account_info_iter)?;
+
+    let vault = Vault::from_account_info(vault_info)?;
+    // The vault PDA will sign this CPI as mint authority of the fraction mint, so
+    // it must never be handed to an arbitrary program.
+    assert_token_metadata_program_matches_package(token_metadata_program_info)?;
+    assert_owned_by(vault_info, program_id)?;
+    assert_vault_authority_correct(&vault, vault_authority_info)?;
+
+    if vault.key == Key::Uninitialized {
+        return Err(VaultError::Uninitialized.into());
+    }
+
+    if fraction_mint_info.key != &vault.fraction_mint {
+        return Err(VaultError::VaultMintNeedsToMatchVault.into());
+    }
+
+    let (authority, bump_seed) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            vault_info.key.as_ref(),
+        ],
+        program_id,
+    );
+    let authority_signer_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        vault_info.key.as_ref(),
+        &[bump_seed],
+    ];
+
+    if authority != *fraction_mint_authority_info.key {
+        return Err(VaultError::InvalidAuthority.into());
     }
+
+    create_fraction_metadata(CreateFractionMetadataParams {
+        metadata: metadata_info.clone(),
+        mint: fraction_mint_info.clone(),
+        authority: fraction_mint_authority_info.clone(),
+        update_authority: vault_info.clone(),
+        payer: payer_info.clone(),
+        token_metadata_program: token_metadata_program_info.clone(),
+        system_program: system_account_info.clone(),
+        rent: rent_info.clone(),
+        name,
+        symbol,
+        uri,
+        authority_signer_seeds,
+    })?;
+
+    Ok(())
 }
 
 pub fn process_update_external_price_account(
@@ -104,14 +340,93 @@ pub fn process_update_external_price_account(
         return Err(VaultError::ExternalPriceAccountMustBeSigner.into());
     }
 
-    let mut external_price_account = ExternalPriceAccount::from_account_info(account)?;
+    let mut external_price_account = ExternalPriceAccountV2::from_account_info(account)?;
+
+    external_price_account.key = if account.data_len() == MAX_EXTERNAL_ACCOUNT_V2_SIZE {
+        Key::ExternalAccountKeyV2
+    } else {
+        Key::ExternalAccountKeyV1
+    };
+
+    if external_price_account.is_timelocked() {
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = Clock::from_account_info(clock_info)?;
+
+        external_price_account.mature(clock.unix_timestamp);
+        external_price_account.assert_price_change_allowed(price_per_share)?;
+
+        // Without a delay, allowing combination would take effect in the same block it could
+        // be used in, so a bound alone can't guard it.
+        if external_price_account.timelock.delay_seconds == 0
+            && external_price_account.changes_combine_terms(&price_mint, allowed_to_combine)
+        {
+            return Err(VaultError::CombineTermsChangeNeedsDelay.into());
+        }
+
+        if external_price_account.timelock.delay_seconds > 0 {
+            let effective_at = clock
+                .unix_timestamp
+                .checked_add(external_price_account.timelock.delay_seconds)
+                .ok_or(VaultError::NumericalOverflowError)?;
+
+            external_price_account.timelock.pending_update = Some(PendingPriceUpdate {
+                price_per_share,
+                price_mint,
+                allowed_to_combine,
+                effective_at,
+            });
+            external_price_account.save(account)?;
+
+            return Ok(());
+        }
+    }
 
-    external_price_account.key = Key::ExternalAccountKeyV1;
     external_price_account.price_per_share = price_per_share;
     external_price_account.price_mint = price_mint;
     external_price_account.allowed_to_combine = allowed_to_combine;
 
-    external_price_account.serialize(&mut *account.data.borrow_mut())?;
+    external_price_account.save(account)?;
+
+    Ok(())
+}
+
+pub fn process_set_external_price_account_timelock(
+    _: &Pubkey,
+    accounts: &[AccountInfo],
+    delay_seconds: UnixTimestamp,
+    max_change_basis_points: Option<u16>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let account = next_account_info(account_info_iter)?;
+    if !account.is_signer {
+        return Err(VaultError::ExternalPriceAccountMustBeSigner.into());
+    }
+
+    if account.data_len() != MAX_EXTERNAL_ACCOUNT_V2_SIZE {
+        return Err(VaultError::DataTypeMismatch.into());
+    }
+
+    let mut external_price_account = ExternalPriceAccountV2::from_account_info(account)?;
+    let timelock = &mut external_price_account.timelock;
+
+    // Loosening the timelock would let whoever controls the price account skip it entirely.
+    if delay_seconds < timelock.delay_seconds {
+        return Err(VaultError::TimelockCanOnlyBeTightened.into());
+    }
+
+    match (timelock.max_change_basis_points, max_change_basis_points) {
+        (Some(_), None) => return Err(VaultError::TimelockCanOnlyBeTightened.into()),
+        (Some(current), Some(new)) if new > current => {
+            return Err(VaultError::TimelockCanOnlyBeTightened.into())
+        }
+        _ => {}
+    }
+
+    timelock.delay_seconds = delay_seconds;
+    timelock.max_change_basis_points = max_change_basis_points;
+    external_price_account.key = Key::ExternalAccountKeyV2;
+
+    external_price_account.save(account)?;
 
     Ok(())
 }
@@ -140,7 +455,7 @@ pub fn process_set_authority(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
     }
 
     vault.authority = *new_authority_info.key;
-    vault.serialize(&mut *vault_info.data.borrow_mut())?;
+    vault.save(vault_info)?;
 
     Ok(())
 }
@@ -459,7 +774,7 @@ pub fn process_withdraw_token_from_safety_deposit_box(
 
                 if fraction_mint.supply == 0 && vault.token_type_count == 0 {
                     vault.state = VaultState::Deactivated;
-                    vault.serialize(&mut *vault_info.data.borrow_mut())?;
+                    vault.save(vault_info)?;
                 }
             }
         }
@@ -576,7 +891,7 @@ pub fn process_redeem_shares(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
 
     if fractional_remaining == 0 && vault.token_type_count == 0 {
         vault.state = VaultState::Deactivated;
-        vault.serialize(&mut *vault_info.data.borrow_mut())?;
+        vault.save(vault_info)?;
     }
 
     Ok(())
@@ -604,7 +919,18 @@ pub fn process_combine_vault(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
     let redeem_treasury: Account = assert_initialized(redeem_treasury_info)?;
     let your_payment_account: Account = assert_initialized(your_payment_info)?;
     let your_outstanding_shares: Account = assert_initialized(your_outstanding_shares_info)?;
-    let external_pricing = ExternalPriceAccount::from_account_info(external_pricing_info)?;
+    let mut external_pricing = ExternalPriceAccountV2::from_account_info(external_pricing_info)?;
+
+    if *external_pricing_info.key != vault.pricing_lookup_address {
+        return Err(VaultError::ExternalPriceAccountNeedsToMatchVault.into());
+    }
+
+    // Only updates whose timelock has passed count towards combination.
+    if external_pricing.is_timelocked() {
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = Clock::from_account_info(clock_info)?;
+        external_pricing.mature(clock.unix_timestamp);
+    }
 
     assert_token_program_matches_package(token_program_info)?;
     assert_token_matching(&vault, token_program_info)?;
@@ -733,7 +1059,7 @@ pub fn process_combine_vault(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
     vault.state = VaultState::Combined;
     vault.authority = *new_vault_authority_info.key;
     vault.locked_price_per_share = external_pricing.price_per_share;
-    vault.serialize(&mut *vault_info.data.borrow_mut())?;
+    vault.save(vault_info)?;
 
     Ok(())
 }
@@ -792,7 +1118,7 @@ pub fn process_activate_vault(
     })?;
 
     vault.state = VaultState::Active;
-    vault.serialize(&mut *vault_info.data.borrow_mut())?;
+    vault.save(vault_info)?;
 
     Ok(())
 }
@@ -882,32 +1208,47 @@ pub fn process_add_token_to_inactivated_vault(
         token_account.mint.as_ref(),
         &[bump_seed],
     ];
+
+    // Boxes follow the version of their vault so that V1 vaults keep producing V1 boxes
+    // for older clients, and V2 vaults can go past 255 boxes.
+    let (safety_deposit_key, safety_deposit_size) = if vault.key == Key::VaultV2 {
+        (Key::SafetyDepositBoxV2, MAX_SAFETY_DEPOSIT_V2_SIZE)
+    } else {
+        (Key::SafetyDepositBoxV1, MAX_SAFETY_DEPOSIT_SIZE)
+    };
+
     create_or_allocate_account_raw(
         *program_id,
         safety_deposit_account_info,
         rent_info,
         system_account_info,
         payer_info,
-        MAX_SAFETY_DEPOSIT_SIZE,
+        safety_deposit_size,
         authority_signer_seeds,
     )?;
 
     let mut safety_deposit_account =
         SafetyDepositBox::from_account_info(safety_deposit_account_info)?;
-    safety_deposit_account.key = Key::SafetyDepositBoxV1;
+    safety_deposit_account.key = safety_deposit_key;
     safety_deposit_account.vault = *vault_info.key;
     safety_deposit_account.token_mint = token_account.mint;
     safety_deposit_account.store = *store_info.key;
     safety_deposit_account.order = vault.token_type_count;
 
-    safety_deposit_account.serialize(&mut *safety_deposit_account_info.data.borrow_mut())?;
+    safety_deposit_account.save(safety_deposit_account_info)?;
 
     vault.token_type_count = match vault.token_type_count.checked_add(1) {
         Some(val) => val,
         None => return Err(VaultError::NumericalOverflowError.into()),
     };
+    if vault.key == Key::VaultV2 {
+        vault.open_safety_deposit_boxes = vault
+            .open_safety_deposit_boxes
+            .checked_add(1)
+            .ok_or(VaultError::NumericalOverflowError)?;
+    }
 
-    vault.serialize(&mut *vault_info.data.borrow_mut())?;
+    vault.save(vault_info)?;
 
     spl_token_transfer(TokenTransferParams {
         source: token_account_info.clone(),
@@ -945,7 +1286,8 @@ pub fn process_init_vault(
         return Err(VaultError::AlreadyInitialized.into());
     }
 
-    let external_pricing_lookup = ExternalPriceAccount::from_account_info(pricing_lookup_address)?;
+    let external_pricing_lookup =
+        ExternalPriceAccountV2::from_account_info(pricing_lookup_address)?;
 
     assert_token_program_matches_package(token_program_info)?;
     assert_rent_exempt(rent, redeem_treasury_info)?;
@@ -1034,7 +1376,11 @@ pub fn process_init_vault(
         return Err(VaultError::VaultTreasuryMintDoesNotMatchVaultMint.into());
     }
 
-    vault.key = Key::VaultV1;
+    vault.key = if vault_info.data_len() == MAX_VAULT_V2_SIZE {
+        Key::VaultV2
+    } else {
+        Key::VaultV1
+    };
     vault.token_program = *token_program_info.key;
     vault.redeem_treasury = *redeem_treasury_info.key;
     vault.fraction_treasury = *fraction_treasury_info.key;
@@ -1043,9 +1389,10 @@ pub fn process_init_vault(
     vault.allow_further_share_creation = allow_further_share_creation;
     vault.authority = *authority_info.key;
     vault.token_type_count = 0;
+    vault.open_safety_deposit_boxes = 0;
     vault.state = VaultState::Inactive;
 
-    vault.serialize(&mut *vault_info.data.borrow_mut())?;
+    vault.save(vault_info)?;
 
     Ok(())
 }
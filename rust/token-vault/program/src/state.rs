@@ -1,7 +1,12 @@
 use {
-    crate::utils::try_from_slice_checked,
+    crate::{error::VaultError, utils::try_from_slice_checked},
+    arrayref::array_ref,
     borsh::{BorshDeserialize, BorshSerialize},
-    solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
+    solana_program::{
+        account_info::AccountInfo, clock::UnixTimestamp, entrypoint::ProgramResult,
+        program_error::ProgramError, pubkey::Pubkey,
+    },
+    std::convert::{TryFrom, TryInto},
 };
 /// prefix used for PDAs to avoid certain collision attacks (https://en.wikipedia.org/wiki/Collision_attack#Chosen-prefix_collision_attack)
 pub const PREFIX: &str = "vault";
@@ -13,11 +18,24 @@ pub enum Key {
     SafetyDepositBoxV1,
     ExternalAccountKeyV1,
     VaultV1,
+    SafetyDepositBoxV2,
+    VaultV2,
+    ExternalAccountKeyV2,
 }
 
 pub const MAX_SAFETY_DEPOSIT_SIZE: usize = 1 + 32 + 32 + 32 + 1;
+pub const MAX_SAFETY_DEPOSIT_V2_SIZE: usize = 1 + 32 + 32 + 32 + 4;
 pub const MAX_VAULT_SIZE: usize = 1 + 32 + 32 + 32 + 32 + 1 + 32 + 1 + 32 + 1 + 1 + 8;
+pub const MAX_VAULT_V2_SIZE: usize = 1 + 32 + 32 + 32 + 32 + 1 + 32 + 1 + 32 + 4 + 1 + 8 + 4;
+/// Offset of token_type_count in both vault versions, as read directly by Metaplex
+pub const VAULT_TOKEN_TYPE_COUNT_POSITION: usize = 194;
+/// Offset of order in both safety deposit box versions, as read directly by Metaplex
+pub const SAFETY_DEPOSIT_ORDER_POSITION: usize = 97;
 pub const MAX_EXTERNAL_ACCOUNT_SIZE: usize = 1 + 8 + 32 + 1;
+pub const MAX_EXTERNAL_ACCOUNT_V2_SIZE: usize =
+    MAX_EXTERNAL_ACCOUNT_SIZE + 8 + 3 + 1 + 8 + 32 + 1 + 8;
+/// Denominator for max_change_basis_points on a price timelock
+pub const BASIS_POINTS_DENOMINATOR: u128 = 10000;
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq)]
 pub enum VaultState {
@@ -52,23 +70,115 @@ pub struct Vault {
     /// The authority of the vault withdrawals a Safety Deposit contents to count down how many
     /// are left to be opened and closed down. Once this hits zero, and the fraction mint has zero shares,
     /// then we can deactivate the vault.
-    pub token_type_count: u8,
+    /// Stored as a u8 on VaultV1 accounts and a u32 on VaultV2 accounts.
+    pub token_type_count: u32,
     pub state: VaultState,
 
     /// Once combination happens, we copy price per share to vault so that if something nefarious happens
     /// to external price account, like price change, we still have the math 'saved' for use in our calcs
     pub locked_price_per_share: u64,
+
+    /// Safety deposit boxes that were added and not yet closed with CloseVault. The vault itself
+    /// can only be closed once this is zero. Only tracked on VaultV2 accounts, always 0 on VaultV1.
+    pub open_safety_deposit_boxes: u32,
 }
 
 impl Vault {
     pub fn from_account_info(a: &AccountInfo) -> Result<Vault, ProgramError> {
-        let vt: Vault = try_from_slice_checked(&a.data.borrow_mut(), Key::VaultV1, MAX_VAULT_SIZE)?;
+        Vault::from_data(&a.data.borrow())
+    }
+
+    /// Reads either vault version. Uninitialized accounts are treated as VaultV2
+    /// if they were allocated with MAX_VAULT_V2_SIZE.
+    pub fn from_data(data: &[u8]) -> Result<Vault, ProgramError> {
+        if data[0] == Key::VaultV2 as u8
+            || (data[0] == Key::Uninitialized as u8 && data.len() == MAX_VAULT_V2_SIZE)
+        {
+            let vt: Vault = try_from_slice_checked(data, Key::VaultV2, MAX_VAULT_V2_SIZE)?;
+
+            Ok(vt)
+        } else {
+            let vt: VaultV1Data = try_from_slice_checked(data, Key::VaultV1, MAX_VAULT_SIZE)?;
+
+            Ok(vt.into())
+        }
+    }
+
+    /// Writes the vault back using the layout matching its key.
+    pub fn save(&self, a: &AccountInfo) -> ProgramResult {
+        if self.key == Key::VaultV2 {
+            self.serialize(&mut *a.data.borrow_mut())?;
+        } else {
+            VaultV1Data::try_from(self)?.serialize(&mut *a.data.borrow_mut())?;
+        }
+        Ok(())
+    }
+
+    pub fn get_token_type_count(a: &AccountInfo) -> u32 {
+        let data = a.data.borrow();
+        if data[0] == Key::VaultV2 as u8 {
+            u32::from_le_bytes(*array_ref![data, VAULT_TOKEN_TYPE_COUNT_POSITION, 4])
+        } else {
+            data[VAULT_TOKEN_TYPE_COUNT_POSITION] as u32
+        }
+    }
+}
 
-        Ok(vt)
+/// On-chain layout of a VaultV1 account, which only has room for a u8 token_type_count.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct VaultV1Data {
+    key: Key,
+    token_program: Pubkey,
+    fraction_mint: Pubkey,
+    authority: Pubkey,
+    fraction_treasury: Pubkey,
+    redeem_treasury: Pubkey,
+    allow_further_share_creation: bool,
+    pricing_lookup_address: Pubkey,
+    token_type_count: u8,
+    state: VaultState,
+    locked_price_per_share: u64,
+}
+
+impl From<VaultV1Data> for Vault {
+    fn from(v: VaultV1Data) -> Self {
+        Vault {
+            key: v.key,
+            token_program: v.token_program,
+            fraction_mint: v.fraction_mint,
+            authority: v.authority,
+            fraction_treasury: v.fraction_treasury,
+            redeem_treasury: v.redeem_treasury,
+            allow_further_share_creation: v.allow_further_share_creation,
+            pricing_lookup_address: v.pricing_lookup_address,
+            token_type_count: v.token_type_count as u32,
+            state: v.state,
+            locked_price_per_share: v.locked_price_per_share,
+            open_safety_deposit_boxes: 0,
+        }
     }
+}
 
-    pub fn get_token_type_count(a: &AccountInfo) -> u8 {
-        return a.data.borrow()[194];
+impl TryFrom<&Vault> for VaultV1Data {
+    type Error = ProgramError;
+
+    fn try_from(v: &Vault) -> Result<Self, Self::Error> {
+        Ok(VaultV1Data {
+            key: v.key.clone(),
+            token_program: v.token_program,
+            fraction_mint: v.fraction_mint,
+            authority: v.authority,
+            fraction_treasury: v.fraction_treasury,
+            redeem_treasury: v.redeem_treasury,
+            allow_further_share_creation: v.allow_further_share_creation,
+            pricing_lookup_address: v.pricing_lookup_address,
+            token_type_count: v
+                .token_type_count
+                .try_into()
+                .map_err(|_| VaultError::NumericalOverflowError)?,
+            state: v.state.clone(),
+            locked_price_per_share: v.locked_price_per_share,
+        })
     }
 }
 
@@ -86,22 +196,89 @@ pub struct SafetyDepositBox {
     /// Account that stores the tokens under management
     pub store: Pubkey,
     /// the order in the array of registries
-    pub order: u8,
+    /// Stored as a u8 on SafetyDepositBoxV1 accounts and a u32 on SafetyDepositBoxV2 accounts.
+    pub order: u32,
 }
 
 impl SafetyDepositBox {
     pub fn from_account_info(a: &AccountInfo) -> Result<SafetyDepositBox, ProgramError> {
-        let sd: SafetyDepositBox = try_from_slice_checked(
-            &a.data.borrow_mut(),
-            Key::SafetyDepositBoxV1,
-            MAX_SAFETY_DEPOSIT_SIZE,
-        )?;
+        SafetyDepositBox::from_data(&a.data.borrow())
+    }
 
-        Ok(sd)
+    /// Reads either safety deposit box version. Uninitialized accounts are treated as
+    /// SafetyDepositBoxV2 if they were allocated with MAX_SAFETY_DEPOSIT_V2_SIZE.
+    pub fn from_data(data: &[u8]) -> Result<SafetyDepositBox, ProgramError> {
+        if data[0] == Key::SafetyDepositBoxV2 as u8
+            || (data[0] == Key::Uninitialized as u8 && data.len() == MAX_SAFETY_DEPOSIT_V2_SIZE)
+        {
+            let sd: SafetyDepositBox =
+                try_from_slice_checked(data, Key::SafetyDepositBoxV2, MAX_SAFETY_DEPOSIT_V2_SIZE)?;
+
+            Ok(sd)
+        } else {
+            let sd: SafetyDepositBoxV1Data =
+                try_from_slice_checked(data, Key::SafetyDepositBoxV1, MAX_SAFETY_DEPOSIT_SIZE)?;
+
+            Ok(sd.into())
+        }
+    }
+
+    /// Writes the box back using the layout matching its key.
+    pub fn save(&self, a: &AccountInfo) -> ProgramResult {
+        if self.key == Key::SafetyDepositBoxV2 {
+            self.serialize(&mut *a.data.borrow_mut())?;
+        } else {
+            SafetyDepositBoxV1Data::try_from(self)?.serialize(&mut *a.data.borrow_mut())?;
+        }
+        Ok(())
     }
 
-    pub fn get_order(a: &AccountInfo) -> u8 {
-        a.data.borrow()[97]
+    pub fn get_order(a: &AccountInfo) -> u32 {
+        let data = a.data.borrow();
+        if data[0] == Key::SafetyDepositBoxV2 as u8 {
+            u32::from_le_bytes(*array_ref![data, SAFETY_DEPOSIT_ORDER_POSITION, 4])
+        } else {
+            data[SAFETY_DEPOSIT_ORDER_POSITION] as u32
+        }
+    }
+}
+
+/// On-chain layout of a SafetyDepositBoxV1 account, which only has room for a u8 order.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct SafetyDepositBoxV1Data {
+    key: Key,
+    vault: Pubkey,
+    token_mint: Pubkey,
+    store: Pubkey,
+    order: u8,
+}
+
+impl From<SafetyDepositBoxV1Data> for SafetyDepositBox {
+    fn from(sd: SafetyDepositBoxV1Data) -> Self {
+        SafetyDepositBox {
+            key: sd.key,
+            vault: sd.vault,
+            token_mint: sd.token_mint,
+            store: sd.store,
+            order: sd.order as u32,
+        }
+    }
+}
+
+impl TryFrom<&SafetyDepositBox> for SafetyDepositBoxV1Data {
+    type Error = ProgramError;
+
+    fn try_from(sd: &SafetyDepositBox) -> Result<Self, Self::Error> {
+        Ok(SafetyDepositBoxV1Data {
+            key: sd.key.clone(),
+            vault: sd.vault,
+            token_mint: sd.token_mint,
+            store: sd.store,
+            order: sd
+                .order
+                .try_into()
+                .map_err(|_| VaultError::NumericalOverflowError)?,
+        })
     }
 }
 
@@ -128,3 +305,135 @@ impl ExternalPriceAccount {
         Ok(sd)
     }
 }
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Default)]
+pub struct PendingPriceUpdate {
+    pub price_per_share: u64,
+    pub price_mint: Pubkey,
+    pub allowed_to_combine: bool,
+    /// Unix timestamp after which this update replaces the current values
+    pub effective_at: UnixTimestamp,
+}
+
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Default)]
+pub struct PriceTimelock {
+    /// Seconds an update waits in pending_update before it takes effect. Zero applies updates immediately.
+    pub delay_seconds: UnixTimestamp,
+    /// If set, the most price_per_share may move in a single update, relative to the current price.
+    pub max_change_basis_points: Option<u16>,
+    /// Update waiting for its timelock to pass. Queuing another update replaces it.
+    pub pending_update: Option<PendingPriceUpdate>,
+}
+
+/// An ExternalPriceAccount with an optional timelock on updates, so that shareholders have
+/// a window to react before a price or permission change can be used to combine a vault.
+/// Its first fields share the layout of an ExternalPriceAccount.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize)]
+pub struct ExternalPriceAccountV2 {
+    pub key: Key,
+    pub price_per_share: u64,
+    /// Mint of the currency we are pricing the shares against, should be same as redeem_treasury.
+    pub price_mint: Pubkey,
+    /// Whether or not combination has been allowed for this vault.
+    pub allowed_to_combine: bool,
+    pub timelock: PriceTimelock,
+}
+
+impl ExternalPriceAccountV2 {
+    pub fn from_account_info(a: &AccountInfo) -> Result<ExternalPriceAccountV2, ProgramError> {
+        ExternalPriceAccountV2::from_data(&a.data.borrow())
+    }
+
+    /// Reads either price account version. V1 accounts come back with an empty timelock.
+    /// Uninitialized accounts are treated as V2 if they were allocated with MAX_EXTERNAL_ACCOUNT_V2_SIZE.
+    pub fn from_data(data: &[u8]) -> Result<ExternalPriceAccountV2, ProgramError> {
+        if data[0] == Key::ExternalAccountKeyV2 as u8
+            || (data[0] == Key::Uninitialized as u8 && data.len() == MAX_EXTERNAL_ACCOUNT_V2_SIZE)
+        {
+            let epa: ExternalPriceAccountV2 = try_from_slice_checked(
+                data,
+                Key::ExternalAccountKeyV2,
+                MAX_EXTERNAL_ACCOUNT_V2_SIZE,
+            )?;
+
+            Ok(epa)
+        } else {
+            let epa: ExternalPriceAccount =
+                try_from_slice_checked(data, Key::ExternalAccountKeyV1, MAX_EXTERNAL_ACCOUNT_SIZE)?;
+
+            Ok(ExternalPriceAccountV2 {
+                key: epa.key,
+                price_per_share: epa.price_per_share,
+                price_mint: epa.price_mint,
+                allowed_to_combine: epa.allowed_to_combine,
+                timelock: PriceTimelock::default(),
+            })
+        }
+    }
+
+    /// Writes the account back using the layout matching its key.
+    pub fn save(&self, a: &AccountInfo) -> ProgramResult {
+        if self.key == Key::ExternalAccountKeyV2 {
+            self.serialize(&mut *a.data.borrow_mut())?;
+        } else {
+            ExternalPriceAccount {
+                key: self.key.clone(),
+                price_per_share: self.price_per_share,
+                price_mint: self.price_mint,
+                allowed_to_combine: self.allowed_to_combine,
+            }
+            .serialize(&mut *a.data.borrow_mut())?;
+        }
+        Ok(())
+    }
+
+    /// True if updates to this account have to wait out a delay or are bounded in size.
+    pub fn is_timelocked(&self) -> bool {
+        self.timelock.delay_seconds > 0 || self.timelock.max_change_basis_points.is_some()
+    }
+
+    /// True if an update would let the vault be combined on different terms than it can be now,
+    /// either by allowing combination or by pricing shares in another mint.
+    pub fn changes_combine_terms(&self, price_mint: &Pubkey, allowed_to_combine: bool) -> bool {
+        self.price_mint != *price_mint || (allowed_to_combine && !self.allowed_to_combine)
+    }
+
+    /// Promotes the pending update, if any, once its effective timestamp has passed.
+    pub fn mature(&mut self, now: UnixTimestamp) {
+        if let Some(pending) = &self.timelock.pending_update {
+            if now >= pending.effective_at {
+                self.price_per_share = pending.price_per_share;
+                self.price_mint = pending.price_mint;
+                self.allowed_to_combine = pending.allowed_to_combine;
+                self.timelock.pending_update = None;
+            }
+        }
+    }
+
+    /// Checks a new price against max_change_basis_points, relative to the current price.
+    /// A current price of zero can't move at all, so the first price has to be set before the bound.
+    pub fn assert_price_change_allowed(&self, new_price_per_share: u64) -> ProgramResult {
+        if let Some(max_change_basis_points) = self.timelock.max_change_basis_points {
+            let current = self.price_per_share as u128;
+            let new = new_price_per_share as u128;
+            let change = if new > current {
+                new - current
+            } else {
+                current - new
+            };
+            let max_change = current
+                .checked_mul(max_change_basis_points as u128)
+                .ok_or(VaultError::NumericalOverflowError)?
+                / BASIS_POINTS_DENOMINATOR;
+
+            if change > max_change {
+                return Err(VaultError::PriceChangeExceedsMaximum.into());
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -3,22 +3,44 @@ use {
         error::VaultError,
         state::{Key, Vault},
     },
-    borsh::BorshDeserialize,
+    borsh::{BorshDeserialize, BorshSerialize},
     solana_program::{
         account_info::AccountInfo,
         borsh::try_from_slice_unchecked,
         entrypoint::ProgramResult,
+        instruction::{AccountMeta, Instruction},
         msg,
         program::{invoke, invoke_signed},
         program_error::ProgramError,
         program_pack::{IsInitialized, Pack},
         pubkey::Pubkey,
         system_instruction,
-        sysvar::{rent::Rent, Sysvar},
+        sysvar::{self, rent::Rent, Sysvar},
     },
     std::convert::TryInto,
 };
 
+/// The token metadata program depends on this crate, so its id and the parts of its
+/// instruction layout we need are mirrored here rather than imported.
+pub mod spl_token_metadata {
+    solana_program::declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+    /// Index of CreateMetadataAccount in the token metadata instruction enum
+    pub const CREATE_METADATA_ACCOUNT: u8 = 0;
+}
+
+/// Mirror of token metadata's CreateMetadataAccountArgs with no creators.
+#[derive(BorshSerialize)]
+struct CreateMetadataAccountArgs {
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    /// Always None, which borsh writes as a single zero byte whatever the inner type.
+    creators: Option<()>,
+    is_mutable: bool,
+}
+
 /// assert initialized account
 pub fn assert_initialized<T: Pack + IsInitialized>(
     account_info: &AccountInfo,
@@ -76,6 +98,16 @@ pub fn assert_token_program_matches_package(token_program_info: &AccountInfo) ->
     Ok(())
 }
 
+pub fn assert_token_metadata_program_matches_package(
+    token_metadata_program_info: &AccountInfo,
+) -> ProgramResult {
+    if *token_metadata_program_info.key != spl_token_metadata::id() {
+        return Err(VaultError::InvalidTokenMetadataProgram.into());
+    }
+
+    Ok(())
+}
+
 /// Create account almost from scratch, lifted from
 /// https://github.com/solana-labs/solana-program-library/blob/7d4873c61721aca25464d42cc5ef651a7923ca79/associated-token-account/program/src/processor.rs#L51-L98
 #[inline(always)]
@@ -201,6 +233,102 @@ pub fn spl_token_burn(params: TokenBurnParams<'_, '_>) -> ProgramResult {
     result.map_err(|_| VaultError::TokenBurnFailed.into())
 }
 
+/// Issue a spl_token `CloseAccount` instruction.
+#[inline(always)]
+pub fn spl_token_close_account(params: TokenCloseAccountParams<'_, '_>) -> ProgramResult {
+    let TokenCloseAccountParams {
+        account,
+        destination,
+        authority,
+        token_program,
+        authority_signer_seeds,
+    } = params;
+    let result = invoke_signed(
+        &spl_token::instruction::close_account(
+            token_program.key,
+            account.key,
+            destination.key,
+            authority.key,
+            &[],
+        )?,
+        &[account, destination, authority, token_program],
+        &[authority_signer_seeds],
+    );
+    result.map_err(|_| VaultError::TokenCloseAccountFailed.into())
+}
+
+/// Zero out a program-owned account and move all of its lamports to the receiver.
+pub fn close_program_account(account: &AccountInfo, receiver: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **receiver.lamports.borrow_mut() = receiver
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(VaultError::NumericalOverflowError)?;
+    **account.lamports.borrow_mut() = 0;
+    account.data.borrow_mut().fill(0);
+
+    Ok(())
+}
+
+/// Issue a token metadata `CreateMetadataAccount` instruction for a fraction mint. The authority
+/// signs as mint authority. The metadata is immutable, since nothing can sign as its update
+/// authority later on.
+pub fn create_fraction_metadata(params: CreateFractionMetadataParams<'_, '_>) -> ProgramResult {
+    let CreateFractionMetadataParams {
+        metadata,
+        mint,
+        authority,
+        update_authority,
+        payer,
+        token_metadata_program,
+        system_program,
+        rent,
+        name,
+        symbol,
+        uri,
+        authority_signer_seeds,
+    } = params;
+
+    let mut data = vec![spl_token_metadata::CREATE_METADATA_ACCOUNT];
+    CreateMetadataAccountArgs {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        is_mutable: false,
+    }
+    .serialize(&mut data)?;
+
+    let result = invoke_signed(
+        &Instruction {
+            program_id: *token_metadata_program.key,
+            accounts: vec![
+                AccountMeta::new(*metadata.key, false),
+                AccountMeta::new_readonly(*mint.key, false),
+                AccountMeta::new_readonly(*authority.key, true),
+                AccountMeta::new(*payer.key, true),
+                AccountMeta::new_readonly(*update_authority.key, false),
+                AccountMeta::new_readonly(*system_program.key, false),
+                AccountMeta::new_readonly(sysvar::rent::id(), false),
+            ],
+            data,
+        },
+        &[
+            metadata,
+            mint,
+            authority,
+            payer,
+            update_authority,
+            system_program,
+            rent,
+            token_metadata_program,
+        ],
+        &[authority_signer_seeds],
+    );
+    result.map_err(|_| VaultError::TokenMetadataCreationFailed.into())
+}
+
 ///TokenTransferParams
 pub struct TokenTransferParams<'a: 'b, 'b> {
     /// source
@@ -247,6 +375,47 @@ pub struct TokenBurnParams<'a: 'b, 'b> {
     pub token_program: AccountInfo<'a>,
 }
 
+/// TokenCloseAccountParams
+pub struct TokenCloseAccountParams<'a: 'b, 'b> {
+    /// account
+    pub account: AccountInfo<'a>,
+    /// destination
+    pub destination: AccountInfo<'a>,
+    /// authority
+    pub authority: AccountInfo<'a>,
+    /// authority_signer_seeds
+    pub authority_signer_seeds: &'b [&'b [u8]],
+    /// token_program
+    pub token_program: AccountInfo<'a>,
+}
+/// CreateFractionMetadataParams
+pub struct CreateFractionMetadataParams<'a: 'b, 'b> {
+    /// metadata
+    pub metadata: AccountInfo<'a>,
+    /// mint
+    pub mint: AccountInfo<'a>,
+    /// authority
+    pub authority: AccountInfo<'a>,
+    /// update_authority
+    pub update_authority: AccountInfo<'a>,
+    /// payer
+    pub payer: AccountInfo<'a>,
+    /// token_metadata_program
+    pub token_metadata_program: AccountInfo<'a>,
+    /// system_program
+    pub system_program: AccountInfo<'a>,
+    /// rent
+    pub rent: AccountInfo<'a>,
+    /// name
+    pub name: String,
+    /// symbol
+    pub symbol: String,
+    /// uri
+    pub uri: String,
+    /// authority_signer_seeds
+    pub authority_signer_seeds: &'b [&'b [u8]],
+}
+
 pub fn try_from_slice_checked<T: BorshDeserialize>(
     data: &[u8],
     data_type: Key,
@@ -1,3 +1,5 @@
+mod scenario;
+
 use {
     clap::{crate_description, crate_name, crate_version, App, Arg, ArgMatches, SubCommand},
     solana_clap_utils::{
@@ -5,7 +7,11 @@ use {
         input_validators::{is_url, is_valid_pubkey, is_valid_signer},
     },
     solana_client::rpc_client::RpcClient,
-    solana_program::{borsh::try_from_slice_unchecked, program_pack::Pack},
+    solana_program::{
+        borsh::try_from_slice_unchecked,
+        program_pack::Pack,
+        sysvar::clock::{self, Clock},
+    },
     solana_sdk::{
         pubkey::Pubkey,
         signature::{read_keypair_file, Keypair, Signer},
@@ -19,15 +25,20 @@ use {
     spl_token_vault::{
         instruction::{
             create_activate_vault_instruction, create_add_shares_instruction,
-            create_add_token_to_inactive_vault_instruction, create_combine_vault_instruction,
+            create_add_token_to_inactive_vault_instruction, create_close_vault_instruction,
+            create_combine_vault_instruction, create_fraction_metadata_instruction,
             create_init_vault_instruction, create_mint_shares_instruction,
-            create_redeem_shares_instruction, create_update_external_price_account_instruction,
-            create_withdraw_shares_instruction, create_withdraw_tokens_instruction,
+            create_redeem_shares_instruction,
+            create_set_external_price_account_timelock_instruction,
+            create_update_external_price_account_instruction, create_withdraw_shares_instruction,
+            create_withdraw_tokens_instruction,
         },
         state::{
-            ExternalPriceAccount, SafetyDepositBox, Vault, VaultState, MAX_EXTERNAL_ACCOUNT_SIZE,
-            MAX_VAULT_SIZE, PREFIX,
+            ExternalPriceAccount, ExternalPriceAccountV2, SafetyDepositBox, Vault, VaultState,
+            MAX_EXTERNAL_ACCOUNT_SIZE, MAX_EXTERNAL_ACCOUNT_V2_SIZE, MAX_VAULT_SIZE,
+            MAX_VAULT_V2_SIZE, PREFIX,
         },
+        utils::spl_token_metadata,
     },
     std::str::FromStr,
 };
@@ -49,6 +60,11 @@ fn initialize_vault(app_matches: &ArgMatches, payer: Keypair, client: RpcClient)
     let fraction_treasury = Keypair::new();
     let vault = Keypair::new();
     let allow_further_share_creation = app_matches.is_present("allow_further_share_creation");
+    let vault_size = if app_matches.is_present("v1") {
+        MAX_VAULT_SIZE
+    } else {
+        MAX_VAULT_V2_SIZE
+    };
 
     let seeds = &[PREFIX.as_bytes(), program_key.as_ref()];
     let (authority, _) = Pubkey::find_program_address(seeds, &program_key);
@@ -85,9 +101,9 @@ fn initialize_vault(app_matches: &ArgMatches, payer: Keypair, client: RpcClient)
             &payer.pubkey(),
             &vault.pubkey(),
             client
-                .get_minimum_balance_for_rent_exemption(MAX_VAULT_SIZE)
+                .get_minimum_balance_for_rent_exemption(vault_size)
                 .unwrap(),
-            MAX_VAULT_SIZE as u64,
+            vault_size as u64,
             &program_key,
         ),
         initialize_mint(
@@ -151,6 +167,13 @@ fn rewrite_price_account(app_matches: &ArgMatches, payer: Keypair, client: RpcCl
         .unwrap();
     let allowed_to_combine = app_matches.is_present("allowed_to_combine");
     let already_created = app_matches.is_present("already_created");
+    let timelock_seconds = app_matches
+        .value_of("timelock_seconds")
+        .map(|v| v.parse::<i64>().unwrap());
+    let max_change_basis_points = app_matches
+        .value_of("max_change_basis_points")
+        .map(|v| v.parse::<u16>().unwrap());
+    let timelocked = timelock_seconds.is_some() || max_change_basis_points.is_some();
     let mut signers = vec![&payer, &external_account];
 
     let mut instructions = vec![];
@@ -186,24 +209,50 @@ fn rewrite_price_account(app_matches: &ArgMatches, payer: Keypair, client: RpcCl
     };
 
     if !already_created {
+        // Only the larger V2 layout has room for a timelock.
+        let size = if timelocked {
+            MAX_EXTERNAL_ACCOUNT_V2_SIZE
+        } else {
+            MAX_EXTERNAL_ACCOUNT_SIZE
+        };
         instructions.push(create_account(
             &payer.pubkey(),
             &external_account.pubkey(),
-            client
-                .get_minimum_balance_for_rent_exemption(MAX_EXTERNAL_ACCOUNT_SIZE)
-                .unwrap(),
-            MAX_EXTERNAL_ACCOUNT_SIZE as u64,
+            client.get_minimum_balance_for_rent_exemption(size).unwrap(),
+            size as u64,
             &program_key,
         ));
     }
 
-    instructions.push(create_update_external_price_account_instruction(
-        program_key,
-        external_account.pubkey(),
-        price_per_share,
-        price_mint,
-        allowed_to_combine,
-    ));
+    if timelocked && !already_created {
+        // Set the first price before the timelock exists so it applies immediately.
+        instructions.push(create_update_external_price_account_instruction(
+            program_key,
+            external_account.pubkey(),
+            price_per_share,
+            price_mint,
+            allowed_to_combine,
+        ));
+    }
+
+    if timelocked {
+        instructions.push(create_set_external_price_account_timelock_instruction(
+            program_key,
+            external_account.pubkey(),
+            timelock_seconds.unwrap_or(0),
+            max_change_basis_points,
+        ));
+    }
+
+    if !timelocked || already_created {
+        instructions.push(create_update_external_price_account_instruction(
+            program_key,
+            external_account.pubkey(),
+            price_per_share,
+            price_mint,
+            allowed_to_combine,
+        ));
+    }
 
     let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
     let recent_blockhash = client.get_recent_blockhash().unwrap().0;
@@ -362,7 +411,7 @@ fn activate_vault(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) -
         .unwrap();
     let vault_key = pubkey_of(app_matches, "vault_address").unwrap();
     let vault_account = client.get_account(&vault_key).unwrap();
-    let vault: Vault = try_from_slice_unchecked(&vault_account.data).unwrap();
+    let vault = Vault::from_data(&vault_account.data).unwrap();
 
     let seeds = &[PREFIX.as_bytes(), program_key.as_ref()];
     let (mint_authority, _) = Pubkey::find_program_address(seeds, &program_key);
@@ -384,12 +433,12 @@ fn activate_vault(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) -
     transaction.sign(&signers, recent_blockhash);
     client.send_and_confirm_transaction(&transaction).unwrap();
     let updated_vault_data = client.get_account(&vault_key).unwrap();
-    let updated_vault: Vault = try_from_slice_unchecked(&updated_vault_data.data).unwrap();
+    let updated_vault = Vault::from_data(&updated_vault_data.data).unwrap();
     if updated_vault.state == VaultState::Active {
-        println!("Activated vault.");
+        eprintln!("Activated vault.");
         Some(vault_key)
     } else {
-        println!("Failed to update vault.");
+        eprintln!("Failed to update vault.");
         None
     }
 }
@@ -416,10 +465,17 @@ fn combine_vault(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) ->
 
     let vault_key = pubkey_of(app_matches, "vault_address").unwrap();
     let vault_account = client.get_account(&vault_key).unwrap();
-    let vault: Vault = try_from_slice_unchecked(&vault_account.data).unwrap();
+    let vault = Vault::from_data(&vault_account.data).unwrap();
     let external_price_account = client.get_account(&vault.pricing_lookup_address).unwrap();
-    let external: ExternalPriceAccount =
-        try_from_slice_unchecked(&external_price_account.data).unwrap();
+    let mut external = ExternalPriceAccountV2::from_data(&external_price_account.data).unwrap();
+    // Combination uses the price as of now, including any queued update whose delay has passed.
+    let clock_account = client.get_account(&clock::id()).unwrap();
+    let clock: Clock = bincode::deserialize(&clock_account.data).unwrap();
+    external.mature(clock.unix_timestamp);
+    if !external.allowed_to_combine {
+        eprintln!("External price account does not allow combination yet.");
+        return None;
+    }
     let payment_account = Keypair::new();
 
     let seeds = &[PREFIX.as_bytes(), program_key.as_ref()];
@@ -538,12 +594,12 @@ fn combine_vault(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) ->
     transaction.sign(&signers, recent_blockhash);
     client.send_and_confirm_transaction(&transaction).unwrap();
     let updated_vault_data = client.get_account(&vault_key).unwrap();
-    let updated_vault: Vault = try_from_slice_unchecked(&updated_vault_data.data).unwrap();
+    let updated_vault = Vault::from_data(&updated_vault_data.data).unwrap();
     if updated_vault.state == VaultState::Combined {
-        println!("Combined vault.");
+        eprintln!("Combined vault.");
         Some(vault_key)
     } else {
-        println!("Failed to combined vault.");
+        eprintln!("Failed to combined vault.");
         None
     }
 }
@@ -565,7 +621,7 @@ fn redeem_shares(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) ->
     let outstanding_shares: Account =
         Account::unpack_unchecked(&outstanding_shares_account.data).unwrap();
     let vault_account = client.get_account(&vault_key).unwrap();
-    let vault: Vault = try_from_slice_unchecked(&vault_account.data).unwrap();
+    let vault = Vault::from_data(&vault_account.data).unwrap();
     let redeem_treasury_info = client.get_account(&vault.redeem_treasury).unwrap();
     let redeem_treasury: Account = Account::unpack_unchecked(&redeem_treasury_info.data).unwrap();
 
@@ -649,12 +705,11 @@ fn withdraw_tokens(app_matches: &ArgMatches, payer: Keypair, client: RpcClient)
 
     let safety_deposit_key = pubkey_of(app_matches, "safety_deposit_address").unwrap();
     let safety_deposit_account = client.get_account(&safety_deposit_key).unwrap();
-    let safety_deposit: SafetyDepositBox =
-        try_from_slice_unchecked(&safety_deposit_account.data).unwrap();
+    let safety_deposit = SafetyDepositBox::from_data(&safety_deposit_account.data).unwrap();
     let store_account = client.get_account(&safety_deposit.store).unwrap();
     let store: Account = Account::unpack_unchecked(&store_account.data).unwrap();
     let vault_account = client.get_account(&safety_deposit.vault).unwrap();
-    let vault: Vault = try_from_slice_unchecked(&vault_account.data).unwrap();
+    let vault = Vault::from_data(&vault_account.data).unwrap();
     let amount: u64 = app_matches
         .value_of("amount")
         .unwrap_or(&store.amount.to_string())
@@ -722,7 +777,7 @@ fn mint_shares(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) -> P
 
     let vault_key = pubkey_of(app_matches, "vault_address").unwrap();
     let vault_account = client.get_account(&vault_key).unwrap();
-    let vault: Vault = try_from_slice_unchecked(&vault_account.data).unwrap();
+    let vault = Vault::from_data(&vault_account.data).unwrap();
 
     let signers = vec![&payer, &vault_authority];
     let seeds = &[PREFIX.as_bytes(), program_key.as_ref()];
@@ -766,7 +821,7 @@ fn withdraw_shares(app_matches: &ArgMatches, payer: Keypair, client: RpcClient)
 
     let vault_key = pubkey_of(app_matches, "vault_address").unwrap();
     let vault_account = client.get_account(&vault_key).unwrap();
-    let vault: Vault = try_from_slice_unchecked(&vault_account.data).unwrap();
+    let vault = Vault::from_data(&vault_account.data).unwrap();
     let number_of_shares: u64 = app_matches
         .value_of("number_of_shares")
         .unwrap_or("100")
@@ -838,7 +893,7 @@ fn add_shares(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) -> Pu
 
     let vault_key = pubkey_of(app_matches, "vault_address").unwrap();
     let vault_account = client.get_account(&vault_key).unwrap();
-    let vault: Vault = try_from_slice_unchecked(&vault_account.data).unwrap();
+    let vault = Vault::from_data(&vault_account.data).unwrap();
     let number_of_shares: u64 = app_matches
         .value_of("number_of_shares")
         .unwrap_or("100")
@@ -879,8 +934,122 @@ fn add_shares(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) -> Pu
     vault.fraction_treasury
 }
 
+fn create_fraction_metadata(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) -> Pubkey {
+    let program_key = Pubkey::from_str(PROGRAM_PUBKEY).unwrap();
+    let metadata_program_key = spl_token_metadata::id();
+
+    let vault_authority = read_keypair_file(
+        app_matches
+            .value_of("vault_authority")
+            .unwrap_or_else(|| app_matches.value_of("keypair").unwrap()),
+    )
+    .unwrap();
+    let name = app_matches.value_of("name").unwrap().to_owned();
+    let symbol = app_matches.value_of("symbol").unwrap_or("").to_owned();
+    let uri = app_matches.value_of("uri").unwrap().to_owned();
+    let vault_key = pubkey_of(app_matches, "vault_address").unwrap();
+    let vault_account = client.get_account(&vault_key).unwrap();
+    let vault = Vault::from_data(&vault_account.data).unwrap();
+
+    let seeds = &[PREFIX.as_bytes(), program_key.as_ref(), vault_key.as_ref()];
+    let (mint_authority, _) = Pubkey::find_program_address(seeds, &program_key);
+
+    let metadata_seeds = &[
+        "metadata".as_bytes(),
+        metadata_program_key.as_ref(),
+        vault.fraction_mint.as_ref(),
+    ];
+    let (metadata_key, _) = Pubkey::find_program_address(metadata_seeds, &metadata_program_key);
+
+    let instructions = [create_fraction_metadata_instruction(
+        program_key,
+        metadata_key,
+        vault.fraction_mint,
+        vault_key,
+        vault_authority.pubkey(),
+        mint_authority,
+        payer.pubkey(),
+        metadata_program_key,
+        name,
+        symbol,
+        uri,
+    )];
+
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+    let recent_blockhash = client.get_recent_blockhash().unwrap().0;
+    let signers = vec![&payer, &vault_authority];
+
+    transaction.sign(&signers, recent_blockhash);
+    client.send_and_confirm_transaction(&transaction).unwrap();
+    metadata_key
+}
+
+fn close_vault(app_matches: &ArgMatches, payer: Keypair, client: RpcClient) -> Pubkey {
+    let program_key = Pubkey::from_str(PROGRAM_PUBKEY).unwrap();
+
+    let vault_authority = read_keypair_file(
+        app_matches
+            .value_of("vault_authority")
+            .unwrap_or_else(|| app_matches.value_of("keypair").unwrap()),
+    )
+    .unwrap();
+    let vault_key = pubkey_of(app_matches, "vault_address").unwrap();
+    let vault_account = client.get_account(&vault_key).unwrap();
+    let vault = Vault::from_data(&vault_account.data).unwrap();
+    let receiver = pubkey_of(app_matches, "receiver").unwrap_or_else(|| payer.pubkey());
+    let close_vault = !app_matches.is_present("keep_vault");
+
+    let seeds = &[PREFIX.as_bytes(), program_key.as_ref(), vault_key.as_ref()];
+    let (pda_authority, _) = Pubkey::find_program_address(seeds, &program_key);
+
+    let safety_deposit_boxes_and_stores: Vec<(Pubkey, Pubkey)> = app_matches
+        .values_of("safety_deposit_address")
+        .map(|values| {
+            values
+                .map(|value| {
+                    let safety_deposit_key = Pubkey::from_str(value).unwrap();
+                    let safety_deposit_account = client.get_account(&safety_deposit_key).unwrap();
+                    let safety_deposit =
+                        SafetyDepositBox::from_data(&safety_deposit_account.data).unwrap();
+                    (safety_deposit_key, safety_deposit.store)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let external_price_account = app_matches
+        .value_of("external_price_account")
+        .map(|path| read_keypair_file(path).unwrap());
+
+    let instructions = [create_close_vault_instruction(
+        program_key,
+        vault_key,
+        vault_authority.pubkey(),
+        pda_authority,
+        vault.fraction_treasury,
+        vault.redeem_treasury,
+        receiver,
+        vault.pricing_lookup_address,
+        external_price_account.is_some(),
+        safety_deposit_boxes_and_stores,
+        close_vault,
+    )];
+
+    let mut signers = vec![&payer, &vault_authority];
+    if let Some(external_price_account) = &external_price_account {
+        signers.push(external_price_account);
+    }
+
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+    let recent_blockhash = client.get_recent_blockhash().unwrap().0;
+
+    transaction.sign(&signers, recent_blockhash);
+    client.send_and_confirm_transaction(&transaction).unwrap();
+    vault_key
+}
+
 fn main() {
-    let app_matches = App::new(crate_name!())
+    let app = App::new(crate_name!())
         .about(crate_description!())
         .version(crate_version!())
         .arg(
@@ -929,6 +1098,14 @@ fn main() {
                         .takes_value(false)
                         .required(false)
                         .help("Allows further share creation after activation of vault"),
+                )
+                .arg(
+                    Arg::with_name("v1")
+                        .long("v1")
+                        .value_name("V1")
+                        .takes_value(false)
+                        .required(false)
+                        .help("Create a legacy VaultV1, limited to 255 safety deposit boxes"),
                 ),
         )
         .subcommand(
@@ -975,6 +1152,22 @@ fn main() {
                         .takes_value(false)
                         .required(false)
                         .help("If we should skip creation because this account already exists"),
+                )
+                .arg(
+                    Arg::with_name("timelock_seconds")
+                        .long("timelock_seconds")
+                        .value_name("TIMELOCK_SECONDS")
+                        .takes_value(true)
+                        .required(false)
+                        .help("Seconds each price update must wait before it takes effect, can only be raised once set"),
+                )
+                .arg(
+                    Arg::with_name("max_change_basis_points")
+                        .long("max_change_basis_points")
+                        .value_name("MAX_CHANGE_BASIS_POINTS")
+                        .takes_value(true)
+                        .required(false)
+                        .help("Most the price per share may move in one update, in basis points, can only be lowered once set"),
                 ),
         )
         .subcommand(
@@ -1258,7 +1451,139 @@ fn main() {
                                 .takes_value(true)
                                 .help("Pubkey of source shares account"),
                         ))
-        .get_matches();
+        .subcommand(
+            SubCommand::with_name("create_fraction_metadata")
+                .about("Create token metadata for the vault's fraction mint")
+                .arg(
+                    Arg::with_name("vault_authority")
+                        .long("vault_authority")
+                        .value_name("VAULT_AUTHORITY")
+                        .required(false)
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help("Filepath or URL to a keypair, defaults to you otherwise"),
+                )
+                .arg(
+                    Arg::with_name("vault_address")
+                        .long("vault_address")
+                        .value_name("VAULT_ADDRESS")
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("Pubkey of vault"),
+                )
+                .arg(
+                    Arg::with_name("name")
+                        .long("name")
+                        .value_name("NAME")
+                        .required(true)
+                        .takes_value(true)
+                        .help("Name of the fraction token"),
+                )
+                .arg(
+                    Arg::with_name("symbol")
+                        .long("symbol")
+                        .value_name("SYMBOL")
+                        .required(false)
+                        .takes_value(true)
+                        .help("Symbol of the fraction token, defaults to empty"),
+                )
+                .arg(
+                    Arg::with_name("uri")
+                        .long("uri")
+                        .value_name("URI")
+                        .required(true)
+                        .takes_value(true)
+                        .help("URI of the fraction token's JSON metadata"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("close_vault")
+                .about("Close a Deactivated Vault's safety deposit boxes and then the vault itself, reclaiming rent")
+                .arg(
+                    Arg::with_name("vault_authority")
+                        .long("vault_authority")
+                        .value_name("VAULT_AUTHORITY")
+                        .required(false)
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help("Filepath or URL to a keypair, defaults to you otherwise"),
+                )
+                .arg(
+                    Arg::with_name("vault_address")
+                        .long("vault_address")
+                        .value_name("VAULT_ADDRESS")
+                        .required(true)
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("Pubkey of the vault"),
+                )
+                .arg(
+                    Arg::with_name("safety_deposit_address")
+                        .long("safety_deposit_address")
+                        .value_name("SAFETY_DEPOSIT_ADDRESS")
+                        .required(false)
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .multiple(true)
+                        .help("Pubkeys of the safety deposit boxes to close, their stores must be empty. A V1 vault can't tell if any are missing, so pass all of its remaining boxes when closing it"),
+                )
+                .arg(
+                    Arg::with_name("receiver")
+                        .long("receiver")
+                        .value_name("RECEIVER")
+                        .required(false)
+                        .validator(is_valid_pubkey)
+                        .takes_value(true)
+                        .help("Pubkey that receives the reclaimed lamports, defaults to you otherwise"),
+                )
+                .arg(
+                    Arg::with_name("external_price_account")
+                        .long("external_price_account")
+                        .value_name("EXTERNAL_PRICE_ACCOUNT")
+                        .required(false)
+                        .validator(is_valid_signer)
+                        .takes_value(true)
+                        .help("Filepath or URL to the external price account keypair, if it should be closed too"),
+                )
+                .arg(
+                    Arg::with_name("keep_vault")
+                        .long("keep_vault")
+                        .value_name("KEEP_VAULT")
+                        .required(false)
+                        .takes_value(false)
+                        .help("Only close the given safety deposit boxes, for vaults with more boxes than fit in one transaction"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("scenario")
+                .about("Run a vault lifecycle described in a YAML or JSON file, defaults to a local validator")
+                .arg(
+                    Arg::with_name("file")
+                        .long("file")
+                        .value_name("FILE")
+                        .required(true)
+                        .takes_value(true)
+                        .help("Path to the scenario, parsed as JSON if it ends in .json and YAML otherwise"),
+                )
+                .arg(
+                    Arg::with_name("report")
+                        .long("report")
+                        .value_name("REPORT")
+                        .required(false)
+                        .takes_value(true)
+                        .help("Path to write the JSON report to, defaults to stdout"),
+                )
+                .arg(
+                    Arg::with_name("keypair_dir")
+                        .long("keypair_dir")
+                        .value_name("KEYPAIR_DIR")
+                        .required(false)
+                        .takes_value(true)
+                        .help("Directory to write the scenario's generated keypairs to, defaults to a temporary one"),
+                ),
+        );
+    let app_matches = app.clone().get_matches();
 
     let client = RpcClient::new(
         app_matches
@@ -1329,6 +1654,18 @@ fn main() {
                 add_shares(arg_matches, payer, client)
             );
         }
+        ("create_fraction_metadata", Some(arg_matches)) => {
+            println!(
+                "Created fraction metadata {:?}",
+                create_fraction_metadata(arg_matches, payer, client)
+            );
+        }
+        ("close_vault", Some(arg_matches)) => {
+            println!("Closed vault {:?}", close_vault(arg_matches, payer, client));
+        }
+        ("scenario", Some(arg_matches)) => {
+            scenario::run_scenario(&app, arg_matches, payer);
+        }
         _ => unreachable!(),
     }
 }
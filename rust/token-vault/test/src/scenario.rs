@@ -0,0 +1,430 @@
+//! Runs a whole vault lifecycle described in a YAML or JSON file, e.g.
+//!
+//! ```yaml
+//! name: combine and redeem
+//! keypairs: [price_account]
+//! steps:
+//!   - command: external_price_account_rewrite
+//!     args: { external_price_account: $price_account, price_per_share: 1, allowed_to_combine: true }
+//!   - command: init
+//!     args: { external_price_account: $price_account }
+//!     save_as: vault
+//!     expect: { vault: vault, state: Inactive }
+//!   - command: add_token_to_vault
+//!     args: { vault_address: $vault, amount: 1 }
+//!     save_as: box
+//!   - command: activate_vault
+//!     args: { vault_address: $vault, number_of_shares: 100 }
+//!     expect: { vault: vault, state: Active, token_type_count: 1, fraction_supply: 100 }
+//! ```
+//!
+//! Every step is an ordinary subcommand of this client. `$name` refers to a generated
+//! keypair (expanded to its file, which works for both signer and pubkey arguments) or to
+//! the address a previous step saved with `save_as`. Boolean `true` becomes a bare flag.
+//!
+//! The JSON report is the only thing written to stdout, commands log their progress to stderr.
+
+use {
+    super::{
+        activate_vault, add_shares, add_token_to_vault, combine_vault, create_fraction_metadata,
+        initialize_vault, mint_shares, redeem_shares, rewrite_price_account, withdraw_shares,
+        withdraw_tokens,
+    },
+    clap::{App, ArgMatches},
+    serde_derive::{Deserialize, Serialize},
+    solana_client::rpc_client::RpcClient,
+    solana_program::program_pack::Pack,
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{write_keypair_file, Keypair, Signer},
+    },
+    spl_token::state::{Account, Mint},
+    spl_token_vault::state::{Vault, VaultState},
+    std::{
+        collections::BTreeMap,
+        fs,
+        panic::{catch_unwind, AssertUnwindSafe},
+        path::PathBuf,
+        process,
+    },
+};
+
+const LOCAL_VALIDATOR_URL: &str = "http://localhost:8899";
+
+#[derive(Deserialize)]
+struct Scenario {
+    #[serde(default)]
+    name: String,
+    /// Keypairs to generate before the first step, referenced as `$name`.
+    #[serde(default)]
+    keypairs: Vec<String>,
+    steps: Vec<Step>,
+}
+
+#[derive(Deserialize)]
+struct Step {
+    command: String,
+    #[serde(default)]
+    args: BTreeMap<String, ArgValue>,
+    /// Name to store the address the command returns under.
+    save_as: Option<String>,
+    expect: Option<Expectation>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ArgValue {
+    Flag(bool),
+    Number(u64),
+    Text(String),
+}
+
+#[derive(Deserialize)]
+struct Expectation {
+    /// Name of the vault the vault checks below apply to.
+    vault: Option<String>,
+    state: Option<String>,
+    token_type_count: Option<u32>,
+    fraction_supply: Option<u64>,
+    fraction_treasury_balance: Option<u64>,
+    redeem_treasury_balance: Option<u64>,
+    /// Token balances keyed by the name of the token account.
+    #[serde(default)]
+    balances: BTreeMap<String, u64>,
+}
+
+#[derive(Serialize)]
+struct StepReport {
+    step: usize,
+    command: String,
+    save_as: Option<String>,
+    address: Option<String>,
+    passed: bool,
+    error: Option<String>,
+    failed_assertions: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct ScenarioReport {
+    scenario: String,
+    url: String,
+    passed: bool,
+    keys: BTreeMap<String, String>,
+    steps: Vec<StepReport>,
+}
+
+struct Context {
+    keypair_dir: PathBuf,
+    keypair_paths: BTreeMap<String, String>,
+    addresses: BTreeMap<String, Pubkey>,
+}
+
+impl Context {
+    fn resolve(&self, value: &str) -> Result<String, String> {
+        match value.strip_prefix('$') {
+            Some(name) => {
+                if let Some(path) = self.keypair_paths.get(name) {
+                    Ok(path.clone())
+                } else if let Some(address) = self.addresses.get(name) {
+                    Ok(address.to_string())
+                } else {
+                    Err(format!("Unknown key ${}", name))
+                }
+            }
+            None => Ok(value.to_owned()),
+        }
+    }
+
+    fn address(&self, name: &str) -> Result<Pubkey, String> {
+        self.addresses
+            .get(name.trim_start_matches('$'))
+            .copied()
+            .ok_or_else(|| format!("Unknown key {}", name))
+    }
+
+    fn generate_keypair(&mut self, name: &str) -> Result<(), String> {
+        let keypair = Keypair::new();
+        let path = self.keypair_dir.join(format!("{}.json", name));
+        let path = path.to_str().unwrap().to_owned();
+        write_keypair_file(&keypair, &path).map_err(|e| e.to_string())?;
+        self.keypair_paths.insert(name.to_owned(), path);
+        self.addresses.insert(name.to_owned(), keypair.pubkey());
+        Ok(())
+    }
+}
+
+fn parse_scenario(path: &str) -> Result<Scenario, String> {
+    let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    if path.ends_with(".json") {
+        serde_json::from_str(&contents).map_err(|e| e.to_string())
+    } else {
+        serde_yaml::from_str(&contents).map_err(|e| e.to_string())
+    }
+}
+
+fn vault_state_name(state: &VaultState) -> &'static str {
+    match state {
+        VaultState::Inactive => "Inactive",
+        VaultState::Active => "Active",
+        VaultState::Combined => "Combined",
+        VaultState::Deactivated => "Deactivated",
+    }
+}
+
+fn token_balance(client: &RpcClient, key: &Pubkey) -> Result<u64, String> {
+    let account = client.get_account(key).map_err(|e| e.to_string())?;
+    let account = Account::unpack_unchecked(&account.data).map_err(|e| e.to_string())?;
+    Ok(account.amount)
+}
+
+fn check(failures: &mut Vec<String>, what: &str, expected: u64, actual: u64) {
+    if expected != actual {
+        failures.push(format!("{}: expected {}, got {}", what, expected, actual));
+    }
+}
+
+fn check_expectation(
+    expectation: &Expectation,
+    context: &Context,
+    client: &RpcClient,
+) -> Result<Vec<String>, String> {
+    let mut failures = vec![];
+
+    if let Some(vault_name) = &expectation.vault {
+        let vault_key = context.address(vault_name)?;
+        let vault_account = client.get_account(&vault_key).map_err(|e| e.to_string())?;
+        let vault = Vault::from_data(&vault_account.data).map_err(|e| e.to_string())?;
+
+        if let Some(count) = expectation.token_type_count {
+            check(
+                &mut failures,
+                "token_type_count",
+                count as u64,
+                vault.token_type_count as u64,
+            );
+        }
+        if let Some(supply) = expectation.fraction_supply {
+            let mint_account = client
+                .get_account(&vault.fraction_mint)
+                .map_err(|e| e.to_string())?;
+            let mint = Mint::unpack_unchecked(&mint_account.data).map_err(|e| e.to_string())?;
+            check(&mut failures, "fraction_supply", supply, mint.supply);
+        }
+        if let Some(balance) = expectation.fraction_treasury_balance {
+            let actual = token_balance(client, &vault.fraction_treasury)?;
+            check(&mut failures, "fraction_treasury_balance", balance, actual);
+        }
+        if let Some(balance) = expectation.redeem_treasury_balance {
+            let actual = token_balance(client, &vault.redeem_treasury)?;
+            check(&mut failures, "redeem_treasury_balance", balance, actual);
+        }
+        if let Some(state) = &expectation.state {
+            let actual = vault_state_name(&vault.state);
+            if state != actual {
+                failures.push(format!("state: expected {}, got {}", state, actual));
+            }
+        }
+    } else if expectation.state.is_some()
+        || expectation.token_type_count.is_some()
+        || expectation.fraction_supply.is_some()
+        || expectation.fraction_treasury_balance.is_some()
+        || expectation.redeem_treasury_balance.is_some()
+    {
+        return Err("Vault expectations need a vault to check".to_owned());
+    }
+
+    for (name, balance) in &expectation.balances {
+        let actual = token_balance(client, &context.address(name)?)?;
+        check(
+            &mut failures,
+            &format!("balance of {}", name),
+            *balance,
+            actual,
+        );
+    }
+
+    Ok(failures)
+}
+
+fn run_command(
+    command: &str,
+    arg_matches: &ArgMatches,
+    payer: Keypair,
+    client: RpcClient,
+) -> Result<Option<Pubkey>, String> {
+    match command {
+        "init" => Ok(Some(initialize_vault(arg_matches, payer, client))),
+        "external_price_account_rewrite" => {
+            Ok(Some(rewrite_price_account(arg_matches, payer, client)))
+        }
+        "add_token_to_vault" => Ok(Some(add_token_to_vault(arg_matches, payer, client))),
+        "activate_vault" => activate_vault(arg_matches, payer, client)
+            .map(Some)
+            .ok_or_else(|| "Vault was not activated".to_owned()),
+        "combine_vault" => combine_vault(arg_matches, payer, client)
+            .map(Some)
+            .ok_or_else(|| "Vault was not combined".to_owned()),
+        "redeem_shares" => Ok(Some(redeem_shares(arg_matches, payer, client))),
+        "withdraw_tokens" => Ok(Some(withdraw_tokens(arg_matches, payer, client))),
+        "mint_shares" => Ok(Some(mint_shares(arg_matches, payer, client))),
+        "withdraw_shares" => Ok(Some(withdraw_shares(arg_matches, payer, client))),
+        "add_shares" => Ok(Some(add_shares(arg_matches, payer, client))),
+        "create_fraction_metadata" => {
+            Ok(Some(create_fraction_metadata(arg_matches, payer, client)))
+        }
+        _ => Err(format!("{} cannot be used in a scenario", command)),
+    }
+}
+
+fn run_step(
+    app: &App,
+    step: &Step,
+    context: &Context,
+    payer: &Keypair,
+    payer_path: &str,
+    url: &str,
+) -> Result<Option<Pubkey>, String> {
+    let mut argv = vec![
+        "scenario".to_owned(),
+        "--keypair".to_owned(),
+        payer_path.to_owned(),
+        "--url".to_owned(),
+        url.to_owned(),
+        step.command.clone(),
+    ];
+    for (name, value) in &step.args {
+        match value {
+            ArgValue::Flag(true) => argv.push(format!("--{}", name)),
+            ArgValue::Flag(false) => {}
+            ArgValue::Number(number) => {
+                argv.push(format!("--{}", name));
+                argv.push(number.to_string());
+            }
+            ArgValue::Text(text) => {
+                argv.push(format!("--{}", name));
+                argv.push(context.resolve(text)?);
+            }
+        }
+    }
+
+    let app_matches = app
+        .clone()
+        .get_matches_from_safe(argv)
+        .map_err(|e| e.message)?;
+    let arg_matches = match app_matches.subcommand() {
+        (_, Some(arg_matches)) => arg_matches,
+        _ => return Err(format!("Unknown command {}", step.command)),
+    };
+
+    // Every command signs and sends its own transaction and panics on failure.
+    let payer = Keypair::from_bytes(&payer.to_bytes()).unwrap();
+    let client = RpcClient::new(url.to_owned());
+    catch_unwind(AssertUnwindSafe(|| {
+        run_command(&step.command, arg_matches, payer, client)
+    }))
+    .unwrap_or_else(|panic| {
+        Err(panic
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| panic.downcast_ref::<&str>().map(|s| (*s).to_owned()))
+            .unwrap_or_else(|| "Command panicked".to_owned()))
+    })
+}
+
+pub fn run_scenario(app: &App, app_matches: &ArgMatches, payer: Keypair) {
+    let path = app_matches.value_of("file").unwrap();
+    let url = app_matches
+        .value_of("json_rpc_url")
+        .unwrap_or(LOCAL_VALIDATOR_URL)
+        .to_owned();
+    let payer_path = app_matches.value_of("keypair").unwrap();
+
+    let scenario = match parse_scenario(path) {
+        Ok(scenario) => scenario,
+        Err(e) => {
+            eprintln!("Could not read scenario {}: {}", path, e);
+            process::exit(1);
+        }
+    };
+
+    let keypair_dir = match app_matches.value_of("keypair_dir") {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            std::env::temp_dir().join(format!("token-vault-scenario-{}", Keypair::new().pubkey()))
+        }
+    };
+    fs::create_dir_all(&keypair_dir).unwrap();
+
+    let mut context = Context {
+        keypair_dir,
+        keypair_paths: BTreeMap::new(),
+        addresses: BTreeMap::new(),
+    };
+    context.addresses.insert("payer".to_owned(), payer.pubkey());
+    for name in &scenario.keypairs {
+        context.generate_keypair(name).unwrap();
+    }
+
+    let client = RpcClient::new(url.clone());
+    let mut steps = vec![];
+    let mut passed = true;
+    for (i, step) in scenario.steps.iter().enumerate() {
+        let mut report = StepReport {
+            step: i,
+            command: step.command.clone(),
+            save_as: step.save_as.clone(),
+            address: None,
+            passed: false,
+            error: None,
+            failed_assertions: vec![],
+        };
+
+        match run_step(app, step, &context, &payer, payer_path, &url) {
+            Ok(address) => {
+                report.address = address.map(|a| a.to_string());
+                if let (Some(name), Some(address)) = (&step.save_as, address) {
+                    context.addresses.insert(name.clone(), address);
+                }
+                match step
+                    .expect
+                    .as_ref()
+                    .map(|e| check_expectation(e, &context, &client))
+                {
+                    Some(Ok(failures)) => report.failed_assertions = failures,
+                    Some(Err(e)) => report.error = Some(e),
+                    None => {}
+                }
+            }
+            Err(e) => report.error = Some(e),
+        }
+
+        report.passed = report.error.is_none() && report.failed_assertions.is_empty();
+        passed = report.passed;
+        steps.push(report);
+        // Later steps depend on the keys earlier ones produce, so stop at the first failure.
+        if !passed {
+            break;
+        }
+    }
+
+    let report = ScenarioReport {
+        scenario: scenario.name,
+        url,
+        passed,
+        keys: context
+            .addresses
+            .iter()
+            .map(|(name, key)| (name.clone(), key.to_string()))
+            .collect(),
+        steps,
+    };
+    let output = serde_json::to_string_pretty(&report).unwrap();
+    match app_matches.value_of("report") {
+        Some(report_path) => fs::write(report_path, output).unwrap(),
+        None => println!("{}", output),
+    }
+
+    if !passed {
+        process::exit(1);
+    }
+}